@@ -1 +1,98 @@
+//! Benchmarks. Run with `cargo bench`.
+//!
+//! These use a plain timing loop rather than the unstable `test` crate so
+//! they run on stable Rust.
 
+extern crate civil;
+
+use civil::calc::linalg::{reverse_cuthill_mckee, Matrix, Skyline};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// Run `f` repeatedly for about a second and report the mean time per run
+fn bench<T, F: FnMut() -> T>(name: &str, mut f: F) {
+    let mut runs = 0u32;
+    let start = Instant::now();
+    while runs == 0 || start.elapsed() < Duration::from_secs(1) {
+        black_box(f());
+        runs += 1;
+    }
+    println!(
+        "{:<40} {:>12.3?} per run ({} runs)",
+        name,
+        start.elapsed() / runs,
+        runs
+    );
+}
+
+// Pairs of nodes joined by springs in an n by n grid, numbered in a
+// scrambled order like a mesh from a drawing often is
+fn grid(n: usize) -> Vec<(usize, usize)> {
+    let scramble = |i: usize| (i * 7919) % (n * n);
+    let mut pairs = Vec::new();
+    for r in 0..n {
+        for c in 0..n {
+            if c + 1 < n {
+                pairs.push((scramble(r * n + c), scramble(r * n + c + 1)));
+            }
+            if r + 1 < n {
+                pairs.push((scramble(r * n + c), scramble((r + 1) * n + c)));
+            }
+        }
+    }
+    pairs
+}
+
+// Assemble a stiffness matrix, springs to ground included so it can be
+// solved
+fn assemble(size: usize, pairs: &[(usize, usize)]) -> (Skyline, Matrix) {
+    let mut s = Skyline::from_pattern(size, pairs);
+    let mut m = Matrix::zeros(size, size);
+    for i in 0..size {
+        s.add(i, i, 0.1).unwrap();
+        m[(i, i)] += 0.1;
+    }
+    for &(i, j) in pairs.iter() {
+        s.add(i, i, 1.0).unwrap();
+        s.add(j, j, 1.0).unwrap();
+        s.add(i, j, -1.0).unwrap();
+        m[(i, i)] += 1.0;
+        m[(j, j)] += 1.0;
+        m[(i, j)] -= 1.0;
+        m[(j, i)] -= 1.0;
+    }
+    (s, m)
+}
+
+fn main() {
+    let n = 30;
+    let size = n * n;
+    let pairs = grid(n);
+    let order = reverse_cuthill_mckee(size, &pairs);
+    let mut new = vec![0; size];
+    for (k, old) in order.iter().enumerate() {
+        new[*old] = k;
+    }
+    let renumbered: Vec<(usize, usize)> = pairs.iter().map(|(i, j)| (new[*i], new[*j])).collect();
+    let (scrambled, dense) = assemble(size, &pairs);
+    let (skyline, _) = assemble(size, &renumbered);
+    let b = vec![1.0; size];
+
+    println!(
+        "{} DOF grid: dense {} entries, skyline {} scrambled / {} after RCM",
+        size,
+        size * size,
+        scrambled.len(),
+        skyline.len()
+    );
+    bench("dense LU solve", || dense.solve(&b).unwrap());
+    bench("skyline Cholesky solve (scrambled)", || {
+        scrambled.solve(&b).unwrap()
+    });
+    bench("skyline Cholesky solve (RCM)", || {
+        skyline.solve(&b).unwrap()
+    });
+    bench("reverse Cuthill-McKee", || {
+        reverse_cuthill_mckee(size, &pairs)
+    });
+}
@@ -0,0 +1,170 @@
+//! Generates the unit conversion table from
+//! `scripts/clean_conversion_table.csv`.
+//!
+//! Two statics are written to `$OUT_DIR/conversions.rs` and pulled into
+//! `units::conversions` with `include!`:
+//!
+//! - `ENTRIES`, every usable row of the CSV in file order, duplicates and all,
+//!   as `(from units, to units, factor)`.
+//! - `SORTED`, the same keys with later rows overwriting earlier ones, sorted
+//!   by key so they can be binary searched. Rows between units with an SI
+//!   definition carry the factor computed from the definitions rather than
+//!   the rounded one in the CSV.
+//!
+//! Rows are `from,factor,to`, with fields quoted where they hold a comma.
+//! Rows whose factor is a note or a formula rather than a number are
+//! skipped; any other row that doesn't parse stops the build with its line
+//! number. Keys are kept as written in the CSV, less any periods, with one
+//! exception: the row from "gal" to meters per second squared is the
+//! galileo, not the gallon, so its key is renamed to "galileo".
+//!
+//! The name handling and SI definitions are shared with the library through
+//! `#[path]` modules, so the generated values always agree with
+//! `Table::factor`.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "src/units/names.rs"]
+mod names;
+
+#[allow(dead_code)]
+#[path = "src/units/dimension.rs"]
+mod dimension;
+
+#[allow(dead_code)]
+#[path = "src/units/si.rs"]
+mod si;
+
+// Stand-in for the one item `si` borrows from `units::graph`
+mod graph {
+    pub const EXACT: f64 = 1.0e-9;
+}
+
+const SOURCE: &str = "scripts/clean_conversion_table.csv";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SOURCE);
+    println!("cargo:rerun-if-changed=src/units/names.rs");
+    println!("cargo:rerun-if-changed=src/units/si.rs");
+    println!("cargo:rerun-if-changed=src/units/dimension.rs");
+
+    let csv = fs::read_to_string(SOURCE).expect("reading the conversion table");
+    let entries = parse(&csv);
+
+    let mut last: HashMap<(&str, &str), f64> = HashMap::new();
+    for (from, to, f) in entries.iter() {
+        last.insert((from, to), *f);
+    }
+    let mut sorted: Vec<((&str, &str), f64)> = last
+        .into_iter()
+        .map(|(k, f)| (k, exact(k.0, k.1).unwrap_or(f)))
+        .collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut out = String::new();
+    out.push_str("/// ENTRIES holds every conversion factor in the table as\n");
+    out.push_str("/// `(from units, to units, factor)`, in the order they appear in\n");
+    out.push_str("/// the source CSV. Some keys appear more than once; the last one\n");
+    out.push_str("/// wins. See `Table::audit`.\n");
+    out.push_str("#[allow(clippy::approx_constant)]\n");
+    out.push_str("pub static ENTRIES: &[(&str, &str, f64)] = &[\n");
+    for (from, to, f) in entries.iter() {
+        out.push_str(&format!("    ({:?}, {:?}, {}),\n", from, to, literal(*f)));
+    }
+    out.push_str("];\n\n");
+    out.push_str("// Distinct keys of ENTRIES in sorted order, with exact factors.\n");
+    out.push_str("#[allow(clippy::approx_constant)]\n");
+    out.push_str("static SORTED: &[((&str, &str), f64)] = &[\n");
+    for ((from, to), f) in sorted.iter() {
+        out.push_str(&format!("    (({:?}, {:?}), {}),\n", from, to, literal(*f)));
+    }
+    out.push_str("];\n");
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("conversions.rs");
+    fs::write(dest, out).expect("writing the generated table");
+}
+
+// Rows are `from,factor,to`. A factor column that isn't a number is a note
+// or a formula ("see page", "1.8 + 32"), and the row is skipped. Anything
+// else that doesn't fit is a mistake in the CSV and stops the build.
+fn parse(csv: &str) -> Vec<(String, String, f64)> {
+    let mut entries = Vec::new();
+    for (i, line) in csv.lines().enumerate() {
+        let fields = match split(line) {
+            Some(f) if f.len() == 3 => f,
+            _ => panic!("{}:{}: expected from,factor,to: {}", SOURCE, i + 1, line),
+        };
+        // Some factors are written with a space between digit groups
+        let factor: String = fields[1].split_whitespace().collect();
+        let factor: f64 = match factor.parse() {
+            Ok(f) => f,
+            Err(_) if is_note(&factor) => continue,
+            Err(_) => panic!("{}:{}: bad factor {:?}", SOURCE, i + 1, fields[1]),
+        };
+        let clean = |s: &str| s.replace('.', "").trim().to_string();
+        let mut from = clean(&fields[0]);
+        let to = clean(&fields[2]);
+        // "gal" is the galileo (cm/s^2) here, not the gallon
+        if from == "gal" && to.starts_with("meter per sec") {
+            from = "galileo".to_string();
+        }
+        entries.push((from, to, factor));
+    }
+    entries
+}
+
+// Split a CSV line into fields. Fields may be quoted to hold commas, with
+// "" for a quote inside. Returns None for an unterminated quote.
+fn split(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+
+// A factor column holding words, or an expression, rather than a number
+fn is_note(factor: &str) -> bool {
+    !factor.chars().any(|c| c.is_ascii_digit()) || factor.contains(&['+', 'x', '='][..])
+}
+
+// The factor between two raw names computed from their SI definitions, when
+// both have one and they measure the same thing.
+fn exact(from: &str, to: &str) -> Option<f64> {
+    let a = si::lookup(&names::normalize(from))?;
+    let b = si::lookup(&names::normalize(to))?;
+    if a.base == b.base {
+        Some(a.si / b.si)
+    } else {
+        None
+    }
+}
+
+// Format a float as a Rust literal which parses back to the same value.
+fn literal(f: f64) -> String {
+    let s = format!("{:?}", f);
+    if s.contains('.') || s.contains('e') {
+        s
+    } else {
+        s + ".0"
+    }
+}
@@ -0,0 +1,664 @@
+//! # dual
+//!
+//! Forward mode automatic differentiation with dual numbers. A `Dual`
+//! carries a value and its derivative with respect to one chosen input
+//! through every operation, so the derivative comes out exact to rounding
+//! instead of carrying the truncation error of a finite difference.
+//!
+//! Write a design equation once over the `Float` trait, which both `f64`
+//! and `Dual` implement, then evaluate it with plain numbers for the result
+//! or with duals for its sensitivity to an input:
+//!
+//! ```rust
+//! use civil::calc::dual::{derivative, Float};
+//!
+//! // Manning's equation for a 3 m wide rectangular channel
+//! fn flow<T: Float>(depth: T) -> T {
+//!     let (b, n, s) = (3.0, 0.013, 0.001_f64);
+//!     let area = depth * b;
+//!     let radius = area / (depth * 2.0 + b);
+//!     area * radius.powf(2.0 / 3.0) * (s.sqrt() / n)
+//! }
+//!
+//! let q = flow(1.0);
+//! let dq = derivative(flow, 1.0);
+//! // compare against a central difference
+//! let h = 1.0e-6;
+//! assert!((dq - (flow(1.0 + h) - flow(1.0 - h)) / (2.0 * h)).abs() < 1.0e-6);
+//! assert!(q > 0.0 && dq > 0.0);
+//! ```
+//!
+//! `gradient` and `jacobian` take one pass per input. A Jacobian from
+//! `jacobian` can be handed to `nonlinear::newton_raphson_with_jacobian`,
+//! and a derivative from `derivative` to `roots::newton_with_derivative`.
+
+use super::linalg::Matrix;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Float is the arithmetic the generic design equations need. It is
+/// implemented by `f64` and by `Dual`, and constants come in through
+/// `From<f64>` or the mixed operators, eg `x * 2.0`.
+pub trait Float:
+    Copy
+    + fmt::Debug
+    + PartialEq
+    + PartialOrd
+    + From<f64>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Add<f64, Output = Self>
+    + Sub<f64, Output = Self>
+    + Mul<f64, Output = Self>
+    + Div<f64, Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    /// real returns the plain value, dropping any derivative.
+    fn real(self) -> f64;
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, n: f64) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn log10(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn tanh(self) -> Self;
+    fn is_finite(self) -> bool;
+    /// min returns the lesser of the two by value.
+    fn min(self, other: Self) -> Self {
+        if other.real() < self.real() {
+            other
+        } else {
+            self
+        }
+    }
+    /// max returns the greater of the two by value.
+    fn max(self, other: Self) -> Self {
+        if other.real() > self.real() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl Float for f64 {
+    fn real(self) -> f64 {
+        self
+    }
+    fn abs(self) -> f64 {
+        f64::abs(self)
+    }
+    fn sqrt(self) -> f64 {
+        f64::sqrt(self)
+    }
+    fn cbrt(self) -> f64 {
+        f64::cbrt(self)
+    }
+    fn powi(self, n: i32) -> f64 {
+        f64::powi(self, n)
+    }
+    fn powf(self, n: f64) -> f64 {
+        f64::powf(self, n)
+    }
+    fn exp(self) -> f64 {
+        f64::exp(self)
+    }
+    fn ln(self) -> f64 {
+        f64::ln(self)
+    }
+    fn log10(self) -> f64 {
+        f64::log10(self)
+    }
+    fn sin(self) -> f64 {
+        f64::sin(self)
+    }
+    fn cos(self) -> f64 {
+        f64::cos(self)
+    }
+    fn tan(self) -> f64 {
+        f64::tan(self)
+    }
+    fn asin(self) -> f64 {
+        f64::asin(self)
+    }
+    fn acos(self) -> f64 {
+        f64::acos(self)
+    }
+    fn atan(self) -> f64 {
+        f64::atan(self)
+    }
+    fn atan2(self, x: f64) -> f64 {
+        f64::atan2(self, x)
+    }
+    fn sinh(self) -> f64 {
+        f64::sinh(self)
+    }
+    fn cosh(self) -> f64 {
+        f64::cosh(self)
+    }
+    fn tanh(self) -> f64 {
+        f64::tanh(self)
+    }
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+    fn min(self, other: f64) -> f64 {
+        f64::min(self, other)
+    }
+    fn max(self, other: f64) -> f64 {
+        f64::max(self, other)
+    }
+}
+
+/// Dual is a value together with its derivative with respect to a single
+/// input. Seed that input with `Dual::variable` and everything else with
+/// `Dual::constant`, or `From<f64>`.
+///
+/// Duals order by value, then by derivative.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Dual {
+    pub value: f64,
+    pub derivative: f64,
+}
+
+impl Dual {
+    /// new creates a dual from a value and its derivative.
+    pub fn new(value: f64, derivative: f64) -> Dual {
+        Dual { value, derivative }
+    }
+
+    /// variable creates the input being differentiated with respect to,
+    /// whose derivative is one.
+    pub fn variable(value: f64) -> Dual {
+        Dual::new(value, 1.0)
+    }
+
+    /// constant creates a value which doesn't depend on the input.
+    pub fn constant(value: f64) -> Dual {
+        Dual::new(value, 0.0)
+    }
+
+    // Apply a function with value f and derivative df at self.value
+    fn chain(self, f: f64, df: f64) -> Dual {
+        Dual::new(f, df * self.derivative)
+    }
+}
+
+impl From<f64> for Dual {
+    fn from(value: f64) -> Dual {
+        Dual::constant(value)
+    }
+}
+
+impl fmt::Display for Dual {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} + {}ε", self.value, self.derivative)
+    }
+}
+
+impl PartialOrd for Dual {
+    fn partial_cmp(&self, other: &Dual) -> Option<Ordering> {
+        match self.value.partial_cmp(&other.value) {
+            Some(Ordering::Equal) => self.derivative.partial_cmp(&other.derivative),
+            order => order,
+        }
+    }
+}
+
+impl Add for Dual {
+    type Output = Dual;
+    fn add(self, rhs: Dual) -> Dual {
+        Dual::new(self.value + rhs.value, self.derivative + rhs.derivative)
+    }
+}
+
+impl Sub for Dual {
+    type Output = Dual;
+    fn sub(self, rhs: Dual) -> Dual {
+        Dual::new(self.value - rhs.value, self.derivative - rhs.derivative)
+    }
+}
+
+impl Mul for Dual {
+    type Output = Dual;
+    fn mul(self, rhs: Dual) -> Dual {
+        Dual::new(
+            self.value * rhs.value,
+            self.derivative * rhs.value + self.value * rhs.derivative,
+        )
+    }
+}
+
+impl Div for Dual {
+    type Output = Dual;
+    fn div(self, rhs: Dual) -> Dual {
+        let value = self.value / rhs.value;
+        Dual::new(
+            value,
+            (self.derivative - value * rhs.derivative) / rhs.value,
+        )
+    }
+}
+
+impl Neg for Dual {
+    type Output = Dual;
+    fn neg(self) -> Dual {
+        Dual::new(-self.value, -self.derivative)
+    }
+}
+
+impl Add<f64> for Dual {
+    type Output = Dual;
+    fn add(self, rhs: f64) -> Dual {
+        Dual::new(self.value + rhs, self.derivative)
+    }
+}
+
+impl Sub<f64> for Dual {
+    type Output = Dual;
+    fn sub(self, rhs: f64) -> Dual {
+        Dual::new(self.value - rhs, self.derivative)
+    }
+}
+
+impl Mul<f64> for Dual {
+    type Output = Dual;
+    fn mul(self, rhs: f64) -> Dual {
+        Dual::new(self.value * rhs, self.derivative * rhs)
+    }
+}
+
+impl Div<f64> for Dual {
+    type Output = Dual;
+    fn div(self, rhs: f64) -> Dual {
+        Dual::new(self.value / rhs, self.derivative / rhs)
+    }
+}
+
+impl Add<Dual> for f64 {
+    type Output = Dual;
+    fn add(self, rhs: Dual) -> Dual {
+        rhs + self
+    }
+}
+
+impl Sub<Dual> for f64 {
+    type Output = Dual;
+    fn sub(self, rhs: Dual) -> Dual {
+        -rhs + self
+    }
+}
+
+impl Mul<Dual> for f64 {
+    type Output = Dual;
+    fn mul(self, rhs: Dual) -> Dual {
+        rhs * self
+    }
+}
+
+impl Div<Dual> for f64 {
+    type Output = Dual;
+    fn div(self, rhs: Dual) -> Dual {
+        Dual::constant(self) / rhs
+    }
+}
+
+impl AddAssign for Dual {
+    fn add_assign(&mut self, rhs: Dual) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Dual {
+    fn sub_assign(&mut self, rhs: Dual) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Dual {
+    fn mul_assign(&mut self, rhs: Dual) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Dual {
+    fn div_assign(&mut self, rhs: Dual) {
+        *self = *self / rhs;
+    }
+}
+
+impl Float for Dual {
+    fn real(self) -> f64 {
+        self.value
+    }
+    fn abs(self) -> Dual {
+        if self.value < 0.0 {
+            -self
+        } else {
+            self
+        }
+    }
+    fn sqrt(self) -> Dual {
+        let root = self.value.sqrt();
+        self.chain(root, 0.5 / root)
+    }
+    fn cbrt(self) -> Dual {
+        let root = self.value.cbrt();
+        self.chain(root, 1.0 / (3.0 * root * root))
+    }
+    fn powi(self, n: i32) -> Dual {
+        if n == 0 {
+            return Dual::constant(1.0);
+        }
+        self.chain(self.value.powi(n), f64::from(n) * self.value.powi(n - 1))
+    }
+    fn powf(self, n: f64) -> Dual {
+        if n == 0.0 {
+            return Dual::constant(1.0);
+        }
+        self.chain(self.value.powf(n), n * self.value.powf(n - 1.0))
+    }
+    fn exp(self) -> Dual {
+        let e = self.value.exp();
+        self.chain(e, e)
+    }
+    fn ln(self) -> Dual {
+        self.chain(self.value.ln(), 1.0 / self.value)
+    }
+    fn log10(self) -> Dual {
+        self.chain(
+            self.value.log10(),
+            1.0 / (self.value * std::f64::consts::LN_10),
+        )
+    }
+    fn sin(self) -> Dual {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+    fn cos(self) -> Dual {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+    fn tan(self) -> Dual {
+        let t = self.value.tan();
+        self.chain(t, 1.0 + t * t)
+    }
+    fn asin(self) -> Dual {
+        self.chain(
+            self.value.asin(),
+            1.0 / (1.0 - self.value * self.value).sqrt(),
+        )
+    }
+    fn acos(self) -> Dual {
+        self.chain(
+            self.value.acos(),
+            -1.0 / (1.0 - self.value * self.value).sqrt(),
+        )
+    }
+    fn atan(self) -> Dual {
+        self.chain(self.value.atan(), 1.0 / (1.0 + self.value * self.value))
+    }
+    fn atan2(self, x: Dual) -> Dual {
+        let r2 = self.value * self.value + x.value * x.value;
+        Dual::new(
+            self.value.atan2(x.value),
+            (x.value * self.derivative - self.value * x.derivative) / r2,
+        )
+    }
+    fn sinh(self) -> Dual {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+    fn cosh(self) -> Dual {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+    fn tanh(self) -> Dual {
+        let t = self.value.tanh();
+        self.chain(t, 1.0 - t * t)
+    }
+    fn is_finite(self) -> bool {
+        self.value.is_finite() && self.derivative.is_finite()
+    }
+}
+
+/// derivative returns `f'(x)`, evaluating `f` once with duals.
+pub fn derivative<F: Fn(Dual) -> Dual>(f: F, x: f64) -> f64 {
+    f(Dual::variable(x)).derivative
+}
+
+/// gradient returns the partial derivatives of `f` at `x`, evaluating `f`
+/// once per unknown.
+pub fn gradient<F: Fn(&[Dual]) -> Dual>(f: F, x: &[f64]) -> Vec<f64> {
+    let mut duals: Vec<Dual> = x.iter().map(|&x| Dual::constant(x)).collect();
+    (0..x.len())
+        .map(|i| {
+            duals[i].derivative = 1.0;
+            let d = f(&duals).derivative;
+            duals[i].derivative = 0.0;
+            d
+        })
+        .collect()
+}
+
+/// jacobian returns the Jacobian of `f` at `x`, where `jacobian[(i, j)]` is
+/// the derivative of output i with respect to input j. `f` is evaluated
+/// once per input.
+pub fn jacobian<F: Fn(&[Dual]) -> Vec<Dual>>(f: F, x: &[f64]) -> Matrix {
+    let mut duals: Vec<Dual> = x.iter().map(|&x| Dual::constant(x)).collect();
+    let mut j = Matrix::zeros(0, 0);
+    for col in 0..x.len() {
+        duals[col].derivative = 1.0;
+        let fx = f(&duals);
+        duals[col].derivative = 0.0;
+        if col == 0 {
+            j = Matrix::zeros(fx.len(), x.len());
+        }
+        for (row, y) in fx.iter().enumerate() {
+            j[(row, col)] = y.derivative;
+        }
+    }
+    j
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{derivative, gradient, jacobian, Dual, Float};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            name: &'static str,
+            f: fn(Dual) -> Dual,
+            x: f64,
+            expected: f64,
+        }
+        let table = [
+            Row {
+                name: "polynomial",
+                f: |x| x * x * 3.0 - x * 2.0 + 1.0,
+                x: 2.0,
+                expected: 10.0,
+            },
+            Row {
+                name: "quotient",
+                f: |x| 1.0 / (x + 1.0),
+                x: 1.0,
+                expected: -0.25,
+            },
+            Row {
+                name: "abs",
+                f: |x| x.abs(),
+                x: -3.0,
+                expected: -1.0,
+            },
+            Row {
+                name: "sqrt",
+                f: |x| x.sqrt(),
+                x: 4.0,
+                expected: 0.25,
+            },
+            Row {
+                name: "cbrt",
+                f: |x| x.cbrt(),
+                x: 8.0,
+                expected: 1.0 / 12.0,
+            },
+            Row {
+                name: "powi",
+                f: |x| x.powi(-2),
+                x: 2.0,
+                expected: -0.25,
+            },
+            Row {
+                name: "powf",
+                f: |x| x.powf(2.0 / 3.0),
+                x: 8.0,
+                expected: 1.0 / 3.0,
+            },
+            Row {
+                name: "exp",
+                f: |x| (x * 2.0).exp(),
+                x: 0.0,
+                expected: 2.0,
+            },
+            Row {
+                name: "ln",
+                f: |x| x.ln(),
+                x: 4.0,
+                expected: 0.25,
+            },
+            Row {
+                name: "log10",
+                f: |x| x.log10(),
+                x: 10.0,
+                expected: 1.0 / 10.0f64.ln() / 10.0,
+            },
+            Row {
+                name: "sin",
+                f: |x| x.sin(),
+                x: PI / 3.0,
+                expected: 0.5,
+            },
+            Row {
+                name: "cos",
+                f: |x| x.cos(),
+                x: PI / 2.0,
+                expected: -1.0,
+            },
+            Row {
+                name: "tan",
+                f: |x| x.tan(),
+                x: PI / 4.0,
+                expected: 2.0,
+            },
+            Row {
+                name: "asin",
+                f: |x| x.asin(),
+                x: 0.6,
+                expected: 1.25,
+            },
+            Row {
+                name: "acos",
+                f: |x| x.acos(),
+                x: 0.6,
+                expected: -1.25,
+            },
+            Row {
+                name: "atan",
+                f: |x| x.atan(),
+                x: 1.0,
+                expected: 0.5,
+            },
+            Row {
+                name: "atan2",
+                f: |x| x.atan2(Dual::constant(1.0)),
+                x: 1.0,
+                expected: 0.5,
+            },
+            Row {
+                name: "sinh",
+                f: |x| x.sinh(),
+                x: 0.0,
+                expected: 1.0,
+            },
+            Row {
+                name: "cosh",
+                f: |x| x.cosh(),
+                x: 0.0,
+                expected: 0.0,
+            },
+            Row {
+                name: "tanh",
+                f: |x| x.tanh(),
+                x: 0.0,
+                expected: 1.0,
+            },
+            Row {
+                name: "chain",
+                f: |x| (x * x).sin().exp(),
+                x: 1.0,
+                expected: 2.0 * 1.0f64.cos() * 1.0f64.sin().exp(),
+            },
+        ];
+        for r in table.iter() {
+            let d = derivative(r.f, r.x);
+            assert!(
+                (d - r.expected).abs() < 1.0e-14,
+                "{}: {} != {}",
+                r.name,
+                d,
+                r.expected
+            );
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Dual::new(3.0, 1.0), Dual::new(2.0, -1.0));
+        assert_eq!(a + b, Dual::new(5.0, 0.0));
+        assert_eq!(a - b, Dual::new(1.0, 2.0));
+        assert_eq!(a * b, Dual::new(6.0, -1.0));
+        assert_eq!(a / b, Dual::new(1.5, 1.25));
+        assert_eq!(2.0 - a, Dual::new(-1.0, -1.0));
+        let mut c = a;
+        c *= b;
+        c -= b;
+        assert_eq!(c, Dual::new(4.0, 0.0));
+        // ordered by value first
+        assert!(b < a && a.min(b) == b && a.max(b) == a);
+        assert!(Dual::new(1.0, 0.0) < Dual::new(1.0, 1.0));
+        assert!(!Dual::new(1.0, f64::NAN).is_finite());
+        assert_eq!(Float::real(a), 3.0);
+        assert_eq!(a.to_string(), "3 + 1ε");
+    }
+
+    #[test]
+    fn several_inputs() {
+        // the area of a rectangle and its perimeter
+        let f = |x: &[Dual]| vec![x[0] * x[1], (x[0] + x[1]) * 2.0];
+        let j = jacobian(f, &[3.0, 2.0]);
+        assert_eq!(j.shape(), (2, 2));
+        assert_eq!(j[(0, 0)], 2.0);
+        assert_eq!(j[(0, 1)], 3.0);
+        assert_eq!(j[(1, 0)], 2.0);
+        assert_eq!(j[(1, 1)], 2.0);
+        let g = gradient(|x: &[Dual]| x[0] * x[1] * x[2], &[2.0, 3.0, 4.0]);
+        assert_eq!(g, vec![12.0, 8.0, 6.0]);
+    }
+}
@@ -0,0 +1,511 @@
+//! # eigen
+//!
+//! Eigenvalues and eigenvectors of symmetric matrices: principal stresses,
+//! principal axes of a section, natural frequencies and mode shapes.
+//!
+//! - `symmetric` finds every eigenpair of a symmetric matrix by Jacobi
+//!   rotations, which is accurate and robust for the small matrices that
+//!   come up in hand calculations
+//! - `generalized` finds the first few modes of `K φ = λ M φ`, with K a
+//!   stiffness and M a mass matrix, by subspace iteration. `λ = ω²`.
+//!
+//! ```rust
+//! use civil::calc::eigen::symmetric;
+//! use civil::calc::linalg::Matrix;
+//!
+//! // principal stresses (MPa) from a stress tensor
+//! let stress = Matrix::from_rows(&[
+//!     [50.0, 30.0, 0.0],
+//!     [30.0, -20.0, 0.0],
+//!     [0.0, 0.0, 10.0],
+//! ])
+//! .unwrap();
+//! let e = symmetric(&stress).unwrap();
+//! // in ascending order: σ3, σ2, σ1
+//! assert!((e.values[0] - (15.0 - 2125f64.sqrt())).abs() < 1e-12);
+//! assert!((e.values[1] - 10.0).abs() < 1e-12);
+//! assert!((e.values[2] - (15.0 + 2125f64.sqrt())).abs() < 1e-12);
+//! ```
+
+use super::linalg::{Matrix, MatrixError};
+
+/// Eigen holds eigenvalues in ascending order and their eigenvectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eigen {
+    /// Eigenvalues, smallest first
+    pub values: Vec<f64>,
+    /// Eigenvectors, one per column in the order of `values`. They are
+    /// orthonormal for `symmetric`, and normalized so that `φᵀ M φ = 1` for
+    /// `generalized`.
+    pub vectors: Matrix,
+}
+
+impl Eigen {
+    /// vector copies out the eigenvector for `values[k]`.
+    pub fn vector(&self, k: usize) -> Vec<f64> {
+        (0..self.vectors.rows())
+            .map(|i| self.vectors[(i, k)])
+            .collect()
+    }
+}
+
+// Jacobi sweeps allowed before giving up. Convergence is quadratic, so a
+// handful usually suffice.
+const MAX_SWEEPS: usize = 50;
+
+/// symmetric finds all the eigenvalues and eigenvectors of a symmetric
+/// matrix by the cyclic Jacobi method.
+pub fn symmetric(a: &Matrix) -> Result<Eigen, MatrixError> {
+    check_symmetric(a)?;
+    let n = a.rows();
+    let mut a = a.clone();
+    let mut v = Matrix::identity(n);
+    let mut d: Vec<f64> = (0..n).map(|i| a[(i, i)]).collect();
+    // The diagonal is updated from b, which accumulates the changes of a
+    // sweep in z, to limit rounding
+    let mut b = d.clone();
+    let mut z = vec![0.0; n];
+    for sweep in 0..MAX_SWEEPS {
+        let off: f64 = (0..n)
+            .flat_map(|p| (p + 1..n).map(move |q| (p, q)))
+            .map(|(p, q)| a[(p, q)].abs())
+            .sum();
+        if off == 0.0 {
+            return Ok(sorted(d, v));
+        }
+        // Only rotate away large elements on the first sweeps
+        let threshold = if sweep < 3 {
+            0.2 * off / (n * n) as f64
+        } else {
+            0.0
+        };
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[(p, q)];
+                let g = 100.0 * apq.abs();
+                if sweep > 3 && d[p].abs() + g == d[p].abs() && d[q].abs() + g == d[q].abs() {
+                    // Too small to matter next to the diagonal
+                    a[(p, q)] = 0.0;
+                    continue;
+                }
+                if apq.abs() <= threshold {
+                    continue;
+                }
+                let h = d[q] - d[p];
+                let t = if h.abs() + g == h.abs() {
+                    apq / h
+                } else {
+                    let theta = 0.5 * h / apq;
+                    let t = 1.0 / (theta.abs() + (1.0 + theta * theta).sqrt());
+                    if theta < 0.0 {
+                        -t
+                    } else {
+                        t
+                    }
+                };
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                let s = t * c;
+                let tau = s / (1.0 + c);
+                let h = t * apq;
+                z[p] -= h;
+                z[q] += h;
+                d[p] -= h;
+                d[q] += h;
+                a[(p, q)] = 0.0;
+                // Only the upper triangle of a is kept up to date
+                for j in 0..p {
+                    rotate(&mut a, (j, p), (j, q), s, tau);
+                }
+                for j in p + 1..q {
+                    rotate(&mut a, (p, j), (j, q), s, tau);
+                }
+                for j in q + 1..n {
+                    rotate(&mut a, (p, j), (q, j), s, tau);
+                }
+                for j in 0..n {
+                    rotate(&mut v, (j, p), (j, q), s, tau);
+                }
+            }
+        }
+        for i in 0..n {
+            b[i] += z[i];
+            d[i] = b[i];
+            z[i] = 0.0;
+        }
+    }
+    Err(MatrixError::NoConvergence {
+        iterations: MAX_SWEEPS,
+    })
+}
+
+// Apply a Jacobi rotation to a pair of elements
+fn rotate(a: &mut Matrix, x: (usize, usize), y: (usize, usize), s: f64, tau: f64) {
+    let (g, h) = (a[x], a[y]);
+    a[x] = g - s * (h + g * tau);
+    a[y] = h + s * (g - h * tau);
+}
+
+// Relative change in the eigenvalues at which subspace iteration stops
+const SUBSPACE_TOLERANCE: f64 = 1.0e-12;
+
+// Subspace iterations allowed before giving up
+const MAX_ITERATIONS: usize = 200;
+
+/// generalized finds the `modes` smallest eigenvalues of `K φ = λ M φ` and
+/// their mode shapes, by subspace iteration (Bathe). K must be symmetric
+/// positive definite, so the structure must be supported. M must be
+/// symmetric positive semi-definite; a lumped, diagonal mass matrix with
+/// some massless degrees of freedom is fine, but there must be at least
+/// as many masses as modes.
+///
+/// Subspace iteration converges to the lowest modes from almost any start,
+/// but it has no way to prove that none were missed.
+///
+/// ```rust
+/// use civil::calc::eigen::generalized;
+/// use civil::calc::linalg::Matrix;
+///
+/// // A two storey shear building, floors of 2 and 1 on springs of 3 and 1
+/// let k = Matrix::from_rows(&[[4.0, -1.0], [-1.0, 1.0]]).unwrap();
+/// let m = Matrix::from_rows(&[[2.0, 0.0], [0.0, 1.0]]).unwrap();
+/// let e = generalized(&k, &m, 2).unwrap();
+/// // det(K - λ M) = 2λ² - 6λ + 3 = 0
+/// assert!((e.values[0] - (3.0 - 3f64.sqrt()) / 2.0).abs() < 1e-12);
+/// assert!((e.values[1] - (3.0 + 3f64.sqrt()) / 2.0).abs() < 1e-12);
+/// let w1 = e.values[0].sqrt(); // rad/s
+/// assert!((w1 - 0.796).abs() < 1e-3);
+/// ```
+pub fn generalized(k: &Matrix, m: &Matrix, modes: usize) -> Result<Eigen, MatrixError> {
+    check_symmetric(k)?;
+    check_symmetric(m)?;
+    let n = k.rows();
+    if m.shape() != k.shape() {
+        return Err(MatrixError::Shape {
+            expected: k.shape(),
+            found: m.shape(),
+        });
+    }
+    // Degrees of freedom with mass, those which are most flexible per unit
+    // of mass first
+    let mut massed: Vec<usize> = (0..n).filter(|i| m[(*i, *i)] > 0.0).collect();
+    if modes > massed.len() {
+        let column = (0..n).find(|i| m[(*i, *i)] <= 0.0).unwrap_or(0);
+        return Err(MatrixError::NotPositiveDefinite { column });
+    }
+    massed.sort_by(|i, j| (k[(*i, *i)] / m[(*i, *i)]).total_cmp(&(k[(*j, *j)] / m[(*j, *j)])));
+    let q = (2 * modes).min(modes + 8).min(massed.len()).max(modes);
+    if q == 0 {
+        return Ok(Eigen {
+            values: Vec::new(),
+            vectors: Matrix::zeros(n, 0),
+        });
+    }
+    // Starting vectors: the diagonal of M, then unit vectors
+    let mut x = Matrix::zeros(n, q);
+    for i in 0..n {
+        x[(i, 0)] = m[(i, i)];
+    }
+    for (c, i) in massed.iter().take(q - 1).enumerate() {
+        x[(*i, c + 1)] = 1.0;
+    }
+    let lu = k.lu()?;
+    let mut last: Vec<f64> = Vec::new();
+    for _ in 0..MAX_ITERATIONS {
+        // Inverse iteration on the whole subspace
+        let mx = m.mul(&x)?;
+        let mut xbar = Matrix::zeros(n, q);
+        for c in 0..q {
+            let column: Vec<f64> = (0..n).map(|i| mx[(i, c)]).collect();
+            for (i, v) in lu.solve(&column)?.into_iter().enumerate() {
+                xbar[(i, c)] = v;
+            }
+        }
+        // Rayleigh-Ritz: solve the problem projected onto the subspace
+        let xt = xbar.transpose();
+        let kr = symmetrize(&xt.mul(&k.mul(&xbar)?)?);
+        let mr = symmetrize(&xt.mul(&m.mul(&xbar)?)?);
+        let (values, q_vectors) = reduced(&kr, &mr)?;
+        x = xbar.mul(&q_vectors)?;
+        let converged = !last.is_empty()
+            && (0..modes)
+                .all(|i| (values[i] - last[i]).abs() <= SUBSPACE_TOLERANCE * values[i].abs());
+        last = values;
+        if converged {
+            let mut vectors = Matrix::zeros(n, modes);
+            for i in 0..n {
+                for c in 0..modes {
+                    vectors[(i, c)] = x[(i, c)];
+                }
+            }
+            last.truncate(modes);
+            return Ok(Eigen {
+                values: last,
+                vectors,
+            });
+        }
+    }
+    Err(MatrixError::NoConvergence {
+        iterations: MAX_ITERATIONS,
+    })
+}
+
+// Solve the small generalized problem Kr Q = Mr Q Λ by reducing it to a
+// standard one with the Cholesky factor of Mr = L Lᵀ
+fn reduced(kr: &Matrix, mr: &Matrix) -> Result<(Vec<f64>, Matrix), MatrixError> {
+    let q = kr.rows();
+    let l = cholesky(mr)?;
+    // A = L⁻¹ Kr L⁻ᵀ, by forward substitution on columns then on rows
+    let y = lower_solve(&l, kr);
+    let a = lower_solve(&l, &y.transpose());
+    let e = symmetric(&symmetrize(&a))?;
+    // Q = L⁻ᵀ Z, by back substitution
+    let z = e.vectors;
+    let mut result = Matrix::zeros(q, q);
+    for c in 0..q {
+        for i in (0..q).rev() {
+            let sum: f64 = (i + 1..q).map(|j| l[(j, i)] * result[(j, c)]).sum();
+            result[(i, c)] = (z[(i, c)] - sum) / l[(i, i)];
+        }
+    }
+    Ok((e.values, result))
+}
+
+// Lower triangular L with A = L Lᵀ
+fn cholesky(a: &Matrix) -> Result<Matrix, MatrixError> {
+    let n = a.rows();
+    let mut l = Matrix::zeros(n, n);
+    for j in 0..n {
+        let d = a[(j, j)] - (0..j).map(|k| l[(j, k)] * l[(j, k)]).sum::<f64>();
+        if d <= 0.0 || !d.is_finite() {
+            return Err(MatrixError::NotPositiveDefinite { column: j });
+        }
+        l[(j, j)] = d.sqrt();
+        for i in j + 1..n {
+            let s = a[(i, j)] - (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum::<f64>();
+            l[(i, j)] = s / l[(j, j)];
+        }
+    }
+    Ok(l)
+}
+
+// L⁻¹ B for lower triangular L
+fn lower_solve(l: &Matrix, b: &Matrix) -> Matrix {
+    let (n, cols) = b.shape();
+    let mut x = Matrix::zeros(n, cols);
+    for c in 0..cols {
+        for i in 0..n {
+            let sum: f64 = (0..i).map(|j| l[(i, j)] * x[(j, c)]).sum();
+            x[(i, c)] = (b[(i, c)] - sum) / l[(i, i)];
+        }
+    }
+    x
+}
+
+fn symmetrize(a: &Matrix) -> Matrix {
+    let mut s = a.clone();
+    for i in 0..a.rows() {
+        for j in i + 1..a.cols() {
+            let v = (a[(i, j)] + a[(j, i)]) / 2.0;
+            s[(i, j)] = v;
+            s[(j, i)] = v;
+        }
+    }
+    s
+}
+
+// Square, and symmetric to within rounding
+fn check_symmetric(a: &Matrix) -> Result<(), MatrixError> {
+    let (rows, cols) = a.shape();
+    if rows != cols {
+        return Err(MatrixError::NotSquare { rows, cols });
+    }
+    // NaN fails every comparison, so it would pass for symmetric
+    if let Some(k) = a.data.iter().position(|v| !v.is_finite()) {
+        return Err(MatrixError::NotFinite {
+            row: k / a.stride,
+            col: k % a.stride,
+        });
+    }
+    let largest = a.data.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+    for i in 0..rows {
+        for j in i + 1..cols {
+            if (a[(i, j)] - a[(j, i)]).abs() > 1.0e-10 * largest {
+                return Err(MatrixError::NotSymmetric { row: i, col: j });
+            }
+        }
+    }
+    Ok(())
+}
+
+// Sort eigenpairs by ascending eigenvalue
+fn sorted(values: Vec<f64>, vectors: Matrix) -> Eigen {
+    let n = values.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|i, j| values[*i].total_cmp(&values[*j]));
+    let mut v = Matrix::zeros(n, n);
+    for (c, k) in order.iter().enumerate() {
+        for i in 0..n {
+            v[(i, c)] = vectors[(i, *k)];
+        }
+    }
+    Eigen {
+        values: order.iter().map(|k| values[*k]).collect(),
+        vectors: v,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generalized, symmetric};
+    use crate::calc::linalg::{Matrix, MatrixError};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            a: Matrix,
+            expected: Vec<f64>,
+        }
+        let table = [
+            Row {
+                a: Matrix::from_rows(&[[2.0, 0.0], [0.0, 1.0]]).unwrap(),
+                expected: vec![1.0, 2.0],
+            },
+            Row {
+                a: Matrix::from_rows(&[[2.0, 1.0], [1.0, 2.0]]).unwrap(),
+                expected: vec![1.0, 3.0],
+            },
+            // repeated eigenvalues
+            Row {
+                a: Matrix::from_rows(&[[2.0, 1.0, 1.0], [1.0, 2.0, 1.0], [1.0, 1.0, 2.0]]).unwrap(),
+                expected: vec![1.0, 1.0, 4.0],
+            },
+            // the second difference matrix: 2 - 2 cos(kπ / (n + 1))
+            Row {
+                a: {
+                    let mut a = Matrix::zeros(6, 6);
+                    for i in 0..6 {
+                        a[(i, i)] = 2.0;
+                        if i > 0 {
+                            a[(i, i - 1)] = -1.0;
+                            a[(i - 1, i)] = -1.0;
+                        }
+                    }
+                    a
+                },
+                expected: (1..=6)
+                    .map(|k| 2.0 - 2.0 * (k as f64 * PI / 7.0).cos())
+                    .collect(),
+            },
+        ];
+        for r in table.iter() {
+            let e = symmetric(&r.a).unwrap();
+            let n = r.expected.len();
+            for k in 0..n {
+                assert!(
+                    (e.values[k] - r.expected[k]).abs() < 1e-12,
+                    "{:?}",
+                    e.values
+                );
+                // A v = λ v, with v of unit length
+                let v = e.vector(k);
+                let av = r.a.mul_vec(&v).unwrap();
+                for i in 0..n {
+                    assert!((av[i] - e.values[k] * v[i]).abs() < 1e-12);
+                }
+                let norm: f64 = v.iter().map(|x| x * x).sum();
+                assert!((norm - 1.0).abs() < 1e-12);
+            }
+            // and orthogonal
+            let vtv = e.vectors.transpose().mul(&e.vectors).unwrap();
+            for i in 0..n {
+                for j in 0..n {
+                    let expected = if i == j { 1.0 } else { 0.0 };
+                    assert!((vtv[(i, j)] - expected).abs() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cantilever_modes() {
+        // A uniform cantilever as a chain of lumped masses: the shear
+        // building with n equal storeys has ω = 2 sqrt(k/m) sin((2j-1)π /
+        // (2(2n+1)))
+        let n = 20;
+        let (stiffness, mass) = (1000.0, 2.0);
+        let mut k = Matrix::zeros(n, n);
+        let mut m = Matrix::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = mass;
+            k[(i, i)] = if i == n - 1 {
+                stiffness
+            } else {
+                2.0 * stiffness
+            };
+            if i > 0 {
+                k[(i, i - 1)] = -stiffness;
+                k[(i - 1, i)] = -stiffness;
+            }
+        }
+        let e = generalized(&k, &m, 3).unwrap();
+        assert_eq!(e.values.len(), 3);
+        for j in 1..=3 {
+            let w = 2.0
+                * (stiffness / mass).sqrt()
+                * ((2 * j - 1) as f64 * PI / (2.0 * (2 * n + 1) as f64)).sin();
+            assert!(
+                (e.values[j - 1].sqrt() - w).abs() < 1e-9 * w,
+                "{:?}",
+                e.values
+            );
+            // mass normalized
+            let v = e.vector(j - 1);
+            let mv = m.mul_vec(&v).unwrap();
+            let modal_mass: f64 = v.iter().zip(mv.iter()).map(|(a, b)| a * b).sum();
+            assert!((modal_mass - 1.0).abs() < 1e-9);
+        }
+        // All of them agrees with the standard problem for M⁻¹ K
+        let all = symmetric(&k.scale(1.0 / mass)).unwrap();
+        for j in 0..3 {
+            assert!((all.values[j] - e.values[j]).abs() < 1e-9 * e.values[j]);
+        }
+    }
+
+    #[test]
+    fn failures() {
+        let a = Matrix::from_rows(&[[1.0, 2.0], [3.0, 4.0]]).unwrap();
+        assert_eq!(
+            symmetric(&a),
+            Err(MatrixError::NotSymmetric { row: 0, col: 1 })
+        );
+        let a = Matrix::zeros(2, 3);
+        assert_eq!(
+            symmetric(&a),
+            Err(MatrixError::NotSquare { rows: 2, cols: 3 })
+        );
+        let a = Matrix::from_rows(&[[1.0, 2.0], [2.0, f64::NAN]]).unwrap();
+        assert_eq!(
+            symmetric(&a),
+            Err(MatrixError::NotFinite { row: 1, col: 1 })
+        );
+        let m = Matrix::from_rows(&[[1.0, f64::INFINITY], [f64::INFINITY, 1.0]]).unwrap();
+        assert_eq!(
+            generalized(&Matrix::identity(2), &m, 1),
+            Err(MatrixError::NotFinite { row: 0, col: 1 })
+        );
+        // one mass can't have two modes
+        let k = Matrix::from_rows(&[[2.0, -1.0], [-1.0, 1.0]]).unwrap();
+        let m = Matrix::from_rows(&[[0.0, 0.0], [0.0, 1.0]]).unwrap();
+        assert_eq!(
+            generalized(&k, &m, 2),
+            Err(MatrixError::NotPositiveDefinite { column: 0 })
+        );
+        let e = generalized(&k, &m, 1).unwrap();
+        // static condensation of the massless node leaves k = 1/2
+        assert!((e.values[0] - 0.5).abs() < 1e-12);
+        // an unsupported structure is a mechanism
+        let free = Matrix::from_rows(&[[1.0, -1.0], [-1.0, 1.0]]).unwrap();
+        assert!(generalized(&free, &Matrix::identity(2), 1).is_err());
+    }
+}
@@ -0,0 +1,515 @@
+//! # fit
+//!
+//! Least squares fitting of models to measurements: IDF equations, rating
+//! curves, compaction curves, e-log p lines.
+//!
+//! - `line` and `polynomial` fit models which are linear in their
+//!   parameters, and `least_squares` fits any such model given its design
+//!   matrix
+//! - `levenberg_marquardt` fits a model which is not, such as
+//!   `i = a / (t + b)^c`, from a starting guess
+//!
+//! Every fit reports the residuals, R² and a standard error for each
+//! parameter, which assumes independent errors of equal variance.
+//!
+//! ```rust
+//! use civil::calc::fit::line;
+//!
+//! // void ratio against log10 of effective stress on the virgin curve
+//! let log_p = [1.0, 1.3, 1.6, 1.9, 2.2];
+//! let e = [1.10, 1.01, 0.91, 0.83, 0.73];
+//! let fit = line(&log_p, &e).unwrap();
+//! let cc = -fit.parameters[1]; // compression index
+//! assert!((cc - 0.3067).abs() < 1e-4);
+//! assert!(fit.r_squared > 0.99);
+//! ```
+
+use super::linalg::{Matrix, MatrixError};
+use super::nonlinear::jacobian;
+use super::roots::Tolerance;
+use std::error::Error;
+use std::fmt;
+
+/// Fit is the result of a least squares fit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fit {
+    /// The fitted parameters, in the order of the model
+    pub parameters: Vec<f64>,
+    /// Standard error of each parameter
+    pub standard_errors: Vec<f64>,
+    /// Each measurement less the fitted value
+    pub residuals: Vec<f64>,
+    /// Coefficient of determination: the fraction of the variance in the
+    /// measurements explained by the model
+    pub r_squared: f64,
+    /// Iterations taken; zero for linear fits
+    pub iterations: usize,
+}
+
+impl Fit {
+    /// sum_of_squares is the sum of the squared residuals.
+    pub fn sum_of_squares(&self) -> f64 {
+        self.residuals.iter().map(|r| r * r).sum()
+    }
+
+    /// standard_error is the standard error of the regression: the root
+    /// mean square residual, with a degree of freedom used by each
+    /// parameter.
+    pub fn standard_error(&self) -> f64 {
+        (self.sum_of_squares() / (self.residuals.len() - self.parameters.len()) as f64).sqrt()
+    }
+}
+
+/// FitError explains why a fit failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FitError {
+    /// The x values and the measurements are different lengths.
+    Length { x: usize, y: usize },
+    /// There must be more measurements than parameters to estimate errors.
+    TooFew { points: usize, parameters: usize },
+    /// The model or the measurements are not finite at `parameters`.
+    NotFinite { parameters: Vec<f64> },
+    /// The parameters can't be told apart by the measurements: some
+    /// combination of them has no effect on the fit.
+    Singular { error: MatrixError },
+    /// The iteration limit was reached before the tolerance was met.
+    MaxIterations {
+        parameters: Vec<f64>,
+        sum_of_squares: f64,
+    },
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FitError::Length { x, y } => write!(f, "{} x values but {} measurements", x, y),
+            FitError::TooFew { points, parameters } => write!(
+                f,
+                "{} measurements can't fit {} parameters",
+                points, parameters
+            ),
+            FitError::NotFinite { parameters } => {
+                write!(f, "model is not finite at {:?}", parameters)
+            }
+            FitError::Singular { error } => write!(f, "parameters are not independent: {}", error),
+            FitError::MaxIterations { sum_of_squares, .. } => write!(
+                f,
+                "did not converge; sum of squares is still {:.6e}",
+                sum_of_squares
+            ),
+        }
+    }
+}
+
+impl Error for FitError {}
+
+/// least_squares fits `y ≈ A p`, where each column of the design matrix
+/// `A` is a term of the model evaluated at every measurement.
+pub fn least_squares(design: &Matrix, y: &[f64]) -> Result<Fit, FitError> {
+    let (n, m) = design.shape();
+    if n != y.len() {
+        return Err(FitError::Length { x: n, y: y.len() });
+    }
+    if n <= m {
+        return Err(FitError::TooFew {
+            points: n,
+            parameters: m,
+        });
+    }
+    if !finite(&design.data) || !finite(y) {
+        return Err(FitError::NotFinite {
+            parameters: Vec::new(),
+        });
+    }
+    // Normal equations, with the columns scaled to unit length so that
+    // terms of very different sizes don't spoil the conditioning
+    let scale = column_scales(design);
+    let mut a = design.clone();
+    for i in 0..n {
+        for j in 0..m {
+            a[(i, j)] *= scale[j];
+        }
+    }
+    let at = a.transpose();
+    let normal = at.mul(&a).map_err(|error| FitError::Singular { error })?;
+    let inverse = normal
+        .inverse()
+        .map_err(|error| FitError::Singular { error })?;
+    let rhs = at
+        .mul_vec(y)
+        .map_err(|error| FitError::Singular { error })?;
+    let z = normal
+        .solve(&rhs)
+        .map_err(|error| FitError::Singular { error })?;
+    let parameters: Vec<f64> = z.iter().zip(scale.iter()).map(|(z, s)| z * s).collect();
+    let fitted = design
+        .mul_vec(&parameters)
+        .map_err(|error| FitError::Singular { error })?;
+    let residuals: Vec<f64> = y.iter().zip(fitted.iter()).map(|(y, f)| y - f).collect();
+    Ok(summary(parameters, residuals, y, &inverse, &scale, 0))
+}
+
+/// line fits `y = a + b x`; the parameters are `[a, b]`.
+pub fn line(x: &[f64], y: &[f64]) -> Result<Fit, FitError> {
+    polynomial(x, y, 1)
+}
+
+/// polynomial fits `y = p[0] + p[1] x + ... + p[degree] x^degree`. High
+/// degrees are rarely a good idea; the fit becomes ill-conditioned and
+/// wiggles between the measurements.
+///
+/// ```rust
+/// use civil::calc::fit::polynomial;
+///
+/// // dry density (pcf) against moisture content (%) from a Proctor test
+/// let w = [8.0, 10.0, 12.0, 14.0, 16.0];
+/// let density = [108.0, 112.5, 114.6, 113.0, 109.2];
+/// let fit = polynomial(&w, &density, 2).unwrap();
+/// let p = &fit.parameters;
+/// let optimum = -p[1] / (2.0 * p[2]);
+/// assert!((optimum - 12.1).abs() < 0.1);
+/// ```
+pub fn polynomial(x: &[f64], y: &[f64], degree: usize) -> Result<Fit, FitError> {
+    if x.len() != y.len() {
+        return Err(FitError::Length {
+            x: x.len(),
+            y: y.len(),
+        });
+    }
+    let mut design = Matrix::zeros(x.len(), degree + 1);
+    for (i, x) in x.iter().enumerate() {
+        let mut term = 1.0;
+        for j in 0..=degree {
+            design[(i, j)] = term;
+            term *= x;
+        }
+    }
+    least_squares(&design, y)
+}
+
+// Largest damping before giving up on reducing the sum of squares
+const MAX_DAMPING: f64 = 1.0e16;
+
+/// levenberg_marquardt fits `y ≈ model(x, p)` starting from the guess `p0`,
+/// using a forward difference Jacobian. It stops once a step is within
+/// `tol` of the parameters, or no longer reduces the sum of squares.
+///
+/// ```rust
+/// use civil::calc::fit::levenberg_marquardt;
+/// use civil::calc::roots::Tolerance;
+///
+/// // rainfall intensity (in/hr) against duration (min)
+/// let t = [5.0, 10.0, 15.0, 30.0, 60.0, 120.0];
+/// let i = [7.1, 5.7, 4.8, 3.4, 2.2, 1.35];
+/// let idf = |t: f64, p: &[f64]| p[0] / (t + p[1]).powf(p[2]);
+/// let fit = levenberg_marquardt(idf, &t, &i, &[50.0, 5.0, 0.8], Tolerance::default()).unwrap();
+/// assert!(fit.r_squared > 0.999);
+/// assert!(fit.residuals.iter().all(|r| r.abs() < 0.1));
+/// ```
+pub fn levenberg_marquardt<M>(
+    model: M,
+    x: &[f64],
+    y: &[f64],
+    p0: &[f64],
+    tol: Tolerance,
+) -> Result<Fit, FitError>
+where
+    M: Fn(f64, &[f64]) -> f64,
+{
+    if x.len() != y.len() {
+        return Err(FitError::Length {
+            x: x.len(),
+            y: y.len(),
+        });
+    }
+    let (n, m) = (x.len(), p0.len());
+    if n <= m {
+        return Err(FitError::TooFew {
+            points: n,
+            parameters: m,
+        });
+    }
+    let fitted = |p: &[f64]| -> Vec<f64> { x.iter().map(|x| model(*x, p)).collect() };
+    let residuals =
+        |f: &[f64]| -> Vec<f64> { y.iter().zip(f.iter()).map(|(y, f)| y - f).collect() };
+    let mut p = p0.to_vec();
+    let mut f = fitted(&p);
+    if !finite(&f) || !finite(y) {
+        return Err(FitError::NotFinite { parameters: p });
+    }
+    let mut r = residuals(&f);
+    let mut squares = sum_of_squares(&r);
+    let mut damping = 1.0e-3;
+    for iteration in 1..=tol.max_iterations {
+        let j = jacobian(fitted, &p, &f);
+        let jt = j.transpose();
+        let normal = jt.mul(&j).map_err(|error| FitError::Singular { error })?;
+        let gradient = jt
+            .mul_vec(&r)
+            .map_err(|error| FitError::Singular { error })?;
+        // Raise the damping until a step reduces the sum of squares
+        let (step, next, f_next, r_next, s_next) = loop {
+            if damping > MAX_DAMPING {
+                // Nothing helps, so this is a minimum to within rounding
+                return finish(&fitted, p, f, r, y, iteration);
+            }
+            let mut a = normal.clone();
+            for k in 0..m {
+                a[(k, k)] += damping * normal[(k, k)].max(f64::MIN_POSITIVE);
+            }
+            let step = match a.solve(&gradient) {
+                Ok(step) => step,
+                Err(_) => {
+                    damping *= 10.0;
+                    continue;
+                }
+            };
+            let next: Vec<f64> = p.iter().zip(step.iter()).map(|(p, s)| p + s).collect();
+            let f_next = fitted(&next);
+            if finite(&f_next) {
+                let r_next = residuals(&f_next);
+                let s_next = sum_of_squares(&r_next);
+                if s_next <= squares {
+                    break (step, next, f_next, r_next, s_next);
+                }
+            }
+            damping *= 10.0;
+        };
+        damping = (damping / 10.0).max(1.0e-12);
+        let small = step.iter().zip(p.iter()).all(|(s, p)| tol.close(*s, *p));
+        let stalled = squares - s_next <= 4.0 * f64::EPSILON * squares;
+        p = next;
+        f = f_next;
+        r = r_next;
+        squares = s_next;
+        if small || stalled || squares.sqrt() <= tol.f_abs {
+            return finish(&fitted, p, f, r, y, iteration);
+        }
+    }
+    Err(FitError::MaxIterations {
+        parameters: p,
+        sum_of_squares: squares,
+    })
+}
+
+// Standard errors from the Jacobian at the solution
+fn finish<F>(
+    fitted: &F,
+    p: Vec<f64>,
+    f: Vec<f64>,
+    r: Vec<f64>,
+    y: &[f64],
+    iterations: usize,
+) -> Result<Fit, FitError>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    let j = jacobian(fitted, &p, &f);
+    let scale = column_scales(&j);
+    let mut a = j;
+    for i in 0..a.rows() {
+        for (k, s) in scale.iter().enumerate() {
+            a[(i, k)] *= s;
+        }
+    }
+    let normal = a
+        .transpose()
+        .mul(&a)
+        .map_err(|error| FitError::Singular { error })?;
+    let inverse = normal
+        .inverse()
+        .map_err(|error| FitError::Singular { error })?;
+    Ok(summary(p, r, y, &inverse, &scale, iterations))
+}
+
+// Assemble a fit from the inverse of the scaled normal matrix
+fn summary(
+    parameters: Vec<f64>,
+    residuals: Vec<f64>,
+    y: &[f64],
+    inverse: &Matrix,
+    scale: &[f64],
+    iterations: usize,
+) -> Fit {
+    let (n, m) = (residuals.len(), parameters.len());
+    let squares = sum_of_squares(&residuals);
+    let variance = squares / (n - m) as f64;
+    let standard_errors = (0..m)
+        .map(|k| scale[k] * (variance * inverse[(k, k)]).sqrt())
+        .collect();
+    let mean = y.iter().sum::<f64>() / n as f64;
+    let total: f64 = y.iter().map(|y| (y - mean).powi(2)).sum();
+    let r_squared = if total > 0.0 {
+        1.0 - squares / total
+    } else {
+        1.0
+    };
+    Fit {
+        parameters,
+        standard_errors,
+        residuals,
+        r_squared,
+        iterations,
+    }
+}
+
+// Reciprocal of the length of each column, or one for an empty column
+fn column_scales(a: &Matrix) -> Vec<f64> {
+    (0..a.cols())
+        .map(|j| {
+            let norm = (0..a.rows()).map(|i| a[(i, j)].powi(2)).sum::<f64>().sqrt();
+            if norm > 0.0 {
+                1.0 / norm
+            } else {
+                1.0
+            }
+        })
+        .collect()
+}
+
+fn sum_of_squares(v: &[f64]) -> f64 {
+    v.iter().map(|r| r * r).sum()
+}
+
+fn finite(v: &[f64]) -> bool {
+    v.iter().all(|x| x.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{least_squares, levenberg_marquardt, line, polynomial, FitError};
+    use crate::calc::linalg::Matrix;
+    use crate::calc::roots::Tolerance;
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            x: Vec<f64>,
+            y: Vec<f64>,
+            degree: usize,
+            expected: Vec<f64>,
+        }
+        let table = [
+            Row {
+                x: vec![0.0, 1.0, 2.0, 3.0],
+                y: vec![1.0, 3.0, 5.0, 7.0],
+                degree: 1,
+                expected: vec![1.0, 2.0],
+            },
+            Row {
+                x: vec![-2.0, -1.0, 0.0, 1.0, 2.0],
+                y: vec![11.0, 6.0, 3.0, 2.0, 3.0],
+                degree: 2,
+                expected: vec![3.0, -2.0, 1.0],
+            },
+            // terms of very different sizes
+            Row {
+                x: vec![1990.0, 2000.0, 2010.0, 2020.0],
+                y: [1990.0, 2000.0, 2010.0, 2020.0]
+                    .iter()
+                    .map(|t| 5.0 + 0.5 * (t - 2000.0))
+                    .collect(),
+                degree: 1,
+                expected: vec![-995.0, 0.5],
+            },
+        ];
+        for r in table.iter() {
+            let fit = polynomial(&r.x, &r.y, r.degree).unwrap();
+            for (p, e) in fit.parameters.iter().zip(r.expected.iter()) {
+                assert!((p - e).abs() < 1e-8 * e.abs().max(1.0), "{:?}", fit);
+            }
+            assert!((fit.r_squared - 1.0).abs() < 1e-12);
+            assert!(fit.residuals.iter().all(|r| r.abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn standard_errors() {
+        // The textbook formulas for a straight line
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let y = [2.1, 3.9, 6.2, 7.8, 10.1, 12.2];
+        let fit = line(&x, &y).unwrap();
+        let n = x.len() as f64;
+        let mean = x.iter().sum::<f64>() / n;
+        let sxx: f64 = x.iter().map(|x| (x - mean).powi(2)).sum();
+        let s = fit.standard_error();
+        assert!((fit.standard_errors[1] - s / sxx.sqrt()).abs() < 1e-12);
+        let se_a = s * (1.0 / n + mean * mean / sxx).sqrt();
+        assert!((fit.standard_errors[0] - se_a).abs() < 1e-12);
+        assert!(fit.r_squared < 1.0 && fit.r_squared > 0.99);
+        // the residuals of a least squares line sum to zero
+        assert!(fit.residuals.iter().sum::<f64>().abs() < 1e-12);
+        // Levenberg-Marquardt agrees on a linear model
+        let lm = levenberg_marquardt(
+            |x, p| p[0] + p[1] * x,
+            &x,
+            &y,
+            &[0.0, 0.0],
+            Tolerance::default(),
+        )
+        .unwrap();
+        for k in 0..2 {
+            assert!((lm.parameters[k] - fit.parameters[k]).abs() < 1e-9);
+            assert!((lm.standard_errors[k] - fit.standard_errors[k]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn nonlinear() {
+        // An IDF curve recovered from exact data
+        let idf = |t: f64, p: &[f64]| p[0] / (t + p[1]).powf(p[2]);
+        let t = [5.0, 10.0, 15.0, 20.0, 30.0, 60.0, 120.0];
+        let i: Vec<f64> = t.iter().map(|t| idf(*t, &[100.0, 10.0, 0.8])).collect();
+        let fit =
+            levenberg_marquardt(idf, &t, &i, &[50.0, 1.0, 1.0], Tolerance::default()).unwrap();
+        for (p, e) in fit.parameters.iter().zip([100.0, 10.0, 0.8].iter()) {
+            assert!((p - e).abs() < 1e-6 * e, "{:?}", fit);
+        }
+        assert!(fit.iterations > 1);
+        // A rating curve, Q = a (h - h0)^b
+        let rating = |h: f64, p: &[f64]| p[0] * (h - p[1]).max(0.0).powf(p[2]);
+        let h = [1.2, 1.5, 2.0, 2.5, 3.0, 4.0];
+        let q: Vec<f64> = h.iter().map(|h| rating(*h, &[12.0, 0.8, 1.6])).collect();
+        let fit =
+            levenberg_marquardt(rating, &h, &q, &[10.0, 0.5, 1.5], Tolerance::default()).unwrap();
+        assert!((fit.parameters[1] - 0.8).abs() < 1e-6, "{:?}", fit);
+    }
+
+    #[test]
+    fn failures() {
+        assert_eq!(
+            line(&[1.0, 2.0], &[1.0]),
+            Err(FitError::Length { x: 2, y: 1 })
+        );
+        assert_eq!(
+            polynomial(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0], 2),
+            Err(FitError::TooFew {
+                points: 3,
+                parameters: 3
+            })
+        );
+        // two distinct x values can't fix a parabola
+        match polynomial(&[1.0, 1.0, 2.0, 2.0], &[1.0, 2.0, 3.0, 4.0], 2) {
+            Err(FitError::Singular { .. }) => {}
+            other => panic!("expected a singular fit, got {:?}", other),
+        }
+        // nor can a model with a redundant parameter
+        let design = Matrix::from_rows(&[[1.0, 2.0], [1.0, 2.0], [1.0, 2.0]]).unwrap();
+        assert!(least_squares(&design, &[1.0, 2.0, 3.0]).is_err());
+        let tol = Tolerance {
+            max_iterations: 1,
+            ..Tolerance::default()
+        };
+        let fit = levenberg_marquardt(
+            |x, p| p[0] * (p[1] * x).exp(),
+            &[0.0, 1.0, 2.0, 3.0],
+            &[1.0, 2.7, 7.4, 20.1],
+            &[0.1, 0.1],
+            tol,
+        );
+        match fit {
+            Err(FitError::MaxIterations { .. }) => {}
+            other => panic!("expected too many iterations, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,859 @@
+//! # geometry
+//!
+//! Plane geometry. Section properties, catchment areas, channel
+//! cross-sections and earthwork end areas all come down to polygons.
+//!
+//! - `Point` is a location in the plane
+//! - `Segment` is a straight line between two points, and `intersection`
+//!   finds where two of them cross
+//! - `Polyline` is an open chain of segments, such as a ground profile
+//! - `Polygon` is a closed ring with any number of holes, with an area,
+//!   perimeter, centroid, first and second moments and a test for whether
+//!   it contains a point. It can be clipped by a horizontal line, for the
+//!   part of a cross-section below a water surface.
+//!
+//! ```rust
+//! use civil::calc::geometry::{Point, Polygon};
+//!
+//! // a trapezoidal channel, 2 m wide at the base with 2:1 side slopes and
+//! // 1.5 m deep, flowing 1 m deep
+//! let channel = Polygon::new(&[
+//!     Point::new(0.0, 0.0),
+//!     Point::new(2.0, 0.0),
+//!     Point::new(5.0, 1.5),
+//!     Point::new(-3.0, 1.5),
+//! ])
+//! .unwrap();
+//! let flow = channel.clip_below(1.0).unwrap();
+//! assert!((flow.area() - 4.0).abs() < 1e-12);
+//! assert!((channel.perimeter_below(1.0) - (2.0 + 2.0 * 5f64.sqrt())).abs() < 1e-12);
+//! assert!((channel.width_at(1.0) - 6.0).abs() < 1e-12);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+/// GeometryError is returned for shapes which can't be built from the
+/// points given.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeometryError {
+    /// Fewer points than the shape needs.
+    TooFew { needed: usize, found: usize },
+    /// A coordinate is not finite.
+    NotFinite { index: usize },
+    /// The ring encloses no area, eg its points are all on one line.
+    Degenerate,
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeometryError::TooFew { needed, found } => {
+                write!(f, "at least {} points are needed, found {}", needed, found)
+            }
+            GeometryError::NotFinite { index } => {
+                write!(f, "point at index {} is not finite", index)
+            }
+            GeometryError::Degenerate => write!(f, "the ring encloses no area"),
+        }
+    }
+}
+
+impl Error for GeometryError {}
+
+/// Point is a location in the plane.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    /// new creates a point at (x, y).
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    /// distance returns the straight line distance to `other`.
+    pub fn distance(&self, other: Point) -> f64 {
+        (other.x - self.x).hypot(other.y - self.y)
+    }
+}
+
+/// Segment is the straight line from `start` to `end`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    /// new creates the segment from `start` to `end`.
+    pub fn new(start: Point, end: Point) -> Segment {
+        Segment { start, end }
+    }
+
+    /// length returns the length of the segment.
+    pub fn length(&self) -> f64 {
+        self.start.distance(self.end)
+    }
+
+    /// at returns the point a fraction `t` of the way from `start` to
+    /// `end`. Fractions outside [0, 1] are on the line beyond the segment.
+    pub fn at(&self, t: f64) -> Point {
+        Point::new(
+            self.start.x + t * (self.end.x - self.start.x),
+            self.start.y + t * (self.end.y - self.start.y),
+        )
+    }
+
+    /// intersection returns the point where two segments cross, or `None`
+    /// if they don't. Touching at an end counts as crossing. Parallel
+    /// segments never cross, even where they overlap.
+    ///
+    /// ```rust
+    /// use civil::calc::geometry::{Point, Segment};
+    ///
+    /// let a = Segment::new(Point::new(0.0, 0.0), Point::new(2.0, 2.0));
+    /// let b = Segment::new(Point::new(0.0, 2.0), Point::new(2.0, 0.0));
+    /// assert_eq!(a.intersection(&b), Some(Point::new(1.0, 1.0)));
+    /// ```
+    pub fn intersection(&self, other: &Segment) -> Option<Point> {
+        let (t, u) = self.fractions(other)?;
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            Some(self.at(t))
+        } else {
+            None
+        }
+    }
+
+    /// line_intersection returns the point where the lines through two
+    /// segments cross, wherever that is, or `None` if they are parallel.
+    pub fn line_intersection(&self, other: &Segment) -> Option<Point> {
+        self.fractions(other).map(|(t, _)| self.at(t))
+    }
+
+    // Where the lines through the segments cross, as fractions of the way
+    // along each
+    fn fractions(&self, other: &Segment) -> Option<(f64, f64)> {
+        let (dx, dy) = (self.end.x - self.start.x, self.end.y - self.start.y);
+        let (ex, ey) = (other.end.x - other.start.x, other.end.y - other.start.y);
+        let denominator = dx * ey - dy * ex;
+        if denominator.abs() <= f64::EPSILON * dx.hypot(dy) * ex.hypot(ey) {
+            return None;
+        }
+        let (wx, wy) = (other.start.x - self.start.x, other.start.y - self.start.y);
+        Some((
+            (wx * ey - wy * ex) / denominator,
+            (wx * dy - wy * dx) / denominator,
+        ))
+    }
+}
+
+/// Polyline is an open chain of straight segments, such as a ground
+/// profile or a channel bank.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polyline {
+    points: Vec<Point>,
+}
+
+impl Polyline {
+    /// new creates a polyline through at least two points.
+    pub fn new(points: &[Point]) -> Result<Polyline, GeometryError> {
+        check(points, 2)?;
+        Ok(Polyline {
+            points: points.to_vec(),
+        })
+    }
+
+    /// points returns the vertices in order.
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// segments returns the segments from the first point to the last.
+    pub fn segments(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.points.windows(2).map(|w| Segment::new(w[0], w[1]))
+    }
+
+    /// length returns the length along the polyline.
+    pub fn length(&self) -> f64 {
+        self.segments().map(|s| s.length()).sum()
+    }
+
+    /// intersections returns every point where the two polylines cross, in
+    /// order along `self`. A crossing at a shared vertex is reported once.
+    ///
+    /// ```rust
+    /// use civil::calc::geometry::{Point, Polyline};
+    ///
+    /// // where a 2:1 fill slope from the shoulder meets the ground
+    /// let ground = Polyline::new(&[Point::new(0.0, 0.0), Point::new(20.0, 2.0)]).unwrap();
+    /// let slope = Polyline::new(&[Point::new(6.0, 3.0), Point::new(20.0, -4.0)]).unwrap();
+    /// let toe = ground.intersections(&slope);
+    /// assert_eq!(toe.len(), 1);
+    /// assert!((toe[0].x - 10.0).abs() < 1e-12);
+    /// ```
+    pub fn intersections(&self, other: &Polyline) -> Vec<Point> {
+        let (last, other_last) = (self.points.len() - 2, other.points.len() - 2);
+        // A fraction belongs to a segment from its start up to, but not
+        // including, its end, unless it is the last segment
+        let within =
+            |f: f64, i: usize, last: usize| f >= 0.0 && (f < 1.0 || (f == 1.0 && i == last));
+        let mut found = vec![];
+        for (i, a) in self.segments().enumerate() {
+            let mut hits: Vec<f64> = other
+                .segments()
+                .enumerate()
+                .filter_map(|(j, b)| a.fractions(&b).filter(|&(_, u)| within(u, j, other_last)))
+                .map(|(t, _)| t)
+                .filter(|&t| within(t, i, last))
+                .collect();
+            hits.sort_by(|a, b| a.total_cmp(b));
+            found.extend(hits.into_iter().map(|t| a.at(t)));
+        }
+        found
+    }
+}
+
+/// SecondMoments are the second moments of area of a shape about a pair
+/// of axes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecondMoments {
+    /// Moment about the x axis, ∫y² dA
+    pub ix: f64,
+    /// Moment about the y axis, ∫x² dA
+    pub iy: f64,
+    /// Product of inertia, ∫xy dA
+    pub ixy: f64,
+}
+
+impl SecondMoments {
+    /// principal returns the greatest and least moments about any pair of
+    /// axes through the same origin, and the angle in radians, counter
+    /// clockwise from the x axis, of the axis about which the moment is
+    /// greatest.
+    pub fn principal(&self) -> (f64, f64, f64) {
+        let average = (self.ix + self.iy) / 2.0;
+        let radius = ((self.ix - self.iy) / 2.0).hypot(self.ixy);
+        let angle = (-2.0 * self.ixy).atan2(self.ix - self.iy) / 2.0;
+        (average + radius, average - radius, angle)
+    }
+}
+
+/// Polygon is a closed ring of points with any number of holes. The rings
+/// close themselves; the first point needn't be repeated at the end. They
+/// may be given in either direction, but holes are assumed to lie inside
+/// the exterior and not to overlap one another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    // Counter clockwise
+    exterior: Vec<Point>,
+    // Clockwise, so that every ring adds its signed area
+    holes: Vec<Vec<Point>>,
+}
+
+impl Polygon {
+    /// new creates a polygon with no holes from at least three points.
+    pub fn new(exterior: &[Point]) -> Result<Polygon, GeometryError> {
+        Ok(Polygon {
+            exterior: ring(exterior, 1.0)?,
+            holes: vec![],
+        })
+    }
+
+    /// with_hole cuts a hole out of the polygon.
+    ///
+    /// ```rust
+    /// use civil::calc::geometry::{Point, Polygon};
+    ///
+    /// // a 300 x 200 box culvert with 25 thick walls
+    /// let rectangle = |w: f64, h: f64, t: f64| {
+    ///     vec![
+    ///         Point::new(t, t),
+    ///         Point::new(w - t, t),
+    ///         Point::new(w - t, h - t),
+    ///         Point::new(t, h - t),
+    ///     ]
+    /// };
+    /// let culvert = Polygon::new(&rectangle(300.0, 200.0, 0.0))
+    ///     .unwrap()
+    ///     .with_hole(&rectangle(300.0, 200.0, 25.0))
+    ///     .unwrap();
+    /// assert_eq!(culvert.area(), 300.0 * 200.0 - 250.0 * 150.0);
+    /// assert_eq!(culvert.centroid(), Point::new(150.0, 100.0));
+    /// ```
+    pub fn with_hole(mut self, hole: &[Point]) -> Result<Polygon, GeometryError> {
+        self.holes.push(ring(hole, -1.0)?);
+        Ok(self)
+    }
+
+    /// exterior returns the outer ring, counter clockwise.
+    pub fn exterior(&self) -> &[Point] {
+        &self.exterior
+    }
+
+    /// holes returns the rings of the holes, each clockwise.
+    pub fn holes(&self) -> &[Vec<Point>] {
+        &self.holes
+    }
+
+    /// area returns the area, less that of the holes.
+    pub fn area(&self) -> f64 {
+        self.integrals(self.exterior[0]).area
+    }
+
+    /// perimeter returns the length around every ring, holes included.
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|s| s.length()).sum()
+    }
+
+    /// centroid returns the center of area.
+    pub fn centroid(&self) -> Point {
+        let origin = self.exterior[0];
+        let m = self.integrals(origin);
+        Point::new(origin.x + m.qy / m.area, origin.y + m.qx / m.area)
+    }
+
+    /// first_moments returns `(Qx, Qy)`, the first moments of area about
+    /// the x and y axes: ∫y dA and ∫x dA.
+    pub fn first_moments(&self) -> (f64, f64) {
+        let (area, c) = (self.area(), self.centroid());
+        (area * c.y, area * c.x)
+    }
+
+    /// second_moments returns the second moments of area about the x and y
+    /// axes.
+    pub fn second_moments(&self) -> SecondMoments {
+        let (area, c) = (self.area(), self.centroid());
+        let i = self.centroidal_moments();
+        // Parallel axes
+        SecondMoments {
+            ix: i.ix + area * c.y * c.y,
+            iy: i.iy + area * c.x * c.x,
+            ixy: i.ixy + area * c.x * c.y,
+        }
+    }
+
+    /// centroidal_moments returns the second moments of area about axes
+    /// through the centroid, parallel to x and y, as used for bending.
+    pub fn centroidal_moments(&self) -> SecondMoments {
+        let m = self.integrals(self.centroid());
+        SecondMoments {
+            ix: m.ix,
+            iy: m.iy,
+            ixy: m.ixy,
+        }
+    }
+
+    /// contains is true when `p` is inside the polygon and not inside one
+    /// of its holes. Points on the boundary may go either way.
+    pub fn contains(&self, p: Point) -> bool {
+        self.edges()
+            .filter(|s| (s.start.y > p.y) != (s.end.y > p.y))
+            .filter(|s| p.x < s.at((p.y - s.start.y) / (s.end.y - s.start.y)).x)
+            .count()
+            % 2
+            == 1
+    }
+
+    /// clip_below returns the part of the polygon below the horizontal line
+    /// at `y`, or `None` if none of it is. For a cross-section and a water
+    /// level, this is the flow area. Where the line cuts the polygon more
+    /// than once the pieces are joined along the line, which leaves the
+    /// area and moments right but adds to the perimeter; use
+    /// `perimeter_below` for the wetted perimeter.
+    pub fn clip_below(&self, y: f64) -> Option<Polygon> {
+        self.clip(y, 1.0)
+    }
+
+    /// clip_above returns the part of the polygon above the horizontal line
+    /// at `y`, or `None` if none of it is. See `clip_below`.
+    pub fn clip_above(&self, y: f64) -> Option<Polygon> {
+        self.clip(y, -1.0)
+    }
+
+    /// perimeter_below returns the length of the boundary below the
+    /// horizontal line at `y`: the wetted perimeter, when `y` is a water
+    /// level. Edges lying along the line aren't counted.
+    pub fn perimeter_below(&self, y: f64) -> f64 {
+        self.edges()
+            .map(|s| {
+                let (low, high) = if s.start.y <= s.end.y {
+                    (s.start.y, s.end.y)
+                } else {
+                    (s.end.y, s.start.y)
+                };
+                if low >= y {
+                    0.0
+                } else if high <= y {
+                    s.length()
+                } else {
+                    s.length() * (y - low) / (high - low)
+                }
+            })
+            .sum()
+    }
+
+    /// width_at returns the total width of the polygon along the horizontal
+    /// line at `y`, leaving out any holes: the top width, when `y` is a
+    /// water level.
+    pub fn width_at(&self, y: f64) -> f64 {
+        let mut x: Vec<f64> = self
+            .edges()
+            .filter(|s| (s.start.y > y) != (s.end.y > y))
+            .map(|s| s.at((y - s.start.y) / (s.end.y - s.start.y)).x)
+            .collect();
+        x.sort_by(|a, b| a.total_cmp(b));
+        x.chunks(2)
+            .filter(|pair| pair.len() == 2)
+            .map(|pair| pair[1] - pair[0])
+            .sum()
+    }
+
+    // Every edge of every ring
+    fn edges(&self) -> impl Iterator<Item = Segment> + '_ {
+        std::iter::once(&self.exterior)
+            .chain(self.holes.iter())
+            .flat_map(|ring| {
+                ring.iter()
+                    .zip(ring.iter().cycle().skip(1))
+                    .map(|(&a, &b)| Segment::new(a, b))
+            })
+    }
+
+    // Area, first and second moments with coordinates measured from
+    // `origin`, which keeps their precision for shapes far from zero
+    fn integrals(&self, origin: Point) -> Integrals {
+        let mut m = Integrals::default();
+        for s in self.edges() {
+            let (x0, y0) = (s.start.x - origin.x, s.start.y - origin.y);
+            let (x1, y1) = (s.end.x - origin.x, s.end.y - origin.y);
+            let a = x0 * y1 - x1 * y0;
+            m.area += a;
+            m.qx += (y0 + y1) * a;
+            m.qy += (x0 + x1) * a;
+            m.ix += (y0 * y0 + y0 * y1 + y1 * y1) * a;
+            m.iy += (x0 * x0 + x0 * x1 + x1 * x1) * a;
+            m.ixy += (x0 * y1 + 2.0 * x0 * y0 + 2.0 * x1 * y1 + x1 * y0) * a;
+        }
+        Integrals {
+            area: m.area / 2.0,
+            qx: m.qx / 6.0,
+            qy: m.qy / 6.0,
+            ix: m.ix / 12.0,
+            iy: m.iy / 12.0,
+            ixy: m.ixy / 24.0,
+        }
+    }
+
+    // Clip every ring to the side of the line at `y` where `side * (p.y -
+    // y)` isn't positive
+    fn clip(&self, y: f64, side: f64) -> Option<Polygon> {
+        let exterior = clip_ring(&self.exterior, y, side);
+        if exterior.len() < 3 || signed_area(&exterior) <= 0.0 {
+            return None;
+        }
+        let holes = self
+            .holes
+            .iter()
+            .map(|hole| clip_ring(hole, y, side))
+            .filter(|hole| hole.len() >= 3 && signed_area(hole) < 0.0)
+            .collect();
+        Some(Polygon { exterior, holes })
+    }
+}
+
+// Integrals over a polygon
+#[derive(Debug, Default)]
+struct Integrals {
+    area: f64,
+    qx: f64,
+    qy: f64,
+    ix: f64,
+    iy: f64,
+    ixy: f64,
+}
+
+// Check there are enough points, all finite
+fn check(points: &[Point], needed: usize) -> Result<(), GeometryError> {
+    if points.len() < needed {
+        return Err(GeometryError::TooFew {
+            needed,
+            found: points.len(),
+        });
+    }
+    match points
+        .iter()
+        .position(|p| !p.x.is_finite() || !p.y.is_finite())
+    {
+        Some(index) => Err(GeometryError::NotFinite { index }),
+        None => Ok(()),
+    }
+}
+
+// Validate a ring, drop a repeated first point and orient it counter
+// clockwise when `direction` is positive and clockwise otherwise
+fn ring(points: &[Point], direction: f64) -> Result<Vec<Point>, GeometryError> {
+    let closed = points.len() > 1 && points.first() == points.last();
+    let points = if closed {
+        &points[..points.len() - 1]
+    } else {
+        points
+    };
+    check(points, 3)?;
+    let area = signed_area(points);
+    if area == 0.0 {
+        return Err(GeometryError::Degenerate);
+    }
+    let mut ring = points.to_vec();
+    if area * direction < 0.0 {
+        ring.reverse();
+    }
+    Ok(ring)
+}
+
+// Shoelace area, positive for a counter clockwise ring
+fn signed_area(ring: &[Point]) -> f64 {
+    let o = ring[0];
+    ring.iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(a, b)| (a.x - o.x) * (b.y - o.y) - (b.x - o.x) * (a.y - o.y))
+        .sum::<f64>()
+        / 2.0
+}
+
+// Sutherland-Hodgman against a single horizontal line
+fn clip_ring(ring: &[Point], y: f64, side: f64) -> Vec<Point> {
+    let inside = |p: &Point| side * (p.y - y) <= 0.0;
+    let mut clipped = vec![];
+    for (a, b) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+        if inside(a) {
+            clipped.push(*a);
+        }
+        if inside(a) != inside(b) {
+            let t = (y - a.y) / (b.y - a.y);
+            clipped.push(Point::new(a.x + t * (b.x - a.x), y));
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GeometryError, Point, Polygon, Polyline, Segment};
+    use std::f64::consts::PI;
+
+    const PRECISION: f64 = 1e-12;
+
+    fn points(xy: &[(f64, f64)]) -> Vec<Point> {
+        xy.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            name: &'static str,
+            exterior: Vec<(f64, f64)>,
+            holes: Vec<Vec<(f64, f64)>>,
+            area: f64,
+            perimeter: f64,
+            centroid: (f64, f64),
+            // centroidal ix, iy, ixy
+            moments: (f64, f64, f64),
+        }
+        let table = [
+            Row {
+                name: "rectangle",
+                exterior: vec![(1.0, 2.0), (5.0, 2.0), (5.0, 8.0), (1.0, 8.0)],
+                holes: vec![],
+                area: 24.0,
+                perimeter: 20.0,
+                centroid: (3.0, 5.0),
+                moments: (4.0 * 216.0 / 12.0, 6.0 * 64.0 / 12.0, 0.0),
+            },
+            Row {
+                name: "clockwise right triangle",
+                exterior: vec![(0.0, 0.0), (0.0, 3.0), (6.0, 0.0)],
+                holes: vec![],
+                area: 9.0,
+                perimeter: 9.0 + 45f64.sqrt(),
+                centroid: (2.0, 1.0),
+                // bh³/36, hb³/36 and -b²h²/72
+                moments: (6.0 * 27.0 / 36.0, 3.0 * 216.0 / 36.0, -36.0 * 9.0 / 72.0),
+            },
+            Row {
+                name: "angle",
+                exterior: vec![
+                    (0.0, 0.0),
+                    (4.0, 0.0),
+                    (4.0, 1.0),
+                    (1.0, 1.0),
+                    (1.0, 3.0),
+                    (0.0, 3.0),
+                ],
+                holes: vec![],
+                area: 6.0,
+                perimeter: 14.0,
+                centroid: (1.5, 1.0),
+                // two rectangles about the composite centroid
+                moments: (
+                    4.0 / 12.0 + 4.0 * 0.25 + 8.0 / 12.0 + 2.0 * 1.0,
+                    64.0 / 12.0 + 4.0 * 0.25 + 2.0 / 12.0 + 2.0 * 1.0,
+                    4.0 * 0.5 * -0.5 - 2.0 * 1.0 * 1.0,
+                ),
+            },
+            Row {
+                name: "square tube, closed rings",
+                exterior: vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)],
+                holes: vec![vec![
+                    (1.0, 1.0),
+                    (3.0, 1.0),
+                    (3.0, 3.0),
+                    (1.0, 3.0),
+                    (1.0, 1.0),
+                ]],
+                area: 12.0,
+                perimeter: 24.0,
+                centroid: (2.0, 2.0),
+                moments: (240.0 / 12.0, 240.0 / 12.0, 0.0),
+            },
+        ];
+        for r in table.iter() {
+            let mut p = Polygon::new(&points(&r.exterior)).unwrap();
+            for hole in r.holes.iter() {
+                p = p.with_hole(&points(hole)).unwrap();
+            }
+            let c = p.centroid();
+            let i = p.centroidal_moments();
+            assert!(
+                (p.area() - r.area).abs() < PRECISION,
+                "{}: {}",
+                r.name,
+                p.area()
+            );
+            assert!(
+                (p.perimeter() - r.perimeter).abs() < PRECISION,
+                "{}",
+                r.name
+            );
+            assert!(
+                (c.x - r.centroid.0).abs() < PRECISION,
+                "{}: {:?}",
+                r.name,
+                c
+            );
+            assert!(
+                (c.y - r.centroid.1).abs() < PRECISION,
+                "{}: {:?}",
+                r.name,
+                c
+            );
+            assert!(
+                (i.ix - r.moments.0).abs() < PRECISION,
+                "{}: {:?}",
+                r.name,
+                i
+            );
+            assert!(
+                (i.iy - r.moments.1).abs() < PRECISION,
+                "{}: {:?}",
+                r.name,
+                i
+            );
+            assert!(
+                (i.ixy - r.moments.2).abs() < PRECISION,
+                "{}: {:?}",
+                r.name,
+                i
+            );
+            // about the axes
+            let (qx, qy) = p.first_moments();
+            assert!((qx - r.area * c.y).abs() < PRECISION && (qy - r.area * c.x).abs() < PRECISION);
+            let o = p.second_moments();
+            assert!(
+                (o.ix - i.ix - r.area * c.y * c.y).abs() < 1e-9,
+                "{}",
+                r.name
+            );
+            assert!(
+                (o.ixy - i.ixy - r.area * c.x * c.y).abs() < 1e-9,
+                "{}",
+                r.name
+            );
+        }
+    }
+
+    #[test]
+    fn far_from_the_origin() {
+        // survey coordinates shouldn't cost precision
+        let (x, y) = (500_000.0, 4_000_000.0);
+        let p = Polygon::new(&points(&[
+            (x, y),
+            (x + 3.0, y),
+            (x + 3.0, y + 2.0),
+            (x, y + 2.0),
+        ]))
+        .unwrap();
+        assert!((p.area() - 6.0).abs() < 1e-9);
+        let i = p.centroidal_moments();
+        assert!((i.ix - 3.0 * 8.0 / 12.0).abs() < 1e-9, "{:?}", i);
+        assert!(i.ixy.abs() < 1e-9, "{:?}", i);
+    }
+
+    #[test]
+    fn principal_axes() {
+        // a 1 x 4 rectangle turned 30° counter clockwise
+        let turn = |x: f64, y: f64| {
+            let (s, c) = (PI / 6.0).sin_cos();
+            (x * c - y * s, x * s + y * c)
+        };
+        let corners = [
+            turn(-2.0, -0.5),
+            turn(2.0, -0.5),
+            turn(2.0, 0.5),
+            turn(-2.0, 0.5),
+        ];
+        let p = Polygon::new(&points(&corners)).unwrap();
+        let (max, min, angle) = p.centroidal_moments().principal();
+        assert!((max - 16.0 / 3.0).abs() < PRECISION, "{}", max);
+        assert!((min - 1.0 / 3.0).abs() < PRECISION, "{}", min);
+        // the long axis carries the least moment, so the greatest is about
+        // the axis square to it
+        assert!(
+            (angle - (PI / 6.0 - PI / 2.0)).abs() < PRECISION,
+            "{}",
+            angle
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let p = Polygon::new(&points(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]))
+            .unwrap()
+            .with_hole(&points(&[(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)]))
+            .unwrap();
+        assert!(p.contains(Point::new(0.5, 2.0)));
+        assert!(p.contains(Point::new(3.5, 3.5)));
+        assert!(!p.contains(Point::new(2.0, 2.0)));
+        assert!(!p.contains(Point::new(5.0, 2.0)));
+        assert!(!p.contains(Point::new(-1.0, 4.0)));
+        // a ray through a vertex is counted once
+        let diamond =
+            Polygon::new(&points(&[(0.0, -1.0), (1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)])).unwrap();
+        assert!(diamond.contains(Point::new(0.0, 0.0)));
+        assert!(!diamond.contains(Point::new(-2.0, 0.0)));
+    }
+
+    #[test]
+    fn clipping() {
+        // a W shaped section with two channels, 1 m deep, which overtop
+        // into one above 1 m
+        let w = Polygon::new(&points(&[
+            (0.0, 2.0),
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (3.0, 1.0),
+            (3.0, 0.0),
+            (5.0, 0.0),
+            (5.0, 2.0),
+        ]))
+        .unwrap();
+        let low = w.clip_below(0.5).unwrap();
+        assert!((low.area() - 2.0).abs() < PRECISION);
+        assert!((low.centroid().y - 0.25).abs() < PRECISION);
+        assert!((low.centroid().x - 2.5).abs() < PRECISION);
+        assert!((w.width_at(0.5) - 4.0).abs() < PRECISION);
+        assert!((w.perimeter_below(0.5) - 6.0).abs() < PRECISION);
+        let high = w.clip_below(1.5).unwrap();
+        assert!((high.area() - 6.5).abs() < PRECISION);
+        assert!((w.width_at(1.5) - 5.0).abs() < PRECISION);
+        // the crest at 1 m is wetted, the top at 2 m isn't
+        assert!((w.perimeter_below(1.5) - 10.0).abs() < PRECISION);
+        // above and below make up the whole
+        let above = w.clip_above(1.5).unwrap();
+        assert!((above.area() + high.area() - w.area()).abs() < PRECISION);
+        assert!(w.clip_below(0.0).is_none());
+        assert!(w.clip_above(2.0).is_none());
+        assert_eq!(w.clip_below(3.0).unwrap().area(), w.area());
+        // holes are clipped with the rest
+        let tube = Polygon::new(&points(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]))
+            .unwrap()
+            .with_hole(&points(&[(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)]))
+            .unwrap();
+        let half = tube.clip_below(2.0).unwrap();
+        assert_eq!(half.holes().len(), 1);
+        assert!((half.area() - 6.0).abs() < PRECISION);
+        assert!((tube.width_at(2.0) - 2.0).abs() < PRECISION);
+        assert_eq!(tube.clip_below(0.5).unwrap().holes().len(), 0);
+    }
+
+    #[test]
+    fn intersections() {
+        let a = Segment::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+        let b = Segment::new(Point::new(1.0, -1.0), Point::new(1.0, 1.0));
+        assert_eq!(a.intersection(&b), Some(Point::new(1.0, 0.0)));
+        // touching ends count
+        let c = Segment::new(Point::new(4.0, 0.0), Point::new(5.0, 3.0));
+        assert_eq!(a.intersection(&c), Some(Point::new(4.0, 0.0)));
+        // short of one another, but the lines cross
+        let d = Segment::new(Point::new(6.0, 1.0), Point::new(6.0, 2.0));
+        assert_eq!(a.intersection(&d), None);
+        assert_eq!(a.line_intersection(&d), Some(Point::new(6.0, 0.0)));
+        // parallel
+        let e = Segment::new(Point::new(0.0, 1.0), Point::new(4.0, 1.0));
+        assert_eq!(a.line_intersection(&e), None);
+        assert_eq!(a.intersection(&a), None);
+        // a zig-zag across a level line, crossing once at a shared vertex
+        let zig = Polyline::new(&points(&[
+            (0.0, -1.0),
+            (1.0, 1.0),
+            (2.0, 0.0),
+            (3.0, -1.0),
+            (4.0, 1.0),
+        ]))
+        .unwrap();
+        let level = Polyline::new(&points(&[(-1.0, 0.0), (5.0, 0.0)])).unwrap();
+        let x: Vec<f64> = zig.intersections(&level).iter().map(|p| p.x).collect();
+        assert_eq!(x, vec![0.5, 2.0, 3.5]);
+        let x: Vec<f64> = level.intersections(&zig).iter().map(|p| p.x).collect();
+        assert_eq!(x, vec![0.5, 2.0, 3.5]);
+        assert!((zig.length() - (2.0 * 5f64.sqrt() + 2.0 * 2f64.sqrt())).abs() < PRECISION);
+    }
+
+    #[test]
+    fn failures() {
+        assert_eq!(
+            Polygon::new(&points(&[(0.0, 0.0), (1.0, 0.0), (0.0, 0.0)])),
+            Err(GeometryError::TooFew {
+                needed: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Polygon::new(&points(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)])),
+            Err(GeometryError::Degenerate)
+        );
+        assert_eq!(
+            Polyline::new(&points(&[(0.0, 0.0), (f64::NAN, 1.0)])),
+            Err(GeometryError::NotFinite { index: 1 })
+        );
+        assert_eq!(
+            Polyline::new(&[Point::default()]),
+            Err(GeometryError::TooFew {
+                needed: 2,
+                found: 1
+            })
+        );
+        let square =
+            Polygon::new(&points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)])).unwrap();
+        assert_eq!(
+            square.with_hole(&[]),
+            Err(GeometryError::TooFew {
+                needed: 3,
+                found: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,547 @@
+//! # interp
+//!
+//! Reading values between the rows of a table: IDF curves, nomographs,
+//! adjustment factors, roughness by depth.
+//!
+//! A `Curve` interpolates a one dimensional table in one of three ways:
+//!
+//! - `Method::Linear` joins the points with straight lines
+//! - `Method::LogLog` joins them with straight lines on log-log paper, for
+//!   tables which follow a power law, such as rainfall intensity against
+//!   duration
+//! - `Method::MonotoneCubic` is a smooth curve which never overshoots: it
+//!   only rises where the table rises and is flat at every local extreme
+//!
+//! A `Grid` interpolates a two dimensional table bilinearly.
+//!
+//! Outside the table, both do what `Extrapolate` says: return an error, hold
+//! the value at the nearest edge, or carry on along the end of the curve.
+//!
+//! ```rust
+//! use civil::calc::interp::{Curve, Extrapolate, Method};
+//!
+//! // intensity (in/hr) against duration (min) for a 10 year storm
+//! let idf = Curve::new(
+//!     Method::LogLog,
+//!     &[5.0, 10.0, 15.0, 30.0, 60.0],
+//!     &[7.0, 5.6, 4.7, 3.3, 2.1],
+//! )
+//! .unwrap();
+//! let i = idf.eval(20.0).unwrap();
+//! assert!(i < 4.7 && i > 3.3);
+//! // a 2 hour storm is off the end of the table
+//! assert!(idf.eval(120.0).is_err());
+//! let idf = idf.extrapolate(Extrapolate::Linear);
+//! assert!(idf.eval(120.0).unwrap() < 2.1);
+//! ```
+
+use super::linalg::Matrix;
+use std::error::Error;
+use std::fmt;
+
+/// Method is how a `Curve` fills in between the points of its table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Straight lines between the points
+    Linear,
+    /// Straight lines between the logarithms of the points, so that a power
+    /// law is reproduced exactly. Every value must be positive.
+    LogLog,
+    /// A piecewise cubic with slopes chosen to preserve the shape of the
+    /// data (Fritsch and Carlson), so it never overshoots
+    MonotoneCubic,
+}
+
+/// Extrapolate says what to do with values outside the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Extrapolate {
+    /// Return `InterpError::OutOfRange`
+    #[default]
+    Error,
+    /// Use the value at the nearest edge of the table
+    Clamp,
+    /// Continue in a straight line from the end of the curve, in log-log
+    /// space for `Method::LogLog`
+    Linear,
+}
+
+/// InterpError is returned for tables which can't be interpolated, and for
+/// values outside a table when extrapolation is not allowed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpError {
+    /// The x values and the y values are different lengths.
+    Length { x: usize, y: usize },
+    /// The table has fewer than the two points needed along an axis.
+    TooFew { found: usize },
+    /// The x values must be finite and strictly increasing, and aren't at
+    /// `index`.
+    Unsorted { index: usize },
+    /// A value which needs a logarithm is not positive.
+    NotPositive { index: usize },
+    /// A table value is not finite.
+    NotFinite { index: usize },
+    /// The shape of a 2-D table, as (rows, columns), doesn't match its
+    /// axes.
+    Shape {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// `x` is outside the table, which covers `min` to `max`.
+    OutOfRange { x: f64, min: f64, max: f64 },
+}
+
+impl fmt::Display for InterpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InterpError::Length { x, y } => write!(f, "{} x values but {} y values", x, y),
+            InterpError::TooFew { found } => {
+                write!(f, "at least 2 points are needed, found {}", found)
+            }
+            InterpError::Unsorted { index } => {
+                write!(f, "x values must increase, but don't at index {}", index)
+            }
+            InterpError::NotPositive { index } => {
+                write!(
+                    f,
+                    "log-log tables must be positive, but aren't at index {}",
+                    index
+                )
+            }
+            InterpError::NotFinite { index } => write!(f, "value at index {} is not finite", index),
+            InterpError::Shape { expected, found } => write!(
+                f,
+                "expected a {}x{} table, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            InterpError::OutOfRange { x, min, max } => {
+                write!(
+                    f,
+                    "{} is outside the table, which covers {} to {}",
+                    x, min, max
+                )
+            }
+        }
+    }
+}
+
+impl Error for InterpError {}
+
+/// Curve interpolates a one dimensional table of y against x.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    method: Method,
+    extrapolate: Extrapolate,
+    // Logarithms of the table for Method::LogLog
+    x: Vec<f64>,
+    y: Vec<f64>,
+    // The slope at each point for Method::MonotoneCubic
+    slopes: Vec<f64>,
+}
+
+impl Curve {
+    /// new builds a curve through the points `(x[i], y[i])`. The x values
+    /// must be strictly increasing. Values outside the table are an error
+    /// until `extrapolate` says otherwise.
+    pub fn new(method: Method, x: &[f64], y: &[f64]) -> Result<Curve, InterpError> {
+        if x.len() != y.len() {
+            return Err(InterpError::Length {
+                x: x.len(),
+                y: y.len(),
+            });
+        }
+        axis(x)?;
+        if let Some(index) = y.iter().position(|v| !v.is_finite()) {
+            return Err(InterpError::NotFinite { index });
+        }
+        let (x, y) = if method == Method::LogLog {
+            if let Some(index) = x
+                .iter()
+                .zip(y.iter())
+                .position(|(a, b)| *a <= 0.0 || *b <= 0.0)
+            {
+                return Err(InterpError::NotPositive { index });
+            }
+            (
+                x.iter().map(|v| v.ln()).collect(),
+                y.iter().map(|v| v.ln()).collect(),
+            )
+        } else {
+            (x.to_vec(), y.to_vec())
+        };
+        let slopes = if method == Method::MonotoneCubic {
+            monotone_slopes(&x, &y)
+        } else {
+            Vec::new()
+        };
+        Ok(Curve {
+            method,
+            extrapolate: Extrapolate::Error,
+            x,
+            y,
+            slopes,
+        })
+    }
+
+    /// extrapolate sets what happens outside the table.
+    pub fn extrapolate(mut self, policy: Extrapolate) -> Curve {
+        self.extrapolate = policy;
+        self
+    }
+
+    /// range is the smallest and largest x in the table.
+    pub fn range(&self) -> (f64, f64) {
+        let (min, max) = (self.x[0], self.x[self.x.len() - 1]);
+        if self.method == Method::LogLog {
+            (min.exp(), max.exp())
+        } else {
+            (min, max)
+        }
+    }
+
+    /// eval interpolates the table at `x`.
+    pub fn eval(&self, x: f64) -> Result<f64, InterpError> {
+        let (min, max) = self.range();
+        let mut u = if self.method == Method::LogLog {
+            if x > 0.0 {
+                x.ln()
+            } else if self.extrapolate == Extrapolate::Clamp {
+                self.x[0]
+            } else {
+                // There's no logarithm to extrapolate to
+                return Err(InterpError::OutOfRange { x, min, max });
+            }
+        } else {
+            x
+        };
+        let n = self.x.len();
+        let (first, last) = (self.x[0], self.x[n - 1]);
+        if !(first..=last).contains(&u) {
+            match self.extrapolate {
+                Extrapolate::Error => return Err(InterpError::OutOfRange { x, min, max }),
+                Extrapolate::Clamp => u = u.clamp(first, last),
+                Extrapolate::Linear => {}
+            }
+        }
+        let v = if self.method == Method::MonotoneCubic {
+            if u < first {
+                self.y[0] + self.slopes[0] * (u - first)
+            } else if u > last {
+                self.y[n - 1] + self.slopes[n - 1] * (u - last)
+            } else {
+                let k = segment(&self.x, u);
+                let h = self.x[k + 1] - self.x[k];
+                let t = (u - self.x[k]) / h;
+                let s = 1.0 - t;
+                (1.0 + 2.0 * t) * s * s * self.y[k]
+                    + t * s * s * h * self.slopes[k]
+                    + t * t * (3.0 - 2.0 * t) * self.y[k + 1]
+                    - t * t * s * h * self.slopes[k + 1]
+            }
+        } else {
+            // Linear in (possibly logarithmic) coordinates. The end segments
+            // carry on past the table.
+            let k = segment(&self.x, u);
+            let t = (u - self.x[k]) / (self.x[k + 1] - self.x[k]);
+            self.y[k] + t * (self.y[k + 1] - self.y[k])
+        };
+        Ok(if self.method == Method::LogLog {
+            v.exp()
+        } else {
+            v
+        })
+    }
+}
+
+/// Grid interpolates a two dimensional table bilinearly. `z[(i, j)]` is
+/// the value at `x[i]`, `y[j]`.
+///
+/// ```rust
+/// use civil::calc::interp::Grid;
+/// use civil::calc::linalg::Matrix;
+///
+/// // a factor tabulated against depth (rows) and width (columns)
+/// let z = Matrix::from_rows(&[[1.0, 1.2], [1.1, 1.5]]).unwrap();
+/// let g = Grid::new(&[2.0, 4.0], &[10.0, 20.0], z).unwrap();
+/// assert!((g.eval(3.0, 15.0).unwrap() - 1.2).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    z: Matrix,
+    extrapolate: Extrapolate,
+}
+
+impl Grid {
+    /// new builds a grid over the axes `x` and `y`, which must both be
+    /// strictly increasing. `z` has a row for each x and a column for each
+    /// y. Values outside the table are an error until `extrapolate` says
+    /// otherwise.
+    pub fn new(x: &[f64], y: &[f64], z: Matrix) -> Result<Grid, InterpError> {
+        axis(x)?;
+        axis(y)?;
+        if z.shape() != (x.len(), y.len()) {
+            return Err(InterpError::Shape {
+                expected: (x.len(), y.len()),
+                found: z.shape(),
+            });
+        }
+        if let Some(index) = z.data.iter().position(|v| !v.is_finite()) {
+            return Err(InterpError::NotFinite { index });
+        }
+        Ok(Grid {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            z,
+            extrapolate: Extrapolate::Error,
+        })
+    }
+
+    /// extrapolate sets what happens outside the table, along either axis.
+    pub fn extrapolate(mut self, policy: Extrapolate) -> Grid {
+        self.extrapolate = policy;
+        self
+    }
+
+    /// eval interpolates the table at `(x, y)`.
+    pub fn eval(&self, x: f64, y: f64) -> Result<f64, InterpError> {
+        let (i, tx) = self.locate(&self.x, x)?;
+        let (j, ty) = self.locate(&self.y, y)?;
+        let z = &self.z;
+        let low = z[(i, j)] + ty * (z[(i, j + 1)] - z[(i, j)]);
+        let high = z[(i + 1, j)] + ty * (z[(i + 1, j + 1)] - z[(i + 1, j)]);
+        Ok(low + tx * (high - low))
+    }
+
+    // The cell along an axis and the fraction of the way across it,
+    // applying the extrapolation policy
+    fn locate(&self, axis: &[f64], v: f64) -> Result<(usize, f64), InterpError> {
+        let (min, max) = (axis[0], axis[axis.len() - 1]);
+        let v = if (min..=max).contains(&v) {
+            v
+        } else {
+            match self.extrapolate {
+                Extrapolate::Error => return Err(InterpError::OutOfRange { x: v, min, max }),
+                Extrapolate::Clamp => v.clamp(min, max),
+                Extrapolate::Linear => v,
+            }
+        };
+        let k = segment(axis, v);
+        Ok((k, (v - axis[k]) / (axis[k + 1] - axis[k])))
+    }
+}
+
+// Check that an axis has at least two finite, strictly increasing values
+fn axis(x: &[f64]) -> Result<(), InterpError> {
+    if x.len() < 2 {
+        return Err(InterpError::TooFew { found: x.len() });
+    }
+    if let Some(index) = x.iter().position(|v| !v.is_finite()) {
+        return Err(InterpError::NotFinite { index });
+    }
+    match x.windows(2).position(|w| w[1] <= w[0]) {
+        Some(i) => Err(InterpError::Unsorted { index: i + 1 }),
+        None => Ok(()),
+    }
+}
+
+// The index of the segment containing v, or the end segment nearest it
+fn segment(x: &[f64], v: f64) -> usize {
+    x.partition_point(|a| *a <= v).clamp(1, x.len() - 1) - 1
+}
+
+// Shape preserving slopes at each point, as in PCHIP: a weighted harmonic
+// mean of the neighbouring secants, zero at local extremes.
+fn monotone_slopes(x: &[f64], y: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    let h: Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+    let delta: Vec<f64> = (0..n - 1).map(|k| (y[k + 1] - y[k]) / h[k]).collect();
+    if n == 2 {
+        return vec![delta[0]; 2];
+    }
+    let mut d = vec![0.0; n];
+    for k in 1..n - 1 {
+        if delta[k - 1] * delta[k] > 0.0 {
+            let w1 = 2.0 * h[k] + h[k - 1];
+            let w2 = h[k] + 2.0 * h[k - 1];
+            d[k] = (w1 + w2) / (w1 / delta[k - 1] + w2 / delta[k]);
+        }
+    }
+    d[0] = end_slope(h[0], h[1], delta[0], delta[1]);
+    d[n - 1] = end_slope(h[n - 2], h[n - 3], delta[n - 2], delta[n - 3]);
+    d
+}
+
+// A three point estimate of the slope at an end, limited so the curve
+// doesn't overshoot
+fn end_slope(h0: f64, h1: f64, delta0: f64, delta1: f64) -> f64 {
+    let d = ((2.0 * h0 + h1) * delta0 - h0 * delta1) / (h0 + h1);
+    if d * delta0 <= 0.0 {
+        0.0
+    } else if delta0 * delta1 < 0.0 && d.abs() > 3.0 * delta0.abs() {
+        3.0 * delta0
+    } else {
+        d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Curve, Extrapolate, Grid, InterpError, Method};
+    use crate::calc::linalg::Matrix;
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            method: Method,
+            extrapolate: Extrapolate,
+            x: f64,
+            expected: f64,
+        }
+        let xs = [1.0, 2.0, 4.0, 8.0];
+        let ys = [2.0, 8.0, 32.0, 128.0]; // 2 x²
+        let table = [
+            Row {
+                method: Method::Linear,
+                extrapolate: Extrapolate::Error,
+                x: 3.0,
+                expected: 20.0,
+            },
+            Row {
+                method: Method::Linear,
+                extrapolate: Extrapolate::Linear,
+                x: 0.0,
+                expected: -4.0,
+            },
+            Row {
+                method: Method::Linear,
+                extrapolate: Extrapolate::Clamp,
+                x: 10.0,
+                expected: 128.0,
+            },
+            // a power law is exact on log-log paper, even beyond the table
+            Row {
+                method: Method::LogLog,
+                extrapolate: Extrapolate::Error,
+                x: 3.0,
+                expected: 18.0,
+            },
+            Row {
+                method: Method::LogLog,
+                extrapolate: Extrapolate::Linear,
+                x: 16.0,
+                expected: 512.0,
+            },
+            Row {
+                method: Method::LogLog,
+                extrapolate: Extrapolate::Clamp,
+                x: 0.5,
+                expected: 2.0,
+            },
+            // the table itself, for every method
+            Row {
+                method: Method::MonotoneCubic,
+                extrapolate: Extrapolate::Error,
+                x: 4.0,
+                expected: 32.0,
+            },
+            Row {
+                method: Method::MonotoneCubic,
+                extrapolate: Extrapolate::Clamp,
+                x: -1.0,
+                expected: 2.0,
+            },
+        ];
+        for r in table.iter() {
+            let c = Curve::new(r.method, &xs, &ys)
+                .unwrap()
+                .extrapolate(r.extrapolate);
+            let v = c.eval(r.x).unwrap();
+            assert!(
+                (v - r.expected).abs() < 1e-9,
+                "{:?} {} {}",
+                r.method,
+                r.x,
+                v
+            );
+        }
+    }
+
+    #[test]
+    fn monotone_cubic_does_not_overshoot() {
+        // A step, which an ordinary spline rings around
+        let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let c = Curve::new(Method::MonotoneCubic, &x, &y).unwrap();
+        let mut last = 0.0;
+        for i in 0..=500 {
+            let v = c.eval(i as f64 / 100.0).unwrap();
+            assert!((0.0..=1.0).contains(&v), "{}", v);
+            assert!(v >= last - 1e-15);
+            last = v;
+        }
+        // smooth data is followed closely
+        let x: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+        let y: Vec<f64> = x.iter().map(|v| v.sin()).collect();
+        let c = Curve::new(Method::MonotoneCubic, &x, &y)
+            .unwrap()
+            .extrapolate(Extrapolate::Linear);
+        assert!((c.eval(0.55).unwrap() - 0.55f64.sin()).abs() < 1e-4);
+        // beyond the end it follows the tangent
+        let slope = c.eval(1.1).unwrap() - c.eval(1.0).unwrap();
+        assert!((slope / 0.1 - 1f64.cos()).abs() < 0.05);
+    }
+
+    #[test]
+    fn grid() {
+        // z = x + 10 y is reproduced exactly
+        let (x, y) = ([0.0, 1.0, 3.0], [0.0, 2.0]);
+        let z = Matrix::from_rows(&[[0.0, 20.0], [1.0, 21.0], [3.0, 23.0]]).unwrap();
+        let g = Grid::new(&x, &y, z.clone()).unwrap();
+        assert!((g.eval(2.0, 0.5).unwrap() - 7.0).abs() < 1e-12);
+        assert_eq!(
+            g.eval(4.0, 0.5),
+            Err(InterpError::OutOfRange {
+                x: 4.0,
+                min: 0.0,
+                max: 3.0
+            })
+        );
+        let clamped = g.clone().extrapolate(Extrapolate::Clamp);
+        assert!((clamped.eval(4.0, -1.0).unwrap() - 3.0).abs() < 1e-12);
+        let linear = g.extrapolate(Extrapolate::Linear);
+        assert!((linear.eval(4.0, -1.0).unwrap() + 6.0).abs() < 1e-12);
+        assert_eq!(
+            Grid::new(&y, &x, z).unwrap_err(),
+            InterpError::Shape {
+                expected: (2, 3),
+                found: (3, 2)
+            }
+        );
+    }
+
+    #[test]
+    fn bad_tables() {
+        let m = Method::Linear;
+        assert_eq!(
+            Curve::new(m, &[1.0, 2.0], &[1.0]),
+            Err(InterpError::Length { x: 2, y: 1 })
+        );
+        assert_eq!(
+            Curve::new(m, &[1.0], &[1.0]),
+            Err(InterpError::TooFew { found: 1 })
+        );
+        assert_eq!(
+            Curve::new(m, &[1.0, 3.0, 2.0], &[1.0, 2.0, 3.0]),
+            Err(InterpError::Unsorted { index: 2 })
+        );
+        assert_eq!(
+            Curve::new(Method::LogLog, &[1.0, 2.0], &[1.0, 0.0]),
+            Err(InterpError::NotPositive { index: 1 })
+        );
+        assert_eq!(
+            Curve::new(m, &[1.0, 2.0], &[1.0, f64::NAN]),
+            Err(InterpError::NotFinite { index: 1 })
+        );
+    }
+}
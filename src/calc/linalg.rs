@@ -1,56 +1,1094 @@
-//! WIP
-//! Solvers for systems of equations.
-//! 
+//! # linalg
+//!
+//! Linear algebra for the solvers in `calc`: dense and skyline matrices and
+//! the factorizations used to solve systems with them.
+//!
+//! `Matrix` is a dense, row-major matrix. Square systems are solved by LU
+//! factorization with partial pivoting, which also gives the determinant
+//! and the inverse. `solve` and `inverse` refuse systems which are singular
+//! or too ill-conditioned for the answer to mean anything.
+//!
+//! ```rust
+//! use civil::calc::linalg::Matrix;
+//!
+//! let a = Matrix::from_rows(&[[4.0, 1.0], [2.0, 3.0]]).unwrap();
+//! let x = a.solve(&[1.0, 2.0]).unwrap();
+//! assert!((x[0] - 0.1).abs() < 1e-12);
+//! assert!((x[1] - 0.6).abs() < 1e-12);
+//! assert!((a.determinant().unwrap() - 10.0).abs() < 1e-12);
+//! ```
+//!
+//! Stiffness matrices are symmetric, positive definite and mostly zero.
+//! `Skyline` stores only the profile of such a matrix, from the first
+//! nonzero in each column down to the diagonal, and solves it by Cholesky
+//! factorization, which creates no fill outside that profile.
+//! `reverse_cuthill_mckee` renumbers nodes to make the profile smaller.
+//!
+//! ```rust
+//! use civil::calc::linalg::Skyline;
+//!
+//! // three springs in a row, fixed at the left end
+//! let mut k = Skyline::from_pattern(3, &[(0, 1), (1, 2)]);
+//! for (i, j, v) in [(0, 0, 200.0), (0, 1, -100.0), (1, 1, 200.0), (1, 2, -100.0), (2, 2, 100.0)].iter() {
+//!     k.add(*i, *j, *v).unwrap();
+//! }
+//! let u = k.solve(&[0.0, 0.0, 10.0]).unwrap();
+//! assert!((u[2] - 0.3).abs() < 1e-12);
+//! ```
 
 use std::error::Error;
 use std::fmt;
+use std::ops::{Index, IndexMut};
 
-#[derive(Debug)]
-pub struct MatrixError {
-    details: String
-}
+/// RCOND_MIN is the smallest estimated reciprocal condition number `solve`
+/// and `inverse` accept. Below it, rounding error can swamp every digit of
+/// the answer.
+pub const RCOND_MIN: f64 = f64::EPSILON;
 
-impl MatrixError {
-    fn new(msg: &str) -> MatrixError {
-        MatrixError{details: msg.to_string()}
-    }
+/// MatrixError is returned when an operation doesn't make sense for the
+/// matrices it was given.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatrixError {
+    /// The shapes, as (rows, columns), don't fit the operation.
+    Shape {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// The operation needs a square matrix.
+    NotSquare { rows: usize, cols: usize },
+    /// No nonzero pivot could be found for `column`, so the matrix has no
+    /// inverse.
+    Singular { column: usize },
+    /// The matrix is nearly singular: `rcond` estimates the reciprocal of
+    /// its condition number in the 1-norm, and is below `RCOND_MIN`.
+    IllConditioned { rcond: f64 },
+    /// A symmetric matrix turned out not to be positive definite at
+    /// `column`. For a stiffness matrix this usually means a missing
+    /// support or a mechanism.
+    NotPositiveDefinite { column: usize },
+    /// An entry was written outside the stored profile of a `Skyline`.
+    OutsideProfile { row: usize, col: usize },
+    /// The operation needs a symmetric matrix, and entries (row, col) and
+    /// (col, row) differ.
+    NotSymmetric { row: usize, col: usize },
+    /// An iterative method gave up after `iterations`.
+    NoConvergence { iterations: usize },
+    /// Entry (row, col) is infinite or NaN.
+    NotFinite { row: usize, col: usize },
 }
 
 impl fmt::Display for MatrixError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f,"{}",self.details)
-    }
-}
-
-impl Error for MatrixError {
-    fn description(&self) -> &str {
-        &self.details
+        match self {
+            MatrixError::Shape { expected, found } => write!(
+                f,
+                "expected a {}x{} matrix, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            MatrixError::NotSquare { rows, cols } => {
+                write!(f, "expected a square matrix, found {}x{}", rows, cols)
+            }
+            MatrixError::Singular { column } => {
+                write!(f, "matrix is singular (no pivot in column {})", column)
+            }
+            MatrixError::IllConditioned { rcond } => write!(
+                f,
+                "matrix is ill-conditioned (reciprocal condition number {:e})",
+                rcond
+            ),
+            MatrixError::NotPositiveDefinite { column } => {
+                write!(f, "matrix is not positive definite at column {}", column)
+            }
+            MatrixError::OutsideProfile { row, col } => {
+                write!(f, "({}, {}) is outside the skyline profile", row, col)
+            }
+            MatrixError::NotSymmetric { row, col } => {
+                write!(f, "matrix is not symmetric at ({}, {})", row, col)
+            }
+            MatrixError::NoConvergence { iterations } => {
+                write!(f, "no convergence after {} iterations", iterations)
+            }
+            MatrixError::NotFinite { row, col } => {
+                write!(f, "matrix entry ({}, {}) is not finite", row, col)
+            }
+        }
     }
 }
 
+impl Error for MatrixError {}
 
-// Matrix is a NxN matrix
-#[derive(Debug)]
+/// Matrix is a dense matrix stored row by row. `stride` is the number of
+/// columns, so element (i, j) is `data[i * stride + j]`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Matrix {
     pub stride: usize,
     pub data: Vec<f64>,
 }
 
-
 impl Matrix {
-    pub fn mul(&self, m: Matrix) -> Result<Matrix, MatrixError> {
-        if m.stride * self.stride != m.data.len() {
-            return Err(MatrixError::new("Matrices are of incompatible size!"));
-        }
-        let mut prod = Matrix{stride: m.stride, data: Vec::with_capacity(self.data.len()/self.stride * m.stride)};
-        for i in (0..self.data.len()).step_by(self.stride) {
-            for j in 0..m.stride {
-                prod.data[i+j] = self.data[i..i+self.stride].iter()
-                .zip((j..m.data.len()).step_by(m.stride).map(|k|m.data[k]))
-                .map(|(a,b)| a*b)
-                .sum();
+    /// new creates a matrix from data given row by row.
+    pub fn new(rows: usize, cols: usize, data: Vec<f64>) -> Result<Matrix, MatrixError> {
+        if data.len() != rows * cols {
+            return Err(MatrixError::Shape {
+                expected: (rows, cols),
+                found: (1, data.len()),
+            });
+        }
+        Ok(Matrix { stride: cols, data })
+    }
+
+    /// zeros creates a matrix of zeros.
+    pub fn zeros(rows: usize, cols: usize) -> Matrix {
+        Matrix {
+            stride: cols,
+            data: vec![0.0; rows * cols],
+        }
+    }
+
+    /// identity creates the n by n identity matrix.
+    pub fn identity(n: usize) -> Matrix {
+        let mut m = Matrix::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = 1.0;
+        }
+        m
+    }
+
+    /// from_rows creates a matrix from a slice of rows, which must all be the
+    /// same length.
+    pub fn from_rows<R: AsRef<[f64]>>(rows: &[R]) -> Result<Matrix, MatrixError> {
+        let cols = rows.first().map_or(0, |r| r.as_ref().len());
+        let mut data = Vec::with_capacity(rows.len() * cols);
+        for r in rows.iter() {
+            let r = r.as_ref();
+            if r.len() != cols {
+                return Err(MatrixError::Shape {
+                    expected: (rows.len(), cols),
+                    found: (rows.len(), r.len()),
+                });
+            }
+            data.extend_from_slice(r);
+        }
+        Ok(Matrix { stride: cols, data })
+    }
+
+    /// rows is the number of rows.
+    pub fn rows(&self) -> usize {
+        self.data.len().checked_div(self.stride).unwrap_or(0)
+    }
+
+    /// cols is the number of columns.
+    pub fn cols(&self) -> usize {
+        self.stride
+    }
+
+    /// shape is (rows, columns).
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows(), self.cols())
+    }
+
+    /// is_square is true for matrices with as many rows as columns.
+    pub fn is_square(&self) -> bool {
+        self.rows() == self.cols()
+    }
+
+    /// row borrows row `i`.
+    pub fn row(&self, i: usize) -> &[f64] {
+        &self.data[i * self.stride..(i + 1) * self.stride]
+    }
+
+    /// transpose swaps rows and columns.
+    pub fn transpose(&self) -> Matrix {
+        let (rows, cols) = self.shape();
+        let mut t = Matrix::zeros(cols, rows);
+        for i in 0..rows {
+            for j in 0..cols {
+                t[(j, i)] = self[(i, j)];
+            }
+        }
+        t
+    }
+
+    /// add adds two matrices of the same shape.
+    pub fn add(&self, m: &Matrix) -> Result<Matrix, MatrixError> {
+        self.zip(m, |a, b| a + b)
+    }
+
+    /// sub subtracts a matrix of the same shape.
+    pub fn sub(&self, m: &Matrix) -> Result<Matrix, MatrixError> {
+        self.zip(m, |a, b| a - b)
+    }
+
+    /// scale multiplies every element by `k`.
+    pub fn scale(&self, k: f64) -> Matrix {
+        Matrix {
+            stride: self.stride,
+            data: self.data.iter().map(|a| a * k).collect(),
+        }
+    }
+
+    /// mul multiplies two matrices. The number of rows of `m` has to match
+    /// the number of columns of `self`.
+    pub fn mul(&self, m: &Matrix) -> Result<Matrix, MatrixError> {
+        if m.rows() != self.cols() {
+            return Err(MatrixError::Shape {
+                expected: (self.cols(), m.cols()),
+                found: m.shape(),
+            });
+        }
+        let mut prod = Matrix::zeros(self.rows(), m.cols());
+        for i in 0..self.rows() {
+            for (k, a) in self.row(i).iter().enumerate() {
+                if *a == 0.0 {
+                    continue;
+                }
+                for (p, b) in prod.data[i * m.stride..(i + 1) * m.stride]
+                    .iter_mut()
+                    .zip(m.row(k).iter())
+                {
+                    *p += a * b;
+                }
             }
         }
         Ok(prod)
     }
-}
\ No newline at end of file
+
+    /// mul_vec multiplies a matrix by a column vector.
+    pub fn mul_vec(&self, x: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        if x.len() != self.cols() {
+            return Err(MatrixError::Shape {
+                expected: (self.cols(), 1),
+                found: (x.len(), 1),
+            });
+        }
+        Ok((0..self.rows())
+            .map(|i| self.row(i).iter().zip(x.iter()).map(|(a, b)| a * b).sum())
+            .collect())
+    }
+
+    /// norm1 is the 1-norm, the largest sum of absolute values in a column.
+    pub fn norm1(&self) -> f64 {
+        (0..self.cols())
+            .map(|j| (0..self.rows()).map(|i| self[(i, j)].abs()).sum())
+            .fold(0.0, f64::max)
+    }
+
+    /// lu factors a square matrix with partial pivoting. The factorization
+    /// succeeds even for singular matrices; `Lu::solve` is where that
+    /// matters.
+    pub fn lu(&self) -> Result<Lu, MatrixError> {
+        Lu::new(self)
+    }
+
+    /// solve solves `self * x = b` for `x`.
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        self.conditioned()?.solve(b)
+    }
+
+    /// determinant of a square matrix.
+    pub fn determinant(&self) -> Result<f64, MatrixError> {
+        Ok(self.lu()?.determinant())
+    }
+
+    /// inverse of a square matrix.
+    pub fn inverse(&self) -> Result<Matrix, MatrixError> {
+        self.conditioned()?.inverse()
+    }
+
+    // Factor the matrix and check that it is safe to solve with
+    fn conditioned(&self) -> Result<Lu, MatrixError> {
+        let lu = self.lu()?;
+        if let Some(column) = lu.zero_pivot() {
+            return Err(MatrixError::Singular { column });
+        }
+        let rcond = lu.rcond();
+        if rcond < RCOND_MIN {
+            return Err(MatrixError::IllConditioned { rcond });
+        }
+        Ok(lu)
+    }
+
+    // Combine two matrices of the same shape element by element
+    fn zip<F: Fn(f64, f64) -> f64>(&self, m: &Matrix, f: F) -> Result<Matrix, MatrixError> {
+        if m.shape() != self.shape() {
+            return Err(MatrixError::Shape {
+                expected: self.shape(),
+                found: m.shape(),
+            });
+        }
+        Ok(Matrix {
+            stride: self.stride,
+            data: self
+                .data
+                .iter()
+                .zip(m.data.iter())
+                .map(|(a, b)| f(*a, *b))
+                .collect(),
+        })
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        assert!(j < self.stride, "column {} out of range", j);
+        &self.data[i * self.stride + j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        assert!(j < self.stride, "column {} out of range", j);
+        &mut self.data[i * self.stride + j]
+    }
+}
+
+/// Lu is the factorization `P A = L U` of a square matrix, where L is unit
+/// lower triangular and U is upper triangular. Both are kept in one matrix.
+/// Factor once and solve as many right hand sides as needed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lu {
+    lu: Matrix,
+    // Row i of the factored matrix is row pivots[i] of the original
+    pivots: Vec<usize>,
+    // 1 or -1 depending on the number of row swaps
+    sign: f64,
+    // 1-norm of the original matrix, for the condition estimate
+    norm: f64,
+}
+
+impl Lu {
+    fn new(a: &Matrix) -> Result<Lu, MatrixError> {
+        let (rows, cols) = a.shape();
+        if rows != cols {
+            return Err(MatrixError::NotSquare { rows, cols });
+        }
+        let n = rows;
+        let mut lu = a.clone();
+        let mut pivots: Vec<usize> = (0..n).collect();
+        let mut sign = 1.0;
+        for k in 0..n {
+            // Largest remaining element in this column
+            let p = (k..n)
+                .max_by(|x, y| lu[(*x, k)].abs().total_cmp(&lu[(*y, k)].abs()))
+                .unwrap();
+            if p != k {
+                for j in 0..n {
+                    lu.data.swap(k * n + j, p * n + j);
+                }
+                pivots.swap(k, p);
+                sign = -sign;
+            }
+            let pivot = lu[(k, k)];
+            if pivot == 0.0 {
+                continue;
+            }
+            for i in k + 1..n {
+                let l = lu[(i, k)] / pivot;
+                lu[(i, k)] = l;
+                if l == 0.0 {
+                    continue;
+                }
+                for j in k + 1..n {
+                    lu[(i, j)] -= l * lu[(k, j)];
+                }
+            }
+        }
+        Ok(Lu {
+            norm: a.norm1(),
+            lu,
+            pivots,
+            sign,
+        })
+    }
+
+    /// size is the number of rows (and columns) of the factored matrix.
+    pub fn size(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// determinant of the factored matrix.
+    pub fn determinant(&self) -> f64 {
+        (0..self.size()).map(|i| self.lu[(i, i)]).product::<f64>() * self.sign
+    }
+
+    /// solve solves `A x = b` for `x`. Only an exactly singular matrix is an
+    /// error here; check `rcond` to see how far the answer can be trusted.
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        let n = self.size();
+        if b.len() != n {
+            return Err(MatrixError::Shape {
+                expected: (n, 1),
+                found: (b.len(), 1),
+            });
+        }
+        if let Some(column) = self.zero_pivot() {
+            return Err(MatrixError::Singular { column });
+        }
+        let mut x: Vec<f64> = self.pivots.iter().map(|p| b[*p]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                x[i] -= self.lu[(i, j)] * x[j];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] -= self.lu[(i, j)] * x[j];
+            }
+            x[i] /= self.lu[(i, i)];
+        }
+        Ok(x)
+    }
+
+    /// inverse of the factored matrix, one column at a time.
+    pub fn inverse(&self) -> Result<Matrix, MatrixError> {
+        let n = self.size();
+        let mut inv = Matrix::zeros(n, n);
+        let mut e = vec![0.0; n];
+        for j in 0..n {
+            e[j] = 1.0;
+            for (i, x) in self.solve(&e)?.into_iter().enumerate() {
+                inv[(i, j)] = x;
+            }
+            e[j] = 0.0;
+        }
+        Ok(inv)
+    }
+
+    /// rcond estimates the reciprocal of the condition number of the
+    /// factored matrix in the 1-norm, using Hager's method. Near 1 is well
+    /// conditioned; near machine epsilon means the matrix is numerically
+    /// singular. Zero for a singular matrix.
+    pub fn rcond(&self) -> f64 {
+        let n = self.size();
+        if n == 0 {
+            return 1.0;
+        }
+        if self.zero_pivot().is_some() || self.norm == 0.0 {
+            return 0.0;
+        }
+        // Maximize |A^-1 x|_1 over |x|_1 = 1
+        let mut x = vec![1.0 / n as f64; n];
+        let mut estimate = 0.0;
+        for _ in 0..5 {
+            let y = self.solve(&x).unwrap();
+            estimate = y.iter().map(|v| v.abs()).sum();
+            let signs: Vec<f64> = y
+                .iter()
+                .map(|v| if *v < 0.0 { -1.0 } else { 1.0 })
+                .collect();
+            let z = self.solve_transpose(&signs);
+            let (j, zj) = z
+                .iter()
+                .map(|v| v.abs())
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            let ztx: f64 = z.iter().zip(x.iter()).map(|(a, b)| a * b).sum();
+            if zj <= ztx {
+                break;
+            }
+            x = vec![0.0; n];
+            x[j] = 1.0;
+        }
+        if !estimate.is_finite() {
+            return 0.0;
+        }
+        1.0 / (self.norm * estimate)
+    }
+
+    // Solve A^T x = b. With P A = L U, A^T = U^T L^T P.
+    fn solve_transpose(&self, b: &[f64]) -> Vec<f64> {
+        let n = self.size();
+        let mut w = b.to_vec();
+        for i in 0..n {
+            for j in 0..i {
+                w[i] -= self.lu[(j, i)] * w[j];
+            }
+            w[i] /= self.lu[(i, i)];
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                w[i] -= self.lu[(j, i)] * w[j];
+            }
+        }
+        let mut x = vec![0.0; n];
+        for (i, p) in self.pivots.iter().enumerate() {
+            x[*p] = w[i];
+        }
+        x
+    }
+
+    // The first column without a usable pivot, if any
+    fn zero_pivot(&self) -> Option<usize> {
+        (0..self.size()).find(|i| self.lu[(*i, *i)] == 0.0)
+    }
+}
+
+/// Skyline is a symmetric matrix stored by its profile: for each column j,
+/// the entries from the first nonzero row down to the diagonal. Entries
+/// above the profile are zero, and so are the corresponding entries of its
+/// Cholesky factor, so the profile is all the memory a solve needs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skyline {
+    // First stored row of each column
+    first: Vec<usize>,
+    // Offset of the first stored entry of each column in `values`, plus
+    // the total length at the end
+    start: Vec<usize>,
+    values: Vec<f64>,
+    // Whether `values` holds the Cholesky factor rather than the matrix
+    factored: bool,
+}
+
+impl Skyline {
+    /// with_profile creates a zero matrix given the first stored row of
+    /// each column. `first[j]` is clamped to `j`.
+    pub fn with_profile(first: Vec<usize>) -> Skyline {
+        let first: Vec<usize> = first.iter().enumerate().map(|(j, f)| *f.min(&j)).collect();
+        let mut start = Vec::with_capacity(first.len() + 1);
+        let mut len = 0;
+        for (j, f) in first.iter().enumerate() {
+            start.push(len);
+            len += j - f + 1;
+        }
+        start.push(len);
+        Skyline {
+            first,
+            start,
+            values: vec![0.0; len],
+            factored: false,
+        }
+    }
+
+    /// from_pattern creates a zero n by n matrix with room for a nonzero at
+    /// each pair of indices, ie the degrees of freedom joined by each
+    /// element, plus the diagonal.
+    ///
+    /// # Panics
+    ///
+    /// If a pair holds an index of `n` or more.
+    pub fn from_pattern(n: usize, pairs: &[(usize, usize)]) -> Skyline {
+        let mut first: Vec<usize> = (0..n).collect();
+        for &(i, j) in pairs.iter() {
+            let (i, j) = (i.min(j), i.max(j));
+            assert!(j < n, "index {} out of range for {} nodes", j, n);
+            first[j] = first[j].min(i);
+        }
+        Skyline::with_profile(first)
+    }
+
+    /// from_dense copies the upper triangle of a square matrix, which is
+    /// assumed to be symmetric.
+    pub fn from_dense(m: &Matrix) -> Result<Skyline, MatrixError> {
+        let (rows, cols) = m.shape();
+        if rows != cols {
+            return Err(MatrixError::NotSquare { rows, cols });
+        }
+        let first = (0..cols)
+            .map(|j| (0..j).find(|i| m[(*i, j)] != 0.0).unwrap_or(j))
+            .collect();
+        let mut s = Skyline::with_profile(first);
+        for j in 0..cols {
+            for i in s.first[j]..=j {
+                s.values[s.start[j] + i - s.first[j]] = m[(i, j)];
+            }
+        }
+        Ok(s)
+    }
+
+    /// size is the number of rows (and columns).
+    pub fn size(&self) -> usize {
+        self.first.len()
+    }
+
+    /// len is the number of stored entries.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// is_empty is true for a 0 by 0 matrix.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// bandwidth is the largest distance from the diagonal to the top of
+    /// the profile.
+    pub fn bandwidth(&self) -> usize {
+        self.first
+            .iter()
+            .enumerate()
+            .map(|(j, f)| j - f)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// get returns entry (i, j), which is zero outside the profile.
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        self.position(i, j).map_or(0.0, |p| self.values[p])
+    }
+
+    /// add adds `v` to entries (i, j) and (j, i), as when assembling element
+    /// stiffness matrices.
+    pub fn add(&mut self, i: usize, j: usize, v: f64) -> Result<(), MatrixError> {
+        match self.position(i, j) {
+            Some(p) if !self.factored => {
+                self.values[p] += v;
+                Ok(())
+            }
+            _ => Err(MatrixError::OutsideProfile { row: i, col: j }),
+        }
+    }
+
+    /// mul_vec multiplies by a column vector.
+    pub fn mul_vec(&self, x: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        let n = self.size();
+        if x.len() != n {
+            return Err(MatrixError::Shape {
+                expected: (n, 1),
+                found: (x.len(), 1),
+            });
+        }
+        let mut y = vec![0.0; n];
+        for j in 0..n {
+            let column = self.column(j);
+            let rows = self.first[j]..j;
+            for (i, a) in rows.zip(column.iter()) {
+                y[i] += a * x[j];
+                y[j] += a * x[i];
+            }
+            y[j] += column[column.len() - 1] * x[j];
+        }
+        Ok(y)
+    }
+
+    /// cholesky factors the matrix in place as `U^T U`, where U is upper
+    /// triangular with the same profile.
+    pub fn cholesky(mut self) -> Result<Skyline, MatrixError> {
+        if self.factored {
+            return Ok(self);
+        }
+        for j in 0..self.size() {
+            let fj = self.first[j];
+            for i in fj..j {
+                // U[i][j] from the overlap of columns i and j above row i
+                let fi = self.first[i];
+                let top = fi.max(fj);
+                let ci = self.start[i] + top - fi;
+                let cj = self.start[j] + top - fj;
+                let len = i - top;
+                let dot: f64 = self.values[ci..ci + len]
+                    .iter()
+                    .zip(self.values[cj..cj + len].iter())
+                    .map(|(a, b)| a * b)
+                    .sum();
+                let pivot = self.values[self.start[i] + i - fi];
+                self.values[self.start[j] + i - fj] =
+                    (self.values[self.start[j] + i - fj] - dot) / pivot;
+            }
+            let column = &self.values[self.start[j]..self.start[j + 1]];
+            let (above, diagonal) = column.split_at(column.len() - 1);
+            let d = diagonal[0] - above.iter().map(|u| u * u).sum::<f64>();
+            if d <= 0.0 || !d.is_finite() {
+                return Err(MatrixError::NotPositiveDefinite { column: j });
+            }
+            self.values[self.start[j + 1] - 1] = d.sqrt();
+        }
+        self.factored = true;
+        Ok(self)
+    }
+
+    /// solve solves `A x = b`, factoring a copy of the matrix first if it
+    /// hasn't been factored. To solve many right hand sides, factor once
+    /// with `cholesky` and call `solve` on the result.
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        if !self.factored {
+            return self.clone().cholesky()?.solve(b);
+        }
+        let n = self.size();
+        if b.len() != n {
+            return Err(MatrixError::Shape {
+                expected: (n, 1),
+                found: (b.len(), 1),
+            });
+        }
+        // U^T y = b
+        let mut x = b.to_vec();
+        for j in 0..n {
+            let column = self.column(j);
+            let (above, diagonal) = column.split_at(column.len() - 1);
+            let dot: f64 = above
+                .iter()
+                .zip(x[self.first[j]..j].iter())
+                .map(|(u, y)| u * y)
+                .sum();
+            x[j] = (x[j] - dot) / diagonal[0];
+        }
+        // U x = y, a column at a time
+        for j in (0..n).rev() {
+            let column = self.column(j);
+            let (above, diagonal) = column.split_at(column.len() - 1);
+            x[j] /= diagonal[0];
+            let xj = x[j];
+            for (y, u) in x[self.first[j]..j].iter_mut().zip(above.iter()) {
+                *y -= u * xj;
+            }
+        }
+        Ok(x)
+    }
+
+    // The stored entries of column j, ending with the diagonal
+    fn column(&self, j: usize) -> &[f64] {
+        &self.values[self.start[j]..self.start[j + 1]]
+    }
+
+    // Where entry (i, j), or (j, i), is stored
+    fn position(&self, i: usize, j: usize) -> Option<usize> {
+        let (i, j) = (i.min(j), i.max(j));
+        if j >= self.size() || i < self.first[j] {
+            return None;
+        }
+        Some(self.start[j] + i - self.first[j])
+    }
+}
+
+/// reverse_cuthill_mckee orders the nodes of a graph, given as n nodes and
+/// the pairs of nodes joined by an element, so that joined nodes get nearby
+/// numbers. `order[k]` is the old number of the node which should be
+/// numbered k. Renumbering a structure this way before assembly can shrink
+/// the profile of its stiffness matrix many times over.
+///
+/// ```rust
+/// use civil::calc::linalg::{reverse_cuthill_mckee, Skyline};
+///
+/// // a chain of five nodes, numbered badly
+/// let pairs = [(0, 4), (4, 1), (1, 3), (3, 2)];
+/// let order = reverse_cuthill_mckee(5, &pairs);
+/// let mut new = vec![0; 5];
+/// for (k, old) in order.iter().enumerate() {
+///     new[*old] = k;
+/// }
+/// let renumbered: Vec<(usize, usize)> = pairs.iter().map(|(i, j)| (new[*i], new[*j])).collect();
+/// assert_eq!(Skyline::from_pattern(5, &renumbered).bandwidth(), 1);
+/// ```
+///
+/// # Panics
+///
+/// If a pair holds an index of `n` or more.
+pub fn reverse_cuthill_mckee(n: usize, pairs: &[(usize, usize)]) -> Vec<usize> {
+    let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for &(i, j) in pairs.iter() {
+        let last = i.max(j);
+        assert!(last < n, "index {} out of range for {} nodes", last, n);
+        if i != j {
+            adjacent[i].push(j);
+            adjacent[j].push(i);
+        }
+    }
+    for a in adjacent.iter_mut() {
+        a.sort_unstable();
+        a.dedup();
+    }
+    let degree = |i: usize| adjacent[i].len();
+    let mut order = Vec::with_capacity(n);
+    let mut seen = vec![false; n];
+    while order.len() < n {
+        // Start each piece of the graph from a node far from the others
+        let start = (0..n)
+            .filter(|i| !seen[*i])
+            .min_by_key(|i| degree(*i))
+            .unwrap();
+        let start = peripheral(&adjacent, start);
+        let mut next = order.len();
+        seen[start] = true;
+        order.push(start);
+        while next < order.len() {
+            let node = order[next];
+            next += 1;
+            let mut fresh: Vec<usize> = adjacent[node]
+                .iter()
+                .cloned()
+                .filter(|i| !seen[*i])
+                .collect();
+            fresh.sort_by_key(|i| degree(*i));
+            for i in fresh {
+                seen[i] = true;
+                order.push(i);
+            }
+        }
+    }
+    order.reverse();
+    order
+}
+
+// Find a node at the far end of the graph from `start` by repeated
+// breadth first searches (George and Liu's pseudo-peripheral node).
+fn peripheral(adjacent: &[Vec<usize>], start: usize) -> usize {
+    let mut node = start;
+    let mut eccentricity = 0;
+    loop {
+        let levels = levels(adjacent, node);
+        let depth = *levels.iter().flatten().max().unwrap();
+        if depth <= eccentricity {
+            return node;
+        }
+        eccentricity = depth;
+        // The least connected node in the last level
+        node = (0..adjacent.len())
+            .filter(|i| levels[*i] == Some(depth))
+            .min_by_key(|i| adjacent[*i].len())
+            .unwrap();
+    }
+}
+
+// Distance of every node reachable from `start`
+fn levels(adjacent: &[Vec<usize>], start: usize) -> Vec<Option<usize>> {
+    let mut level = vec![None; adjacent.len()];
+    level[start] = Some(0);
+    let mut queue = std::collections::VecDeque::new();
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        let d = level[node].unwrap() + 1;
+        for i in adjacent[node].iter() {
+            if level[*i].is_none() {
+                level[*i] = Some(d);
+                queue.push_back(*i);
+            }
+        }
+    }
+    level
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reverse_cuthill_mckee, Matrix, MatrixError, Skyline};
+
+    const PRECISION: f64 = 1.0e-12;
+
+    fn close(a: &Matrix, b: &Matrix) -> bool {
+        a.shape() == b.shape()
+            && a.data
+                .iter()
+                .zip(b.data.iter())
+                .all(|(x, y)| (x - y).abs() < PRECISION)
+    }
+
+    #[test]
+    fn basics() {
+        let a = Matrix::from_rows(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]).unwrap();
+        assert_eq!(a.shape(), (2, 3));
+        assert_eq!(a[(1, 0)], 4.0);
+        assert_eq!(a.transpose().row(2), &[3.0, 6.0]);
+        assert_eq!(a.add(&a).unwrap(), a.scale(2.0));
+        assert_eq!(a.sub(&a).unwrap(), Matrix::zeros(2, 3));
+        assert!(a.add(&a.transpose()).is_err());
+        assert!(Matrix::from_rows(&[vec![1.0], vec![1.0, 2.0]]).is_err());
+        assert!(Matrix::new(2, 2, vec![1.0, 2.0, 3.0]).is_err());
+    }
+
+    #[test]
+    fn mul() {
+        let a = Matrix::from_rows(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]).unwrap();
+        let b = Matrix::from_rows(&[[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]).unwrap();
+        let ab = Matrix::from_rows(&[[58.0, 64.0], [139.0, 154.0]]).unwrap();
+        assert_eq!(a.mul(&b).unwrap(), ab);
+        assert_eq!(a.mul(&Matrix::identity(3)).unwrap(), a);
+        assert_eq!(a.mul_vec(&[1.0, 0.0, -1.0]).unwrap(), vec![-2.0, -2.0]);
+        match a.mul(&a) {
+            Err(MatrixError::Shape { .. }) => {}
+            other => panic!("expected a shape error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            a: Vec<[f64; 3]>,
+            b: [f64; 3],
+            det: f64,
+        }
+        let table = [
+            Row {
+                a: vec![[2.0, 1.0, 1.0], [4.0, -6.0, 0.0], [-2.0, 7.0, 2.0]],
+                b: [5.0, -2.0, 9.0],
+                det: -16.0,
+            },
+            // needs a row swap for the first pivot
+            Row {
+                a: vec![[0.0, 2.0, 1.0], [1.0, 1.0, 1.0], [3.0, 0.0, 2.0]],
+                b: [3.0, 3.0, 5.0],
+                det: -1.0,
+            },
+        ];
+        for r in table.iter() {
+            let a = Matrix::from_rows(&r.a).unwrap();
+            let x = a.solve(&r.b).unwrap();
+            for (ax, b) in a.mul_vec(&x).unwrap().iter().zip(r.b.iter()) {
+                assert!((ax - b).abs() < PRECISION);
+            }
+            assert!((a.determinant().unwrap() - r.det).abs() < PRECISION);
+            let inv = a.inverse().unwrap();
+            assert!(close(&a.mul(&inv).unwrap(), &Matrix::identity(3)));
+        }
+    }
+
+    #[test]
+    fn singular_and_ill_conditioned() {
+        let a = Matrix::from_rows(&[[1.0, 2.0], [2.0, 4.0]]).unwrap();
+        assert_eq!(a.determinant().unwrap(), 0.0);
+        assert_eq!(
+            a.solve(&[1.0, 1.0]),
+            Err(MatrixError::Singular { column: 1 })
+        );
+        // singular too, but rounding leaves a tiny pivot
+        let a = Matrix::from_rows(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]).unwrap();
+        match a.inverse() {
+            Err(MatrixError::Singular { .. }) | Err(MatrixError::IllConditioned { .. }) => {}
+            other => panic!("expected a singular matrix, got {:?}", other),
+        }
+        // Hilbert matrices are notoriously ill-conditioned
+        let n = 12;
+        let mut h = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in 0..n {
+                h[(i, j)] = 1.0 / (i + j + 1) as f64;
+            }
+        }
+        match h.solve(&vec![1.0; n]) {
+            Err(MatrixError::IllConditioned { rcond }) => assert!(rcond < 1.0e-15),
+            other => panic!("expected an ill-conditioned matrix, got {:?}", other),
+        }
+        // a small one is fine
+        let rcond = Matrix::identity(4).lu().unwrap().rcond();
+        assert!((rcond - 1.0).abs() < PRECISION);
+        assert_eq!(
+            Matrix::zeros(2, 3).lu(),
+            Err(MatrixError::NotSquare { rows: 2, cols: 3 })
+        );
+    }
+
+    // A grid of n by n nodes joined to their neighbours, like a plate or a
+    // beam on springs
+    fn grid(n: usize) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for r in 0..n {
+            for c in 0..n {
+                if c + 1 < n {
+                    pairs.push((r * n + c, r * n + c + 1));
+                }
+                if r + 1 < n {
+                    pairs.push((r * n + c, (r + 1) * n + c));
+                }
+            }
+        }
+        pairs
+    }
+
+    // A diagonally dominant stiffness matrix for a pattern
+    fn stiffness(n: usize, pairs: &[(usize, usize)]) -> (Skyline, Matrix) {
+        let mut s = Skyline::from_pattern(n, pairs);
+        let mut m = Matrix::zeros(n, n);
+        for i in 0..n {
+            s.add(i, i, 1.0).unwrap();
+            m[(i, i)] += 1.0;
+        }
+        for (k, &(i, j)) in pairs.iter().enumerate() {
+            let v = 1.0 + k as f64 / 10.0;
+            s.add(i, i, v).unwrap();
+            s.add(j, j, v).unwrap();
+            s.add(i, j, -v).unwrap();
+            m[(i, i)] += v;
+            m[(j, j)] += v;
+            m[(i, j)] -= v;
+            m[(j, i)] -= v;
+        }
+        (s, m)
+    }
+
+    #[test]
+    fn skyline_matches_dense() {
+        let pairs = grid(5);
+        let (s, m) = stiffness(25, &pairs);
+        assert_eq!(Skyline::from_dense(&m).unwrap(), s);
+        let b: Vec<f64> = (0..25).map(|i| (i as f64).sin()).collect();
+        assert_eq!(s.mul_vec(&b).unwrap().len(), 25);
+        for (x, y) in s
+            .mul_vec(&b)
+            .unwrap()
+            .iter()
+            .zip(m.mul_vec(&b).unwrap().iter())
+        {
+            assert!((x - y).abs() < PRECISION);
+        }
+        let x = s.solve(&b).unwrap();
+        let dense = m.solve(&b).unwrap();
+        for (x, y) in x.iter().zip(dense.iter()) {
+            assert!((x - y).abs() < 1.0e-10);
+        }
+        // factor once, solve twice
+        let f = s.clone().cholesky().unwrap();
+        assert_eq!(f.solve(&b).unwrap(), x);
+        assert!(f.clone().add(0, 0, 1.0).is_err());
+        assert_eq!(s.get(0, 24), 0.0);
+        assert_eq!(s.get(1, 0), s.get(0, 1));
+        assert!(s.clone().add(0, 24, 1.0).is_err());
+    }
+
+    #[test]
+    fn not_positive_definite() {
+        // a spring with nothing holding it down
+        let mut s = Skyline::from_pattern(2, &[(0, 1)]);
+        s.add(0, 0, 1.0).unwrap();
+        s.add(1, 1, 1.0).unwrap();
+        s.add(0, 1, -1.0).unwrap();
+        assert_eq!(
+            s.solve(&[1.0, 0.0]),
+            Err(MatrixError::NotPositiveDefinite { column: 1 })
+        );
+    }
+
+    #[test]
+    fn renumbering() {
+        let n = 6;
+        let pairs = grid(n);
+        // scramble the numbering, then recover a good one
+        let scramble: Vec<usize> = (0..n * n).map(|i| (i * 7) % (n * n)).collect();
+        let scrambled: Vec<(usize, usize)> = pairs
+            .iter()
+            .map(|(i, j)| (scramble[*i], scramble[*j]))
+            .collect();
+        let before = Skyline::from_pattern(n * n, &scrambled);
+        let order = reverse_cuthill_mckee(n * n, &scrambled);
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..n * n).collect::<Vec<usize>>());
+        let mut new = vec![0; n * n];
+        for (k, old) in order.iter().enumerate() {
+            new[*old] = k;
+        }
+        let renumbered: Vec<(usize, usize)> =
+            scrambled.iter().map(|(i, j)| (new[*i], new[*j])).collect();
+        let after = Skyline::from_pattern(n * n, &renumbered);
+        assert!(
+            after.bandwidth() <= n + 1,
+            "bandwidth {}",
+            after.bandwidth()
+        );
+        assert!(after.len() < before.len());
+        // disconnected pieces and isolated nodes are still numbered
+        assert_eq!(reverse_cuthill_mckee(4, &[(0, 2)]).len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 4 out of range for 4 nodes")]
+    fn pattern_out_of_range() {
+        Skyline::from_pattern(4, &[(0, 1), (4, 2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of range for 3 nodes")]
+    fn renumbering_out_of_range() {
+        reverse_cuthill_mckee(3, &[(0, 1), (3, 3)]);
+    }
+}
@@ -2,3 +2,13 @@
 
 pub mod linalg;
 pub mod calculus;
+pub mod roots;
+pub mod nonlinear;
+pub mod ode;
+pub mod interp;
+pub mod fit;
+pub mod eigen;
+pub mod stats;
+pub mod optimize;
+pub mod dual;
+pub mod geometry;
@@ -0,0 +1,337 @@
+//! # nonlinear
+//!
+//! Systems of nonlinear equations, `F(x) = 0` with as many equations as
+//! unknowns: flows in a pipe network, coupled factors of safety, phase
+//! relationships in soils. `newton_raphson` solves them by Newton's method
+//! with a backtracking line search, so each step has to reduce the residual.
+//! The Jacobian comes from finite differences unless you supply one.
+//!
+//! ```rust
+//! use civil::calc::nonlinear::newton_raphson;
+//! use civil::calc::roots::Tolerance;
+//!
+//! // Two pipes in parallel carry 1 m³/s between the same two nodes, so they
+//! // lose the same head: h = k Q².
+//! let (k1, k2) = (4.0, 9.0);
+//! let residual = |q: &[f64]| vec![q[0] + q[1] - 1.0, k1 * q[0] * q[0].abs() - k2 * q[1] * q[1].abs()];
+//! let s = newton_raphson(residual, &[0.5, 0.5], Tolerance::default()).unwrap();
+//! assert!((s.x[0] - 0.6).abs() < 1e-10);
+//! assert!((s.x[1] - 0.4).abs() < 1e-10);
+//! ```
+
+use super::linalg::{Matrix, MatrixError};
+use super::roots::Tolerance;
+use std::error::Error;
+use std::fmt;
+
+/// Solution is a converged solution of a nonlinear system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    /// The unknowns
+    pub x: Vec<f64>,
+    /// Residuals at `x`
+    pub residual: Vec<f64>,
+    /// Newton iterations taken
+    pub iterations: usize,
+    /// Number of times the residual was evaluated, including those for
+    /// finite difference Jacobians and the line search
+    pub evaluations: usize,
+}
+
+/// NonlinearError explains why a nonlinear solve failed. Variants reached
+/// during the iteration carry the iterate they stopped at, `x`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NonlinearError {
+    /// The residual has a different length than the unknowns: `equations`
+    /// residuals for `unknowns` unknowns. Nothing was solved.
+    Shape { unknowns: usize, equations: usize },
+    /// No step along the Newton direction reduced the residual; usually the
+    /// system has no solution near the starting point. `norm` is the
+    /// largest residual at `x`, reached after `iterations` steps.
+    Diverged {
+        x: Vec<f64>,
+        norm: f64,
+        iterations: usize,
+    },
+    /// The iteration limit was reached before the tolerance was met. `norm`
+    /// is the largest residual at the last iterate, `x`.
+    MaxIterations { x: Vec<f64>, norm: f64 },
+    /// The residual was not finite, ie NaN or infinite, at `x`.
+    NotFinite { x: Vec<f64> },
+    /// The Jacobian at `x` could not be solved with; `error` says why.
+    Jacobian { x: Vec<f64>, error: MatrixError },
+}
+
+impl fmt::Display for NonlinearError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NonlinearError::Shape {
+                unknowns,
+                equations,
+            } => write!(f, "{} equations for {} unknowns", equations, unknowns),
+            NonlinearError::Diverged {
+                norm, iterations, ..
+            } => write!(
+                f,
+                "diverged after {} iterations: no step reduces the residual from {:.3e}",
+                iterations, norm
+            ),
+            NonlinearError::MaxIterations { norm, .. } => {
+                write!(f, "did not converge; residual is still {:.3e}", norm)
+            }
+            NonlinearError::NotFinite { x } => write!(f, "residual is not finite at {:?}", x),
+            NonlinearError::Jacobian { error, .. } => write!(f, "jacobian: {}", error),
+        }
+    }
+}
+
+impl Error for NonlinearError {}
+
+/// newton_raphson solves `f(x) = 0` from a starting point `x0`, using a
+/// forward difference Jacobian. It stops when a step is within `tol` of
+/// the last iterate or the largest residual is below `tol.f_abs`.
+pub fn newton_raphson<F>(f: F, x0: &[f64], tol: Tolerance) -> Result<Solution, NonlinearError>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    solve(
+        &f,
+        |x: &[f64], fx: &[f64]| (jacobian(&f, x, fx), x.len()),
+        x0,
+        tol,
+    )
+}
+
+/// newton_raphson_with_jacobian is `newton_raphson` with the Jacobian
+/// supplied: `jacobian(x)[(i, j)]` is the derivative of residual i with
+/// respect to unknown j.
+pub fn newton_raphson_with_jacobian<F, J>(
+    f: F,
+    jacobian: J,
+    x0: &[f64],
+    tol: Tolerance,
+) -> Result<Solution, NonlinearError>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+    J: Fn(&[f64]) -> Matrix,
+{
+    solve(&f, |x: &[f64], _: &[f64]| (jacobian(x), 0), x0, tol)
+}
+
+/// jacobian estimates the Jacobian of `f` at `x` by forward differences,
+/// given `fx = f(x)`.
+pub fn jacobian<F>(f: F, x: &[f64], fx: &[f64]) -> Matrix
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    let mut j = Matrix::zeros(fx.len(), x.len());
+    let mut xh = x.to_vec();
+    for col in 0..x.len() {
+        let h = f64::EPSILON.sqrt() * x[col].abs().max(1.0);
+        xh[col] = x[col] + h;
+        // Use the step actually taken
+        let h = xh[col] - x[col];
+        for (row, (a, b)) in f(&xh).iter().zip(fx.iter()).enumerate() {
+            j[(row, col)] = (a - b) / h;
+        }
+        xh[col] = x[col];
+    }
+    j
+}
+
+// Armijo's sufficient decrease constant for the line search
+const ARMIJO: f64 = 1.0e-4;
+
+// Smallest fraction of the Newton step the line search will try
+const MIN_STEP: f64 = 1.0e-10;
+
+// Newton's method with backtracking. `jacobian` returns the Jacobian at x
+// along with the number of residual evaluations it took.
+fn solve<F, J>(f: &F, jacobian: J, x0: &[f64], tol: Tolerance) -> Result<Solution, NonlinearError>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+    J: Fn(&[f64], &[f64]) -> (Matrix, usize),
+{
+    let mut x = x0.to_vec();
+    let mut fx = f(&x);
+    let mut evaluations = 1;
+    if fx.len() != x.len() {
+        return Err(NonlinearError::Shape {
+            unknowns: x.len(),
+            equations: fx.len(),
+        });
+    }
+    if !finite(&fx) {
+        return Err(NonlinearError::NotFinite { x });
+    }
+    for i in 0..=tol.max_iterations {
+        if norm(&fx) <= tol.f_abs || fx.iter().all(|r| *r == 0.0) {
+            return Ok(Solution {
+                x,
+                residual: fx,
+                iterations: i,
+                evaluations,
+            });
+        }
+        if i == tol.max_iterations {
+            break;
+        }
+        let (jac, n) = jacobian(&x, &fx);
+        evaluations += n;
+        let minus: Vec<f64> = fx.iter().map(|r| -r).collect();
+        let step = match jac.solve(&minus) {
+            Ok(s) => s,
+            Err(error) => return Err(NonlinearError::Jacobian { x, error }),
+        };
+        if step.iter().zip(x.iter()).all(|(s, x)| tol.close(*s, *x)) {
+            // Close enough that rounding may stop the step from helping;
+            // take it only if it does
+            let next: Vec<f64> = x.iter().zip(step.iter()).map(|(x, s)| x + s).collect();
+            let fnext = f(&next);
+            evaluations += 1;
+            if finite(&fnext) && norm(&fnext) <= norm(&fx) {
+                x = next;
+                fx = fnext;
+            }
+            return Ok(Solution {
+                x,
+                residual: fx,
+                iterations: i + 1,
+                evaluations,
+            });
+        }
+        // Backtrack along the Newton direction until the sum of squares of
+        // the residuals drops enough
+        let merit = half_squares(&fx);
+        let mut lambda = 1.0;
+        loop {
+            let next: Vec<f64> = x
+                .iter()
+                .zip(step.iter())
+                .map(|(x, s)| x + lambda * s)
+                .collect();
+            let fnext = f(&next);
+            evaluations += 1;
+            if finite(&fnext) && half_squares(&fnext) <= merit * (1.0 - 2.0 * ARMIJO * lambda) {
+                x = next;
+                fx = fnext;
+                break;
+            }
+            lambda /= 2.0;
+            if lambda < MIN_STEP {
+                return Err(NonlinearError::Diverged {
+                    norm: norm(&fx),
+                    x,
+                    iterations: i + 1,
+                });
+            }
+        }
+    }
+    Err(NonlinearError::MaxIterations { norm: norm(&fx), x })
+}
+
+// Largest absolute residual
+fn norm(v: &[f64]) -> f64 {
+    v.iter().fold(0.0, |m, r| m.max(r.abs()))
+}
+
+fn half_squares(v: &[f64]) -> f64 {
+    v.iter().map(|r| r * r).sum::<f64>() / 2.0
+}
+
+fn finite(v: &[f64]) -> bool {
+    v.iter().all(|r| r.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{newton_raphson, newton_raphson_with_jacobian, NonlinearError};
+    use crate::calc::linalg::Matrix;
+    use crate::calc::roots::Tolerance;
+
+    const PRECISION: f64 = 1.0e-10;
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            f: fn(&[f64]) -> Vec<f64>,
+            x0: Vec<f64>,
+            expected: Vec<f64>,
+        }
+        let table = [
+            // a circle and a line
+            Row {
+                f: |x| vec![x[0] * x[0] + x[1] * x[1] - 4.0, x[0] - x[1]],
+                x0: vec![1.0, 2.0],
+                expected: vec![2f64.sqrt(), 2f64.sqrt()],
+            },
+            // Rosenbrock's function, as a system; plain Newton overshoots
+            // badly from here
+            Row {
+                f: |x| vec![10.0 * (x[1] - x[0] * x[0]), 1.0 - x[0]],
+                x0: vec![-1.2, 1.0],
+                expected: vec![1.0, 1.0],
+            },
+            Row {
+                f: |x| {
+                    vec![
+                        x[0] + x[1] + x[2] - 6.0,
+                        x[0] * x[1] * x[2] - 6.0,
+                        x[0].exp() - 1f64.exp(),
+                    ]
+                },
+                x0: vec![0.5, 1.5, 3.5],
+                expected: vec![1.0, 2.0, 3.0],
+            },
+        ];
+        for r in table.iter() {
+            let s = newton_raphson(r.f, &r.x0, Tolerance::default()).unwrap();
+            for (x, e) in s.x.iter().zip(r.expected.iter()) {
+                assert!((x - e).abs() < PRECISION, "{:?}", s);
+            }
+        }
+    }
+
+    #[test]
+    fn user_jacobian() {
+        let f = |x: &[f64]| vec![x[0] * x[0] + x[1] * x[1] - 4.0, x[0] - x[1]];
+        let j = |x: &[f64]| Matrix::from_rows(&[[2.0 * x[0], 2.0 * x[1]], [1.0, -1.0]]).unwrap();
+        let s = newton_raphson_with_jacobian(f, j, &[1.0, 2.0], Tolerance::default()).unwrap();
+        assert!((s.x[0] - 2f64.sqrt()).abs() < PRECISION);
+        let fd = newton_raphson(f, &[1.0, 2.0], Tolerance::default()).unwrap();
+        assert!(s.evaluations < fd.evaluations);
+    }
+
+    #[test]
+    fn failures() {
+        // x² + 1 = 0 has no real solution
+        match newton_raphson(|x| vec![x[0] * x[0] + 1.0], &[0.5], Tolerance::default()) {
+            Err(e @ NonlinearError::Diverged { .. }) => assert_eq!(
+                e.to_string(),
+                "diverged after 4 iterations: no step reduces the residual from 1.000e0"
+            ),
+            other => panic!("expected divergence, got {:?}", other),
+        }
+        match newton_raphson(|x| vec![x[0] - 1.0], &[0.0, 0.0], Tolerance::default()) {
+            Err(NonlinearError::Shape {
+                unknowns: 2,
+                equations: 1,
+            }) => {}
+            other => panic!("expected a shape error, got {:?}", other),
+        }
+        // singular Jacobian at the start
+        let dependent = |x: &[f64]| vec![x[0] + x[1] - 1.0, 2.0 * x[0] + 2.0 * x[1] - 3.0];
+        match newton_raphson(dependent, &[0.0, 0.0], Tolerance::default()) {
+            Err(NonlinearError::Jacobian { x, .. }) => assert_eq!(x, vec![0.0, 0.0]),
+            other => panic!("expected a singular jacobian, got {:?}", other),
+        }
+        let once = Tolerance {
+            max_iterations: 1,
+            ..Tolerance::default()
+        };
+        match newton_raphson(|x| vec![x[0].exp() - 2.0], &[3.0], once) {
+            Err(NonlinearError::MaxIterations { .. }) => {}
+            other => panic!("expected to run out of iterations, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,652 @@
+//! # ode
+//!
+//! Initial value problems, `dy/dt = f(t, y)` with `y` a vector: gradually
+//! varied flow profiles, level-pool routing, consolidation, the response of
+//! a single degree of freedom structure. `f` is a closure, so it can capture
+//! whatever parameters the model needs.
+//!
+//! - `rk4` takes fixed steps with the classical fourth order Runge-Kutta
+//!   method; simple and predictable
+//! - `dormand_prince` adapts its step to meet a tolerance with the fifth
+//!   order Dormand-Prince pair; the usual choice
+//!
+//! Both return a `Trajectory`, which can be evaluated anywhere between its
+//! steps with `at`. The `_until` variants stop at an event: the first time
+//! `event(t, y)` changes sign, such as the depth reaching critical.
+//!
+//! ```rust
+//! use civil::calc::ode::{dormand_prince_until, Options};
+//!
+//! // Draining a tank through an orifice: A dh/dt = -Cd a sqrt(2 g h)
+//! let (area, orifice, cd, g) = (2.0, 0.01, 0.6, 9.806_65);
+//! let f = |_t: f64, h: &[f64]| vec![-cd * orifice * (2.0 * g * h[0]).sqrt() / area];
+//! // how long until it is down to half a meter from three?
+//! let run = dormand_prince_until(f, |_t, h| h[0] - 0.5, 0.0, &[3.0], 1.0e4, Options::default())
+//!     .unwrap();
+//! assert!(run.stopped);
+//! let k = cd * orifice * (2.0 * g).sqrt() / area;
+//! let exact = 2.0 * (3f64.sqrt() - 0.5f64.sqrt()) / k;
+//! assert!((run.t.last().unwrap() - exact).abs() < 1e-6);
+//! ```
+
+use super::roots::{brent, Tolerance};
+use std::error::Error;
+use std::fmt;
+
+/// Options controls the step size of `dormand_prince`. Each step keeps the
+/// estimated error in every component below `abs + rel * |y|`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+    /// Relative tolerance
+    pub rel: f64,
+    /// Absolute tolerance
+    pub abs: f64,
+    /// Size of the first step; zero to choose one automatically
+    pub first_step: f64,
+    /// Largest step allowed; zero for no limit
+    pub max_step: f64,
+    /// The integration fails after this many steps
+    pub max_steps: usize,
+}
+
+impl Options {
+    /// new sets the relative and absolute tolerances, leaving the rest at
+    /// their defaults.
+    pub fn new(rel: f64, abs: f64) -> Options {
+        Options {
+            rel,
+            abs,
+            ..Options::default()
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            rel: 1.0e-8,
+            abs: 1.0e-10,
+            first_step: 0.0,
+            max_step: 0.0,
+            max_steps: 100_000,
+        }
+    }
+}
+
+/// OdeError is returned when an adaptive integration can't continue. `t` is
+/// how far it got.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OdeError {
+    /// The step needed to meet the tolerance became too small to make
+    /// progress, which usually means the solution blows up near `t`.
+    StepTooSmall { t: f64 },
+    /// `Options::max_steps` were taken without reaching the end.
+    MaxSteps { t: f64 },
+    /// The derivative was not finite.
+    NotFinite { t: f64 },
+}
+
+impl fmt::Display for OdeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OdeError::StepTooSmall { t } => write!(f, "step size too small at t = {}", t),
+            OdeError::MaxSteps { t } => write!(f, "too many steps; stopped at t = {}", t),
+            OdeError::NotFinite { t } => write!(f, "derivative is not finite at t = {}", t),
+        }
+    }
+}
+
+impl Error for OdeError {}
+
+// An event function, g(t, y)
+type Event<'a> = dyn Fn(f64, &[f64]) -> f64 + 'a;
+
+// The interpolant over one step of length h from y0:
+// y(θ) = y0 + θ (r[0] + (1 - θ) (r[1] + θ (r[2] + (1 - θ) r[3])))
+#[derive(Debug, Clone, PartialEq)]
+struct Segment {
+    h: f64,
+    r: [Vec<f64>; 4],
+}
+
+/// Trajectory is the solution of an initial value problem: the state at
+/// the end of every step, plus what is needed to interpolate between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trajectory {
+    /// Time at the start and at the end of each step
+    pub t: Vec<f64>,
+    /// State at each time in `t`
+    pub y: Vec<Vec<f64>>,
+    /// True when an event stopped the integration; the last entry of `t`
+    /// and `y` is where it happened
+    pub stopped: bool,
+    /// Number of times `f` was evaluated
+    pub evaluations: usize,
+    segments: Vec<Segment>,
+}
+
+impl Trajectory {
+    fn new(t0: f64, y0: &[f64]) -> Trajectory {
+        Trajectory {
+            t: vec![t0],
+            y: vec![y0.to_vec()],
+            stopped: false,
+            evaluations: 0,
+            segments: Vec::new(),
+        }
+    }
+
+    /// at interpolates the state at any time covered by the trajectory. The
+    /// interpolant is fourth order for `dormand_prince` and a cubic Hermite
+    /// for `rk4`. Returns `None` outside the trajectory.
+    pub fn at(&self, t: f64) -> Option<Vec<f64>> {
+        let (first, last) = (self.t[0], *self.t.last().unwrap());
+        let forward = last >= first;
+        let inside = if forward {
+            first <= t && t <= last
+        } else {
+            last <= t && t <= first
+        };
+        if !inside {
+            return None;
+        }
+        // The step containing t
+        let i = self
+            .t
+            .partition_point(|s| if forward { *s <= t } else { *s >= t })
+            .clamp(1, self.segments.len().max(1))
+            - 1;
+        if self.segments.is_empty() {
+            return Some(self.y[0].clone());
+        }
+        Some(self.segments[i].eval(&self.y[i], (t - self.t[i]) / self.segments[i].h))
+    }
+
+    /// last is the final time and state.
+    pub fn last(&self) -> (f64, &[f64]) {
+        (*self.t.last().unwrap(), self.y.last().unwrap())
+    }
+
+    // Record a step, then check for an event over it. Returns true when
+    // the integration should stop.
+    fn push(&mut self, t: f64, y: Vec<f64>, segment: Segment, event: Option<&Event<'_>>) -> bool {
+        let (t0, y0) = (*self.t.last().unwrap(), self.y.last().unwrap().clone());
+        let crossing = event.and_then(|g| {
+            let (g0, g1) = (g(t0, &y0), g(t, &y));
+            if g0 == 0.0 || g0.signum() == g1.signum() && g1 != 0.0 {
+                return None;
+            }
+            let at = |tau: f64| segment.eval(&y0, (tau - t0) / segment.h);
+            let root = brent(|tau| g(tau, &at(tau)), t0, t, Tolerance::default());
+            Some((root.x, at(root.x)))
+        });
+        self.segments.push(segment);
+        match crossing {
+            Some((te, ye)) => {
+                self.t.push(te);
+                self.y.push(ye);
+                self.stopped = true;
+            }
+            None => {
+                self.t.push(t);
+                self.y.push(y);
+            }
+        }
+        self.stopped
+    }
+}
+
+impl Segment {
+    fn eval(&self, y0: &[f64], theta: f64) -> Vec<f64> {
+        let s = 1.0 - theta;
+        let r = &self.r;
+        (0..y0.len())
+            .map(|i| y0[i] + theta * (r[0][i] + s * (r[1][i] + theta * (r[2][i] + s * r[3][i]))))
+            .collect()
+    }
+
+    // Cubic Hermite interpolant from the ends of a step and their slopes
+    fn hermite(h: f64, y0: &[f64], y1: &[f64], f0: &[f64], f1: &[f64]) -> Segment {
+        let n = y0.len();
+        let dy: Vec<f64> = (0..n).map(|i| y1[i] - y0[i]).collect();
+        let b: Vec<f64> = (0..n).map(|i| h * f0[i] - dy[i]).collect();
+        let c: Vec<f64> = (0..n).map(|i| dy[i] - h * f1[i] - b[i]).collect();
+        Segment {
+            h,
+            r: [dy, b, c, vec![0.0; n]],
+        }
+    }
+}
+
+/// rk4 integrates `dy/dt = f(t, y)` from `t0` to `t1` in `steps` equal
+/// steps of the classical Runge-Kutta method. `t1` may be before `t0`.
+pub fn rk4<F>(f: F, t0: f64, y0: &[f64], t1: f64, steps: usize) -> Trajectory
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    fixed(&f, None, t0, y0, t1, steps)
+}
+
+/// rk4_until is `rk4`, stopping early the first time `event(t, y)` changes
+/// sign.
+pub fn rk4_until<F, G>(f: F, event: G, t0: f64, y0: &[f64], t1: f64, steps: usize) -> Trajectory
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+    G: Fn(f64, &[f64]) -> f64,
+{
+    fixed(&f, Some(&event), t0, y0, t1, steps)
+}
+
+fn fixed<F>(
+    f: &F,
+    event: Option<&Event<'_>>,
+    t0: f64,
+    y0: &[f64],
+    t1: f64,
+    steps: usize,
+) -> Trajectory
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    let mut run = Trajectory::new(t0, y0);
+    let steps = steps.max(1);
+    let h = (t1 - t0) / steps as f64;
+    let mut y = y0.to_vec();
+    let mut k1 = f(t0, &y);
+    run.evaluations += 1;
+    for i in 0..steps {
+        let t = t0 + h * i as f64;
+        let k2 = f(t + h / 2.0, &axpy(&y, h / 2.0, &k1));
+        let k3 = f(t + h / 2.0, &axpy(&y, h / 2.0, &k2));
+        let k4 = f(t + h, &axpy(&y, h, &k3));
+        let next: Vec<f64> = (0..y.len())
+            .map(|j| y[j] + h / 6.0 * (k1[j] + 2.0 * k2[j] + 2.0 * k3[j] + k4[j]))
+            .collect();
+        // The slope at the end starts the next step, and gives the dense
+        // output its derivative
+        let t = if i + 1 == steps { t1 } else { t + h };
+        let f1 = f(t, &next);
+        run.evaluations += 4;
+        let segment = Segment::hermite(h, &y, &next, &k1, &f1);
+        if run.push(t, next.clone(), segment, event) {
+            break;
+        }
+        y = next;
+        k1 = f1;
+    }
+    run
+}
+
+/// dormand_prince integrates `dy/dt = f(t, y)` from `t0` to `t1` with the
+/// adaptive Dormand-Prince 5(4) method, choosing each step to keep the
+/// local error within `options`. `t1` may be before `t0`.
+///
+/// ```rust
+/// use civil::calc::ode::{dormand_prince, Options};
+///
+/// // free vibration of an undamped oscillator, 1 Hz
+/// let w = 2.0 * std::f64::consts::PI;
+/// let f = |_t: f64, y: &[f64]| vec![y[1], -w * w * y[0]];
+/// let run = dormand_prince(f, 0.0, &[1.0, 0.0], 10.0, Options::new(1e-10, 1e-12)).unwrap();
+/// assert!((run.last().1[0] - 1.0).abs() < 1e-7);
+/// // anywhere in between
+/// let y = run.at(2.125).unwrap();
+/// assert!((y[0] - (w * 2.125).cos()).abs() < 1e-6);
+/// ```
+pub fn dormand_prince<F>(
+    f: F,
+    t0: f64,
+    y0: &[f64],
+    t1: f64,
+    options: Options,
+) -> Result<Trajectory, OdeError>
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    adaptive(&f, None, t0, y0, t1, options)
+}
+
+/// dormand_prince_until is `dormand_prince`, stopping early the first time
+/// `event(t, y)` changes sign. The event is located on the interpolant to
+/// within rounding.
+pub fn dormand_prince_until<F, G>(
+    f: F,
+    event: G,
+    t0: f64,
+    y0: &[f64],
+    t1: f64,
+    options: Options,
+) -> Result<Trajectory, OdeError>
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+    G: Fn(f64, &[f64]) -> f64,
+{
+    adaptive(&f, Some(&event), t0, y0, t1, options)
+}
+
+// The Dormand-Prince tableau
+const C: [f64; 7] = [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+const A: [[f64; 6]; 7] = [
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+        0.0,
+    ],
+    // The fifth order weights; the last stage is f at the new point
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+// Fifth order weights less the embedded fourth order ones
+const E: [f64; 7] = [
+    71.0 / 57600.0,
+    0.0,
+    -71.0 / 16695.0,
+    71.0 / 1920.0,
+    -17253.0 / 339_200.0,
+    22.0 / 525.0,
+    -1.0 / 40.0,
+];
+// Dense output weights (Hairer, Nørsett and Wanner)
+const D: [f64; 7] = [
+    -12_715_105_075.0 / 11_282_082_432.0,
+    0.0,
+    87_487_479_700.0 / 32_700_410_799.0,
+    -10_690_763_975.0 / 1_880_347_072.0,
+    701_980_252_875.0 / 199_316_789_632.0,
+    -1_453_857_185.0 / 822_651_844.0,
+    69_997_945.0 / 29_380_423.0,
+];
+
+fn adaptive<F>(
+    f: &F,
+    event: Option<&Event<'_>>,
+    t0: f64,
+    y0: &[f64],
+    t1: f64,
+    options: Options,
+) -> Result<Trajectory, OdeError>
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    let mut run = Trajectory::new(t0, y0);
+    if t1 == t0 {
+        return Ok(run);
+    }
+    let direction = (t1 - t0).signum();
+    let span = (t1 - t0).abs();
+    let max_step = if options.max_step > 0.0 {
+        options.max_step.min(span)
+    } else {
+        span
+    };
+    let n = y0.len();
+    let (mut t, mut y) = (t0, y0.to_vec());
+    let mut k1 = f(t, &y);
+    run.evaluations += 1;
+    if !finite(&k1) {
+        return Err(OdeError::NotFinite { t });
+    }
+    let mut h = if options.first_step > 0.0 {
+        options.first_step
+    } else {
+        run.evaluations += 1;
+        first_step(f, t, &y, &k1, direction, options)
+    }
+    .min(max_step);
+    let mut k: Vec<Vec<f64>> = vec![Vec::new(); 7];
+    for _ in 0..options.max_steps {
+        // Don't overshoot, or leave a sliver at the end
+        let last = h >= (t1 - t).abs() * (1.0 - 1.0e-12);
+        if last {
+            h = (t1 - t).abs();
+        }
+        if h <= 16.0 * f64::EPSILON * t.abs().max(span) {
+            return Err(OdeError::StepTooSmall { t });
+        }
+        let dt = direction * h;
+        k[0] = k1.clone();
+        let mut next = Vec::new();
+        for s in 1..7 {
+            let ys: Vec<f64> = (0..n)
+                .map(|i| y[i] + dt * (0..s).map(|j| A[s][j] * k[j][i]).sum::<f64>())
+                .collect();
+            // The last stage is the new point itself
+            let ts = if s == 6 && last { t1 } else { t + C[s] * dt };
+            k[s] = f(ts, &ys);
+            next = ys;
+        }
+        run.evaluations += 6;
+        // Scaled RMS of the error estimate
+        let error = ((0..n)
+            .map(|i| {
+                let e = dt * (0..7).map(|j| E[j] * k[j][i]).sum::<f64>();
+                let scale = options.abs + options.rel * y[i].abs().max(next[i].abs());
+                (e / scale).powi(2)
+            })
+            .sum::<f64>()
+            / n.max(1) as f64)
+            .sqrt();
+        if !error.is_finite() || !finite(&next) {
+            // Try again much smaller
+            h /= 10.0;
+            continue;
+        }
+        if error <= 1.0 {
+            let t_next = if last { t1 } else { t + dt };
+            let segment = dense(dt, &y, &next, &k);
+            if run.push(t_next, next.clone(), segment, event) || last {
+                return Ok(run);
+            }
+            t = t_next;
+            y = next;
+            k1 = k[6].clone();
+        }
+        // Grow or shrink the step, within limits
+        let factor = if error == 0.0 {
+            5.0
+        } else {
+            (0.9 * error.powf(-0.2)).clamp(0.2, 5.0)
+        };
+        h = (h * if error <= 1.0 {
+            factor
+        } else {
+            factor.min(1.0)
+        })
+        .min(max_step);
+    }
+    Err(OdeError::MaxSteps { t })
+}
+
+// Hairer's interpolant for a Dormand-Prince step
+fn dense(dt: f64, y0: &[f64], y1: &[f64], k: &[Vec<f64>]) -> Segment {
+    let n = y0.len();
+    let dy: Vec<f64> = (0..n).map(|i| y1[i] - y0[i]).collect();
+    let b: Vec<f64> = (0..n).map(|i| dt * k[0][i] - dy[i]).collect();
+    let c: Vec<f64> = (0..n).map(|i| dy[i] - dt * k[6][i] - b[i]).collect();
+    let d: Vec<f64> = (0..n)
+        .map(|i| dt * (0..7).map(|j| D[j] * k[j][i]).sum::<f64>())
+        .collect();
+    Segment {
+        h: dt,
+        r: [dy, b, c, d],
+    }
+}
+
+// Hairer's estimate of a first step from the size of y and its derivatives
+fn first_step<F>(f: &F, t: f64, y: &[f64], f0: &[f64], direction: f64, options: Options) -> f64
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    let n = y.len().max(1) as f64;
+    let scale: Vec<f64> = y
+        .iter()
+        .map(|v| options.abs + options.rel * v.abs())
+        .collect();
+    let rms = |v: &[f64]| {
+        (v.iter()
+            .zip(scale.iter())
+            .map(|(a, s)| (a / s).powi(2))
+            .sum::<f64>()
+            / n)
+            .sqrt()
+    };
+    let (d0, d1) = (rms(y), rms(f0));
+    let h0 = if d0 < 1.0e-5 || d1 < 1.0e-5 {
+        1.0e-6
+    } else {
+        0.01 * d0 / d1
+    };
+    let f1 = f(t + direction * h0, &axpy(y, direction * h0, f0));
+    let diff: Vec<f64> = f1.iter().zip(f0.iter()).map(|(a, b)| a - b).collect();
+    let d2 = rms(&diff) / h0;
+    let h1 = if d1.max(d2) <= 1.0e-15 {
+        (h0 * 1.0e-3).max(1.0e-6)
+    } else {
+        (0.01 / d1.max(d2)).powf(0.2)
+    };
+    (100.0 * h0).min(h1)
+}
+
+// y + a x
+fn axpy(y: &[f64], a: f64, x: &[f64]) -> Vec<f64> {
+    y.iter().zip(x.iter()).map(|(y, x)| y + a * x).collect()
+}
+
+fn finite(v: &[f64]) -> bool {
+    v.iter().all(|x| x.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dormand_prince, dormand_prince_until, rk4, rk4_until, OdeError, Options};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            f: fn(f64, &[f64]) -> Vec<f64>,
+            y0: Vec<f64>,
+            t1: f64,
+            exact: fn(f64) -> f64,
+        }
+        let table = [
+            Row {
+                f: |_, y| vec![-0.5 * y[0]],
+                y0: vec![2.0],
+                t1: 4.0,
+                exact: |t| 2.0 * (-0.5 * t).exp(),
+            },
+            // depends on t as well as y
+            Row {
+                f: |t, y| vec![y[0] * t.cos()],
+                y0: vec![1.0],
+                t1: 6.0,
+                exact: |t| t.sin().exp(),
+            },
+            Row {
+                f: |_, y| vec![y[1], -y[0]],
+                y0: vec![0.0, 1.0],
+                t1: -5.0,
+                exact: |t| t.sin(),
+            },
+        ];
+        for r in table.iter() {
+            let fixed = rk4(r.f, 0.0, &r.y0, r.t1, 200);
+            let (t, y) = fixed.last();
+            assert_eq!(t, r.t1);
+            assert!((y[0] - (r.exact)(r.t1)).abs() < 1.0e-6, "{:?}", y);
+            let run =
+                dormand_prince(r.f, 0.0, &r.y0, r.t1, Options::new(1.0e-10, 1.0e-12)).unwrap();
+            let (t, y) = run.last();
+            assert_eq!(t, r.t1);
+            assert!((y[0] - (r.exact)(r.t1)).abs() < 1.0e-8, "{:?}", y);
+            // dense output between the steps
+            for i in 0..=20 {
+                let t = r.t1 * i as f64 / 20.0;
+                let y = run.at(t).unwrap();
+                assert!((y[0] - (r.exact)(t)).abs() < 1.0e-7, "{} {:?}", t, y);
+                let y = fixed.at(t).unwrap();
+                assert!((y[0] - (r.exact)(t)).abs() < 1.0e-5, "{} {:?}", t, y);
+            }
+            assert_eq!(run.at(2.0 * r.t1), None);
+        }
+    }
+
+    #[test]
+    fn adapts_the_step() {
+        let f = |_: f64, y: &[f64]| vec![-y[0]];
+        let loose = dormand_prince(f, 0.0, &[1.0], 10.0, Options::new(1.0e-4, 1.0e-6)).unwrap();
+        let tight = dormand_prince(f, 0.0, &[1.0], 10.0, Options::new(1.0e-12, 1.0e-14)).unwrap();
+        assert!(loose.t.len() < tight.t.len());
+        assert!(loose.evaluations < tight.evaluations);
+        let (_, y) = tight.last();
+        assert!((y[0] - (-10f64).exp()).abs() < 1.0e-12);
+        let capped = Options {
+            max_step: 0.5,
+            ..Options::default()
+        };
+        let run = dormand_prince(f, 0.0, &[1.0], 10.0, capped).unwrap();
+        assert!(run.t.windows(2).all(|w| w[1] - w[0] <= 0.5 + 1.0e-12));
+    }
+
+    #[test]
+    fn events() {
+        // a ball dropped from 10 m hits the ground after sqrt(2 h / g)
+        let g = 9.806_65;
+        let f = |_: f64, y: &[f64]| vec![y[1], -g];
+        let ground = |_: f64, y: &[f64]| y[0];
+        let exact = (2.0 * 10.0 / g).sqrt();
+        let run =
+            dormand_prince_until(f, ground, 0.0, &[10.0, 0.0], 5.0, Options::default()).unwrap();
+        assert!(run.stopped);
+        let (t, y) = run.last();
+        assert!((t - exact).abs() < 1.0e-10, "{}", t);
+        assert!(y[0].abs() < 1.0e-9);
+        let run = rk4_until(f, ground, 0.0, &[10.0, 0.0], 5.0, 50);
+        assert!(run.stopped);
+        assert!((run.last().0 - exact).abs() < 1.0e-10);
+        // no event, no stop
+        let run = rk4_until(f, |_, y| y[0] + 1000.0, 0.0, &[10.0, 0.0], 5.0, 50);
+        assert!(!run.stopped);
+        assert_eq!(run.last().0, 5.0);
+    }
+
+    #[test]
+    fn blow_up() {
+        // y' = y² from y = 1 reaches infinity at t = 1
+        let f = |_: f64, y: &[f64]| vec![y[0] * y[0]];
+        match dormand_prince(f, 0.0, &[1.0], 2.0, Options::default()) {
+            Err(OdeError::StepTooSmall { t }) | Err(OdeError::MaxSteps { t }) => {
+                assert!((t - 1.0).abs() < 1.0e-3, "{}", t)
+            }
+            other => panic!("expected a failure, got {:?}", other),
+        }
+        let sdof = |_: f64, y: &[f64]| vec![y[1], -4.0 * PI * PI * y[0]];
+        let few = Options {
+            max_steps: 3,
+            ..Options::default()
+        };
+        assert!(dormand_prince(sdof, 0.0, &[1.0, 0.0], 100.0, few).is_err());
+    }
+}
@@ -0,0 +1,632 @@
+//! # optimize
+//!
+//! Minimization: the cheapest section, the best outlet size, the critical
+//! slip circle. To maximize something, minimize its negative.
+//!
+//! - `bracket` widens a guess until it holds a minimum
+//! - `golden_section` narrows an interval holding a single minimum by the
+//!   golden ratio; slow but sure
+//! - `brent` combines golden section steps with parabolic interpolation;
+//!   the usual choice in one unknown
+//! - `nelder_mead` crawls a simplex downhill in several unknowns without
+//!   derivatives, so it copes with functions which are noisy or have kinks.
+//!   `nelder_mead_bounded` keeps it inside `Bounds`.
+//!
+//! Every method returns a `Minimum` saying how it got on, like the root
+//! finders in `roots`, and they share its `Tolerance` and `Status`. A
+//! function may return infinity or NaN where it can't be evaluated, ie an
+//! infeasible design, and the search will stay away.
+//!
+//! A minimum can't be located more closely than about the square root of
+//! the machine epsilon relative to its size, since the function is flat
+//! there to within rounding, so relative tolerances are raised to that.
+//!
+//! ```rust
+//! use civil::calc::optimize::brent;
+//! use civil::calc::roots::Tolerance;
+//!
+//! // the rectangular channel of least perimeter with an area of 8 m²
+//! let area = 8.0;
+//! let perimeter = |b: f64| b + 2.0 * area / b;
+//! let best = brent(perimeter, 0.5, 20.0, Tolerance::default());
+//! assert!(best.converged());
+//! // twice as wide as it is deep
+//! assert!((best.x - 4.0).abs() < 1e-6);
+//! ```
+
+use super::roots::{Status, Tolerance};
+
+/// Minimum is the result of a minimization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Minimum<X> {
+    /// Best estimate of the minimizer: an `f64` in one unknown, a `Vec` in
+    /// several
+    pub x: X,
+    /// Function value at `x`
+    pub fx: f64,
+    /// Iterations taken
+    pub iterations: usize,
+    /// Number of times the function was evaluated
+    pub evaluations: usize,
+    pub status: Status,
+}
+
+impl<X> Minimum<X> {
+    /// converged is true when the tolerance was met.
+    pub fn converged(&self) -> bool {
+        self.status == Status::Converged
+    }
+}
+
+// Golden ratio, and the fraction of an interval a golden section step
+// takes
+const PHI: f64 = 1.618_033_988_749_895;
+const GOLDEN: f64 = 0.381_966_011_250_105_1;
+
+// The relative tolerance actually used; see the module documentation
+fn x_rel(tol: &Tolerance) -> f64 {
+    tol.x_rel.max(f64::EPSILON.sqrt())
+}
+
+// f, with NaN treated as infinitely bad
+fn value<F: Fn(f64) -> f64>(f: &F, x: f64) -> f64 {
+    let v = f(x);
+    if v.is_nan() {
+        f64::INFINITY
+    } else {
+        v
+    }
+}
+
+/// bracket walks downhill from `a` toward `b`, taking growing steps, until
+/// the function turns up again. Returns an interval holding a minimum, or
+/// `None` if it runs downhill for 50 steps.
+///
+/// ```rust
+/// use civil::calc::optimize::bracket;
+///
+/// let (a, b) = bracket(|x| (x - 10.0).powi(2), 0.0, 1.0).unwrap();
+/// assert!(a < 10.0 && 10.0 < b);
+/// ```
+pub fn bracket<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> Option<(f64, f64)> {
+    if a == b {
+        return None;
+    }
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (value(&f, a), value(&f, b));
+    if fb > fa {
+        std::mem::swap(&mut a, &mut b);
+        std::mem::swap(&mut fa, &mut fb);
+    }
+    let mut c = b + PHI * (b - a);
+    let mut fc = value(&f, c);
+    for _ in 0..50 {
+        if fc > fb {
+            return Some((a.min(c), a.max(c)));
+        }
+        a = b;
+        b = c;
+        fb = fc;
+        c = b + PHI * (b - a);
+        fc = value(&f, c);
+    }
+    None
+}
+
+/// golden_section finds a minimum of `f` in `[a, b]`, shrinking the
+/// interval by the golden ratio each step. If `f` has several minima in the
+/// interval it finds one of them.
+pub fn golden_section<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: Tolerance) -> Minimum<f64> {
+    let (mut a, mut b) = (a.min(b), a.max(b));
+    let mut c = a + GOLDEN * (b - a);
+    let mut d = b - GOLDEN * (b - a);
+    let (mut fc, mut fd) = (value(&f, c), value(&f, d));
+    let rel = x_rel(&tol);
+    for i in 0..=tol.max_iterations {
+        let (x, fx) = if fc <= fd { (c, fc) } else { (d, fd) };
+        if b - a <= 2.0 * (tol.x_abs + rel * x.abs()) || i == tol.max_iterations {
+            return Minimum {
+                x,
+                fx,
+                iterations: i,
+                // two to start, then one per step
+                evaluations: i + 2,
+                status: if i == tol.max_iterations {
+                    Status::MaxIterations
+                } else {
+                    Status::Converged
+                },
+            };
+        }
+        if fc <= fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = a + GOLDEN * (b - a);
+            fc = value(&f, c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = b - GOLDEN * (b - a);
+            fd = value(&f, d);
+        }
+    }
+    unreachable!()
+}
+
+/// brent finds a minimum of `f` in `[a, b]` by Brent's method: a parabola
+/// through the best three points when it behaves, a golden section step
+/// when it doesn't. It is much faster than `golden_section` on smooth
+/// functions and never much slower.
+pub fn brent<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: Tolerance) -> Minimum<f64> {
+    let (mut a, mut b) = (a.min(b), a.max(b));
+    let rel = x_rel(&tol);
+    // x is the best point so far, w the second best and v the previous w
+    let mut x = a + GOLDEN * (b - a);
+    let (mut w, mut v) = (x, x);
+    let mut fx = value(&f, x);
+    let (mut fw, mut fv) = (fx, fx);
+    let mut evaluations = 1;
+    // The last step, and the one before it
+    let (mut d, mut e): (f64, f64) = (0.0, 0.0);
+    for i in 0..tol.max_iterations {
+        let middle = (a + b) / 2.0;
+        let tol1 = rel * x.abs() + tol.x_abs;
+        let tol2 = 2.0 * tol1;
+        if (x - middle).abs() <= tol2 - (b - a) / 2.0 {
+            return Minimum {
+                x,
+                fx,
+                iterations: i,
+                evaluations,
+                status: Status::Converged,
+            };
+        }
+        let mut golden = true;
+        if e.abs() > tol1 {
+            // Try a parabola through x, w and v
+            let r = (x - w) * (fx - fv);
+            let mut q = (x - v) * (fx - fw);
+            let mut p = (x - v) * q - (x - w) * r;
+            q = 2.0 * (q - r);
+            if q > 0.0 {
+                p = -p;
+            }
+            q = q.abs();
+            let last = e;
+            e = d;
+            // Take it only if it falls inside the interval and is less than
+            // half the step before last
+            if p.abs() < (0.5 * q * last).abs() && p > q * (a - x) && p < q * (b - x) {
+                d = p / q;
+                let u = x + d;
+                if u - a < tol2 || b - u < tol2 {
+                    d = tol1.copysign(middle - x);
+                }
+                golden = false;
+            }
+        }
+        if golden {
+            e = if x >= middle { a - x } else { b - x };
+            d = GOLDEN * e;
+        }
+        // Never step less than the tolerance
+        let u = if d.abs() >= tol1 {
+            x + d
+        } else {
+            x + tol1.copysign(d)
+        };
+        let fu = value(&f, u);
+        evaluations += 1;
+        if fu <= fx {
+            if u >= x {
+                a = x;
+            } else {
+                b = x;
+            }
+            v = w;
+            fv = fw;
+            w = x;
+            fw = fx;
+            x = u;
+            fx = fu;
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                v = w;
+                fv = fw;
+                w = u;
+                fw = fu;
+            } else if fu <= fv || v == x || v == w {
+                v = u;
+                fv = fu;
+            }
+        }
+    }
+    Minimum {
+        x,
+        fx,
+        iterations: tol.max_iterations,
+        evaluations,
+        status: Status::MaxIterations,
+    }
+}
+
+/// Bounds are lower and upper limits on each unknown. Use infinities for
+/// unknowns without a limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bounds {
+    pub lower: Vec<f64>,
+    pub upper: Vec<f64>,
+}
+
+impl Bounds {
+    /// new creates bounds from the lower and upper limits.
+    ///
+    /// # Panics
+    ///
+    /// If `lower` and `upper` are different lengths.
+    pub fn new(lower: &[f64], upper: &[f64]) -> Bounds {
+        assert_eq!(lower.len(), upper.len(), "bounds of different lengths");
+        Bounds {
+            lower: lower.to_vec(),
+            upper: upper.to_vec(),
+        }
+    }
+
+    /// contains is true when every unknown is within its limits.
+    pub fn contains(&self, x: &[f64]) -> bool {
+        x.iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .all(|(x, (l, u))| l <= x && x <= u)
+    }
+
+    /// clamp moves each unknown to the nearest point within its limits.
+    pub fn clamp(&self, x: &mut [f64]) {
+        for (x, (l, u)) in x.iter_mut().zip(self.lower.iter().zip(self.upper.iter())) {
+            *x = x.max(*l).min(*u);
+        }
+    }
+}
+
+/// nelder_mead minimizes `f` over several unknowns from a starting guess
+/// with the Nelder-Mead simplex method. It stops once every vertex of the
+/// simplex is within `tol` of the best one, or their values are within
+/// `tol.f_abs`. It is allowed `tol.max_iterations` iterations for each
+/// unknown.
+///
+/// The method is robust, but it is not guaranteed to converge to a minimum
+/// and is slow with more than a handful of unknowns. Restarting from the
+/// answer is a cheap check.
+///
+/// ```rust
+/// use civil::calc::optimize::nelder_mead;
+/// use civil::calc::roots::Tolerance;
+///
+/// // Rosenbrock's banana valley
+/// let banana = |x: &[f64]| 100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2);
+/// let tol = Tolerance {
+///     max_iterations: 500,
+///     ..Tolerance::new(1e-10, 1e-10)
+/// };
+/// let best = nelder_mead(banana, &[-1.2, 1.0], tol);
+/// assert!(best.converged());
+/// assert!((best.x[0] - 1.0).abs() < 1e-6 && (best.x[1] - 1.0).abs() < 1e-6);
+/// ```
+pub fn nelder_mead<F>(f: F, x0: &[f64], tol: Tolerance) -> Minimum<Vec<f64>>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let n = x0.len();
+    let free = Bounds {
+        lower: vec![f64::NEG_INFINITY; n],
+        upper: vec![f64::INFINITY; n],
+    };
+    simplex(&f, x0, &free, tol)
+}
+
+/// nelder_mead_bounded is `nelder_mead` kept within `bounds`: any vertex
+/// outside them is moved to the nearest point inside. The starting guess
+/// is moved inside too.
+///
+/// # Panics
+///
+/// If `bounds` don't have a lower and an upper limit for each unknown in
+/// `x0`.
+///
+/// ```rust
+/// use civil::calc::optimize::{nelder_mead_bounded, Bounds};
+/// use civil::calc::roots::Tolerance;
+///
+/// // the unconstrained minimum at (3, -1) is outside x ≤ 2
+/// let f = |x: &[f64]| (x[0] - 3.0).powi(2) + (x[1] + 1.0).powi(2);
+/// let bounds = Bounds::new(&[0.0, -5.0], &[2.0, 5.0]);
+/// let best = nelder_mead_bounded(f, &[1.0, 1.0], &bounds, Tolerance::new(1e-10, 1e-10));
+/// assert!((best.x[0] - 2.0).abs() < 1e-8 && (best.x[1] + 1.0).abs() < 1e-6);
+/// ```
+pub fn nelder_mead_bounded<F>(
+    f: F,
+    x0: &[f64],
+    bounds: &Bounds,
+    tol: Tolerance,
+) -> Minimum<Vec<f64>>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    assert!(
+        x0.len() == bounds.lower.len() && x0.len() == bounds.upper.len(),
+        "bounds don't match the unknowns"
+    );
+    simplex(&f, x0, bounds, tol)
+}
+
+fn simplex<F>(f: &F, x0: &[f64], bounds: &Bounds, tol: Tolerance) -> Minimum<Vec<f64>>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let n = x0.len();
+    let mut evaluations = 0;
+    let mut eval = |x: &mut Vec<f64>| {
+        bounds.clamp(x);
+        evaluations += 1;
+        let v = f(x);
+        if v.is_nan() {
+            f64::INFINITY
+        } else {
+            v
+        }
+    };
+    // The starting simplex steps 5% along each axis, or a little way from
+    // zero, and back if that leaves the bounds
+    let mut start = x0.to_vec();
+    bounds.clamp(&mut start);
+    let mut points = vec![start.clone()];
+    for i in 0..n {
+        let mut p = start.clone();
+        let step = if p[i] != 0.0 { 0.05 * p[i] } else { 2.5e-4 };
+        p[i] += step;
+        if p[i] > bounds.upper[i] || p[i] < bounds.lower[i] {
+            p[i] = start[i] - step;
+        }
+        points.push(p);
+    }
+    let mut values: Vec<f64> = points.iter_mut().map(&mut eval).collect();
+    let limit = tol.max_iterations * n.max(1);
+    let rel = x_rel(&tol);
+    let centroid = |points: &[Vec<f64>]| -> Vec<f64> {
+        (0..n)
+            .map(|j| points[..n].iter().map(|p| p[j]).sum::<f64>() / n as f64)
+            .collect()
+    };
+    // along(c, w, t) is c + t (c - w)
+    let along = |c: &[f64], w: &[f64], t: f64| -> Vec<f64> {
+        c.iter()
+            .zip(w.iter())
+            .map(|(c, w)| c + t * (c - w))
+            .collect()
+    };
+    for i in 0..=limit {
+        // Best first, worst last
+        let mut order: Vec<usize> = (0..=n).collect();
+        order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+        points = order.iter().map(|k| points[*k].clone()).collect();
+        values = order.iter().map(|k| values[*k]).collect();
+        let best = &points[0];
+        let small = points[1..].iter().all(|p| {
+            p.iter()
+                .zip(best.iter())
+                .all(|(p, b)| (p - b).abs() <= tol.x_abs + rel * b.abs())
+        });
+        let flat = values[n] - values[0] <= tol.f_abs;
+        if small || flat || i == limit {
+            return Minimum {
+                x: points[0].clone(),
+                fx: values[0],
+                iterations: i,
+                evaluations,
+                status: if small || flat {
+                    Status::Converged
+                } else {
+                    Status::MaxIterations
+                },
+            };
+        }
+        let c = centroid(&points);
+        let mut reflected = along(&c, &points[n], 1.0);
+        let fr = eval(&mut reflected);
+        if fr < values[0] {
+            let mut expanded = along(&c, &points[n], 2.0);
+            let fe = eval(&mut expanded);
+            if fe < fr {
+                points[n] = expanded;
+                values[n] = fe;
+            } else {
+                points[n] = reflected;
+                values[n] = fr;
+            }
+            continue;
+        }
+        if fr < values[n - 1] {
+            points[n] = reflected;
+            values[n] = fr;
+            continue;
+        }
+        // Contract, outside the simplex if the reflection helped at all
+        let (mut contracted, target) = if fr < values[n] {
+            (along(&c, &points[n], 0.5), fr)
+        } else {
+            (along(&c, &points[n], -0.5), values[n])
+        };
+        let fc = eval(&mut contracted);
+        if fc <= target {
+            points[n] = contracted;
+            values[n] = fc;
+            continue;
+        }
+        // Shrink toward the best vertex
+        for k in 1..=n {
+            let mut p: Vec<f64> = points[0]
+                .iter()
+                .zip(points[k].iter())
+                .map(|(b, p)| b + 0.5 * (p - b))
+                .collect();
+            values[k] = eval(&mut p);
+            points[k] = p;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bracket, brent, golden_section, nelder_mead, nelder_mead_bounded, Bounds};
+    use crate::calc::roots::{Status, Tolerance};
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            f: fn(f64) -> f64,
+            a: f64,
+            b: f64,
+            expected: f64,
+        }
+        let table = [
+            Row {
+                f: |x| (x - 2.0).powi(2) + 1.0,
+                a: 0.0,
+                b: 5.0,
+                expected: 2.0,
+            },
+            Row {
+                f: |x| x.cos(),
+                a: 2.0,
+                b: 4.0,
+                expected: std::f64::consts::PI,
+            },
+            // a kink
+            Row {
+                f: |x| (x - 1.0).abs(),
+                a: -3.0,
+                b: 2.0,
+                expected: 1.0,
+            },
+            // the minimum at an end
+            Row {
+                f: |x| x,
+                a: 1.0,
+                b: 2.0,
+                expected: 1.0,
+            },
+            // undefined in part of the interval
+            Row {
+                f: |x| x.ln() * x,
+                a: -1.0,
+                b: 1.0,
+                expected: (-1f64).exp(),
+            },
+        ];
+        for r in table.iter() {
+            for m in [
+                brent(r.f, r.a, r.b, Tolerance::default()),
+                golden_section(r.f, r.a, r.b, Tolerance::default()),
+            ]
+            .iter()
+            {
+                assert!(m.converged(), "{:?}", m);
+                assert!((m.x - r.expected).abs() < 1e-6, "{:?}", m);
+                assert_eq!(m.fx, (r.f)(m.x));
+            }
+        }
+    }
+
+    #[test]
+    fn brent_is_faster_than_golden_section() {
+        let f = |x: f64| x.exp() - 3.0 * x;
+        let tol = Tolerance::default();
+        let b = brent(f, 0.0, 3.0, tol);
+        let g = golden_section(f, 0.0, 3.0, tol);
+        assert!((b.x - 3f64.ln()).abs() < 1e-7);
+        assert!(b.evaluations < g.evaluations / 2, "{:?} {:?}", b, g);
+        let few = Tolerance {
+            max_iterations: 3,
+            ..tol
+        };
+        assert_eq!(brent(f, 0.0, 3.0, few).status, Status::MaxIterations);
+        assert_eq!(
+            golden_section(f, 0.0, 3.0, few).status,
+            Status::MaxIterations
+        );
+    }
+
+    #[test]
+    fn brackets() {
+        let (a, b) = bracket(|x| (x + 20.0).powi(2), 0.0, 1.0).unwrap();
+        assert!(a < -20.0 && -20.0 < b);
+        assert!(bracket(|x| x, 0.0, 1.0).is_none());
+        assert!(bracket(|x| x, 0.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn simplex() {
+        // a quadratic bowl in four unknowns
+        let bowl = |x: &[f64]| {
+            x.iter()
+                .enumerate()
+                .map(|(i, x)| (i + 1) as f64 * (x - i as f64).powi(2))
+                .sum::<f64>()
+        };
+        let tol = Tolerance::new(1e-10, 1e-10);
+        let tight = nelder_mead(bowl, &[1.0, 1.0, 1.0, 1.0], tol);
+        assert!(tight.converged(), "{:?}", tight);
+        for (i, x) in tight.x.iter().enumerate() {
+            assert!((x - i as f64).abs() < 1e-6, "{:?}", tight);
+        }
+        // infeasible regions are avoided
+        let walled = |x: &[f64]| {
+            if x[0] < 0.5 {
+                f64::NAN
+            } else {
+                x[0] * x[0] + x[1] * x[1]
+            }
+        };
+        let best = nelder_mead(walled, &[2.0, 2.0], tol);
+        assert!(
+            (best.x[0] - 0.5).abs() < 1e-6 && best.x[1].abs() < 1e-6,
+            "{:?}",
+            best
+        );
+        // relative tolerances below the square root of the machine epsilon
+        // are raised to it
+        let far = |x: &[f64]| (x[0] - 100.0).powi(2) + (x[1] - 200.0).powi(2);
+        let best = nelder_mead(far, &[90.0, 210.0], Tolerance::default());
+        let raised = Tolerance {
+            x_rel: f64::EPSILON.sqrt(),
+            ..Tolerance::default()
+        };
+        assert!(best.converged(), "{:?}", best);
+        assert_eq!(best, nelder_mead(far, &[90.0, 210.0], raised));
+        // stopping on the spread of values
+        let loose = Tolerance { f_abs: 1e-3, ..tol };
+        let quick = nelder_mead(bowl, &[1.0, 1.0, 1.0, 1.0], loose);
+        assert!(quick.converged() && quick.evaluations < tight.evaluations);
+        let bounds = Bounds::new(&[2.0, f64::NEG_INFINITY], &[3.0, f64::INFINITY]);
+        assert!(!bounds.contains(&[1.0, 0.0]));
+        let best = nelder_mead_bounded(
+            |x| x[0] * x[0] + (x[1] - 1.0).powi(2),
+            &[10.0, 10.0],
+            &bounds,
+            tol,
+        );
+        assert!(bounds.contains(&best.x));
+        assert!(
+            (best.x[0] - 2.0).abs() < 1e-9 && (best.x[1] - 1.0).abs() < 1e-6,
+            "{:?}",
+            best
+        );
+    }
+}
@@ -0,0 +1,632 @@
+//! # roots
+//!
+//! Root finding for equations in one unknown, such as normal depth from
+//! Manning's equation or a friction factor from Colebrook's. Every solver
+//! returns a `Root` saying how it got on rather than panicking, so a design
+//! routine can decide what to do when an equation has no solution.
+//!
+//! - `bracket` widens a guess until the function changes sign
+//! - `bisection` halves a bracket; slow but sure
+//! - `brent` combines bisection with interpolation; the usual choice
+//! - `newton` uses a numerical derivative from a single guess, guarding
+//!   against steps which make things worse
+//! - `newton_with_derivative` is `newton` with an exact derivative, eg
+//!   from `dual::derivative`
+//! - `newton_bracketed` is Newton's method kept inside a bracket
+//!
+//! ```rust
+//! use civil::calc::roots::{brent, Tolerance};
+//!
+//! // normal depth in a 3 m wide rectangular channel carrying 5 m³/s
+//! let (b, n, s, q): (f64, f64, f64, f64) = (3.0, 0.013, 0.001, 5.0);
+//! let manning = |y: f64| {
+//!     let (area, perimeter) = (b * y, b + 2.0 * y);
+//!     area * (area / perimeter).powf(2.0 / 3.0) * s.sqrt() / n - q
+//! };
+//! let root = brent(manning, 0.01, 10.0, Tolerance::default());
+//! assert!(root.converged());
+//! assert!((root.x - 0.9736).abs() < 1e-4);
+//! ```
+
+/// Tolerance says when a solver should stop.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// Absolute tolerance on the root
+    pub x_abs: f64,
+    /// Tolerance on the root relative to its size
+    pub x_rel: f64,
+    /// The solver also stops once `|f(x)|` is this small
+    pub f_abs: f64,
+    /// The solver gives up after this many iterations
+    pub max_iterations: usize,
+}
+
+impl Tolerance {
+    /// new creates a tolerance on the root, absolute and relative, with the
+    /// default iteration limit.
+    pub fn new(x_abs: f64, x_rel: f64) -> Tolerance {
+        Tolerance {
+            x_abs,
+            x_rel,
+            ..Tolerance::default()
+        }
+    }
+
+    // True once a step is small enough to stop
+    pub(crate) fn close(&self, step: f64, x: f64) -> bool {
+        step.abs() <= self.x_abs + self.x_rel * x.abs()
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance {
+            x_abs: 1.0e-12,
+            x_rel: 4.0 * f64::EPSILON,
+            f_abs: 0.0,
+            max_iterations: 100,
+        }
+    }
+}
+
+/// Status is how a solver finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The tolerance was met.
+    Converged,
+    /// The iteration limit was reached first. `x` is the best estimate.
+    MaxIterations,
+    /// The function has the same sign at both ends of the bracket.
+    NoBracket,
+    /// The function or its derivative stopped being usable, ie it was not
+    /// finite or the derivative was zero, and no step could improve on `x`.
+    Diverged,
+}
+
+/// Root is the result of a root search.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Root {
+    /// Best estimate of the root
+    pub x: f64,
+    /// Function value at `x`
+    pub fx: f64,
+    /// Iterations taken
+    pub iterations: usize,
+    /// Number of times the function was evaluated
+    pub evaluations: usize,
+    pub status: Status,
+}
+
+impl Root {
+    /// converged is true when the tolerance was met.
+    pub fn converged(&self) -> bool {
+        self.status == Status::Converged
+    }
+}
+
+// Bookkeeping shared by the solvers
+struct Counter<F> {
+    f: F,
+    evaluations: usize,
+}
+
+impl<F: Fn(f64) -> f64> Counter<F> {
+    fn new(f: F) -> Counter<F> {
+        Counter { f, evaluations: 0 }
+    }
+
+    fn eval(&mut self, x: f64) -> f64 {
+        self.evaluations += 1;
+        (self.f)(x)
+    }
+
+    fn root(&self, x: f64, fx: f64, iterations: usize, status: Status) -> Root {
+        Root {
+            x,
+            fx,
+            iterations,
+            evaluations: self.evaluations,
+            status,
+        }
+    }
+}
+
+/// bracket widens the interval `[a, b]` geometrically, up to 50 times,
+/// until `f` changes sign across it. Returns `None` if it never does.
+///
+/// ```rust
+/// use civil::calc::roots::bracket;
+///
+/// let (a, b) = bracket(|x| x * x - 50.0, 0.0, 1.0).unwrap();
+/// assert!(a * a - 50.0 < 0.0 && b * b - 50.0 > 0.0);
+/// ```
+pub fn bracket<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> Option<(f64, f64)> {
+    const GROW: f64 = 1.6;
+    if a == b {
+        return None;
+    }
+    let (mut a, mut b) = (a.min(b), a.max(b));
+    let (mut fa, mut fb) = (f(a), f(b));
+    for _ in 0..50 {
+        if !fa.is_finite() || !fb.is_finite() {
+            return None;
+        }
+        if fa * fb <= 0.0 {
+            return Some((a, b));
+        }
+        // Grow toward the end closer to zero
+        if fa.abs() < fb.abs() {
+            a += GROW * (a - b);
+            fa = f(a);
+        } else {
+            b += GROW * (b - a);
+            fb = f(b);
+        }
+    }
+    None
+}
+
+/// bisection finds a root in `[a, b]`, where `f(a)` and `f(b)` have opposite
+/// signs, by halving the interval.
+pub fn bisection<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: Tolerance) -> Root {
+    let mut f = Counter::new(f);
+    let (mut a, mut b) = (a, b);
+    let (fa, fb) = (f.eval(a), f.eval(b));
+    if let Some(root) = ends(&f, a, fa, b, fb) {
+        return root;
+    }
+    let mut fa = fa;
+    for i in 1..=tol.max_iterations {
+        let m = a + (b - a) / 2.0;
+        let fm = f.eval(m);
+        if fm == 0.0 || fm.abs() <= tol.f_abs || tol.close(b - a, m) {
+            return f.root(m, fm, i, Status::Converged);
+        }
+        if fa.signum() == fm.signum() {
+            a = m;
+            fa = fm;
+        } else {
+            b = m;
+        }
+    }
+    let m = a + (b - a) / 2.0;
+    let fm = f.eval(m);
+    f.root(m, fm, tol.max_iterations, Status::MaxIterations)
+}
+
+/// brent finds a root in `[a, b]`, where `f(a)` and `f(b)` have opposite
+/// signs, with Brent's method: inverse quadratic interpolation or the
+/// secant method where they behave, bisection where they don't. It is as
+/// safe as bisection and usually much faster.
+pub fn brent<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: Tolerance) -> Root {
+    let mut f = Counter::new(f);
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f.eval(a), f.eval(b));
+    if let Some(root) = ends(&f, a, fa, b, fb) {
+        return root;
+    }
+    let (mut c, mut fc) = (b, fb);
+    let (mut d, mut e) = (b - a, b - a);
+    let mut width = (b - a).abs();
+    for i in 1..=tol.max_iterations {
+        if fb.signum() == fc.signum() {
+            // Keep the root between b and c
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol1 = (tol.x_abs + tol.x_rel * b.abs()) / 2.0 + f64::EPSILON * b.abs();
+        let m = (c - b) / 2.0;
+        if fb == 0.0 || fb.abs() <= tol.f_abs || m.abs() <= tol1 {
+            return f.root(b, fb, i, Status::Converged);
+        }
+        // Interpolation can creep up on a multiple root from one side for a
+        // long time, so bisect whenever the bracket hasn't halved in three
+        // steps
+        let mut stalled = false;
+        if i % 3 == 0 {
+            stalled = (c - b).abs() > width / 2.0;
+            width = (c - b).abs();
+        }
+        if !stalled && e.abs() >= tol1 && fa.abs() > fb.abs() {
+            // Try interpolating
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let (q, r) = (fa / fc, fb / fc);
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            if 2.0 * p < (3.0 * m * q - (tol1 * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = d;
+            }
+        } else {
+            d = m;
+            e = d;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(m) };
+        fb = f.eval(b);
+        if !fb.is_finite() {
+            return f.root(a, fa, i, Status::Diverged);
+        }
+    }
+    f.root(b, fb, tol.max_iterations, Status::MaxIterations)
+}
+
+/// newton finds a root near `x0` with Newton's method, using a central
+/// difference for the derivative. Any step which doesn't reduce `|f|` is
+/// halved until it does, so the iteration can't run away. Newton's method
+/// converges fastest of all from a good guess, but may find a different
+/// root, or none, from a bad one; use `newton_bracketed` or `brent` when a
+/// bracket is known.
+///
+/// ```rust
+/// use civil::calc::roots::{newton, Tolerance};
+///
+/// // Colebrook friction factor for Re = 1e5 and relative roughness 1e-4
+/// let (re, rr) = (1.0e5, 1.0e-4);
+/// let colebrook = |f: f64| 1.0 / f.sqrt() + 2.0 * (rr / 3.7 + 2.51 / (re * f.sqrt())).log10();
+/// let root = newton(colebrook, 0.02, Tolerance::default());
+/// assert!(root.converged());
+/// assert!((root.x - 0.018_52).abs() < 1e-5);
+/// ```
+pub fn newton<F: Fn(f64) -> f64>(f: F, x0: f64, tol: Tolerance) -> Root {
+    newton_with(f, |f, x| derivative(|x| f.eval(x), x), x0, tol)
+}
+
+/// newton_with_derivative is `newton` with the derivative supplied, say
+/// from `dual::derivative`, instead of estimated. Each call to `df` counts
+/// as an evaluation.
+///
+/// ```rust
+/// use civil::calc::dual::{derivative, Float};
+/// use civil::calc::roots::{newton_with_derivative, Tolerance};
+///
+/// // Colebrook again, written once for both f64 and Dual
+/// fn colebrook<T: Float>(f: T) -> T {
+///     let (re, rr) = (1.0e5, 1.0e-4);
+///     T::from(1.0) / f.sqrt() + ((T::from(2.51) / (f.sqrt() * re)) + rr / 3.7).log10() * 2.0
+/// }
+/// let df = |x| derivative(colebrook, x);
+/// let root = newton_with_derivative(colebrook, df, 0.02, Tolerance::default());
+/// assert!(root.converged());
+/// assert!((root.x - 0.018_52).abs() < 1e-5);
+/// ```
+pub fn newton_with_derivative<F, D>(f: F, df: D, x0: f64, tol: Tolerance) -> Root
+where
+    F: Fn(f64) -> f64,
+    D: Fn(f64) -> f64,
+{
+    newton_with(
+        f,
+        |f, x| {
+            f.evaluations += 1;
+            df(x)
+        },
+        x0,
+        tol,
+    )
+}
+
+// Newton's method with the derivative at x from `slope`, which may
+// evaluate f through the counter
+fn newton_with<F, D>(f: F, mut slope: D, x0: f64, tol: Tolerance) -> Root
+where
+    F: Fn(f64) -> f64,
+    D: FnMut(&mut Counter<F>, f64) -> f64,
+{
+    let mut f = Counter::new(f);
+    let mut x = x0;
+    let mut fx = f.eval(x);
+    if !fx.is_finite() {
+        return f.root(x, fx, 0, Status::Diverged);
+    }
+    for i in 1..=tol.max_iterations {
+        if fx == 0.0 || fx.abs() <= tol.f_abs {
+            return f.root(x, fx, i - 1, Status::Converged);
+        }
+        let df = slope(&mut f, x);
+        if df == 0.0 || !df.is_finite() {
+            return f.root(x, fx, i, Status::Diverged);
+        }
+        let mut step = fx / df;
+        // Halve the step until it helps
+        let mut halvings = 0;
+        let (next, fnext) = loop {
+            let next = x - step;
+            let fnext = f.eval(next);
+            if fnext.is_finite() && fnext.abs() < fx.abs() {
+                break (next, fnext);
+            }
+            halvings += 1;
+            if halvings > 30 || tol.close(step, x) {
+                break (next, fnext);
+            }
+            step /= 2.0;
+        };
+        if !fnext.is_finite() || (fnext.abs() >= fx.abs() && !tol.close(step, x)) {
+            return f.root(x, fx, i, Status::Diverged);
+        }
+        if tol.close(step, next) {
+            // Keep whichever end of the last step is better
+            if fnext.abs() <= fx.abs() {
+                return f.root(next, fnext, i, Status::Converged);
+            }
+            return f.root(x, fx, i, Status::Converged);
+        }
+        x = next;
+        fx = fnext;
+    }
+    f.root(x, fx, tol.max_iterations, Status::MaxIterations)
+}
+
+/// newton_bracketed finds a root in `[a, b]`, where `f(a)` and `f(b)` have
+/// opposite signs, with Newton's method and a numerical derivative, falling
+/// back to bisection whenever a Newton step would leave the bracket or isn't
+/// shrinking it fast enough.
+pub fn newton_bracketed<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: Tolerance) -> Root {
+    let mut f = Counter::new(f);
+    let (fa, fb) = (f.eval(a), f.eval(b));
+    if let Some(root) = ends(&f, a, fa, b, fb) {
+        return root;
+    }
+    // Orient the bracket so that f(lo) < 0 < f(hi)
+    let (mut lo, mut hi) = if fa < 0.0 { (a, b) } else { (b, a) };
+    let mut x = (a + b) / 2.0;
+    let mut fx = f.eval(x);
+    let mut last = (b - a).abs();
+    let mut step = last;
+    for i in 1..=tol.max_iterations {
+        if fx == 0.0 || fx.abs() <= tol.f_abs {
+            return f.root(x, fx, i, Status::Converged);
+        }
+        let df = derivative(|x| f.eval(x), x);
+        let newton = x - fx / df;
+        let outside = !newton.is_finite() || (newton - lo) * (newton - hi) > 0.0;
+        if outside || (2.0 * fx).abs() > (last * df).abs() {
+            last = step;
+            step = (hi - lo) / 2.0;
+            x = lo + step;
+        } else {
+            last = step;
+            step = fx / df;
+            x = newton;
+        }
+        if tol.close(step, x) {
+            let fx = f.eval(x);
+            return f.root(x, fx, i, Status::Converged);
+        }
+        fx = f.eval(x);
+        if !fx.is_finite() {
+            return f.root(x, fx, i, Status::Diverged);
+        }
+        if fx < 0.0 {
+            lo = x;
+        } else {
+            hi = x;
+        }
+    }
+    f.root(x, fx, tol.max_iterations, Status::MaxIterations)
+}
+
+/// derivative estimates `f'(x)` by a central difference, with a step scaled
+/// to `x` to balance truncation against rounding error.
+pub fn derivative<F: FnMut(f64) -> f64>(mut f: F, x: f64) -> f64 {
+    let h = f64::EPSILON.cbrt() * x.abs().max(1.0);
+    // Make the step exactly representable
+    let (up, down) = (x + h, x - h);
+    (f(up) - f(down)) / (up - down)
+}
+
+// Check the ends of a bracket before searching it
+fn ends<F>(f: &Counter<F>, a: f64, fa: f64, b: f64, fb: f64) -> Option<Root>
+where
+    F: Fn(f64) -> f64,
+{
+    if fa == 0.0 {
+        return Some(f.root(a, fa, 0, Status::Converged));
+    }
+    if fb == 0.0 {
+        return Some(f.root(b, fb, 0, Status::Converged));
+    }
+    if !fa.is_finite() || !fb.is_finite() {
+        return Some(f.root(a, fa, 0, Status::Diverged));
+    }
+    if fa.signum() == fb.signum() {
+        let (x, fx) = if fa.abs() < fb.abs() {
+            (a, fa)
+        } else {
+            (b, fb)
+        };
+        return Some(f.root(x, fx, 0, Status::NoBracket));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bisection, bracket, brent, newton, newton_bracketed, newton_with_derivative, Root, Status,
+        Tolerance,
+    };
+    use crate::calc::dual::{derivative, Dual, Float};
+
+    const PRECISION: f64 = 1.0e-10;
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            f: fn(f64) -> f64,
+            a: f64,
+            b: f64,
+            expected: f64,
+        }
+        let table = [
+            Row {
+                f: |x| x * x - 2.0,
+                a: 0.0,
+                b: 2.0,
+                expected: 2f64.sqrt(),
+            },
+            Row {
+                f: |x| x.cos() - x,
+                a: 0.0,
+                b: 1.0,
+                expected: 0.739_085_133_215_160_6,
+            },
+            Row {
+                f: |x| x.powi(3) - 2.0 * x - 5.0,
+                a: 2.0,
+                b: 3.0,
+                expected: 2.094_551_481_542_326_5,
+            },
+            Row {
+                f: |x| x.exp() - 1.0e4,
+                a: 0.0,
+                b: 20.0,
+                expected: 1.0e4f64.ln(),
+            },
+        ];
+        type Solver = fn(fn(f64) -> f64, f64, f64, Tolerance) -> Root;
+        let solvers: [(&str, Solver); 4] = [
+            ("bisection", |f, a, b, t| bisection(f, a, b, t)),
+            ("brent", |f, a, b, t| brent(f, a, b, t)),
+            ("newton_bracketed", |f, a, b, t| {
+                newton_bracketed(f, a, b, t)
+            }),
+            ("newton", |f, a, b, t| newton(f, (a + b) / 2.0, t)),
+        ];
+        for r in table.iter() {
+            for (name, solve) in solvers.iter() {
+                let root = solve(r.f, r.a, r.b, Tolerance::default());
+                assert!(root.converged(), "{}: {:?}", name, root);
+                assert!(
+                    (root.x - r.expected).abs() < PRECISION,
+                    "{}: {:?}",
+                    name,
+                    root
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn multiple_root() {
+        // A triple root is flat: only about a third of the digits of x show
+        // in f(x). The bracketing methods still close in on it.
+        let f = |x: f64| (x - 1.0).powi(3);
+        let tol = Tolerance::new(1.0e-7, 0.0);
+        for root in [
+            bisection(f, 0.0, 3.0, tol),
+            brent(f, 0.0, 3.0, tol),
+            newton_bracketed(f, 0.0, 3.0, tol),
+        ]
+        .iter()
+        {
+            assert!(root.converged(), "{:?}", root);
+            assert!((root.x - 1.0).abs() < 1.0e-6, "{:?}", root);
+        }
+    }
+
+    #[test]
+    fn brent_is_faster_than_bisection() {
+        let f = |x: f64| x.cos() - x;
+        let slow = bisection(f, 0.0, 1.0, Tolerance::default());
+        let fast = brent(f, 0.0, 1.0, Tolerance::default());
+        assert!(
+            fast.evaluations * 3 < slow.evaluations,
+            "{:?} {:?}",
+            fast,
+            slow
+        );
+    }
+
+    #[test]
+    fn failures() {
+        let f = |x: f64| x * x + 1.0;
+        assert_eq!(
+            brent(f, -1.0, 1.0, Tolerance::default()).status,
+            Status::NoBracket
+        );
+        assert_eq!(
+            bisection(f, -1.0, 1.0, Tolerance::default()).status,
+            Status::NoBracket
+        );
+        assert_eq!(bracket(f, -1.0, 1.0), None);
+        // minimum of x^2 + 1 at zero: nowhere to go
+        assert_eq!(
+            newton(f, 0.0, Tolerance::default()).status,
+            Status::Diverged
+        );
+        let tight = Tolerance {
+            max_iterations: 3,
+            ..Tolerance::default()
+        };
+        let root = bisection(|x| x - 0.3, 0.0, 1.0, tight);
+        assert_eq!(root.status, Status::MaxIterations);
+        assert_eq!(root.iterations, 3);
+        // loose tolerances stop early
+        let loose = Tolerance::new(1.0e-3, 0.0);
+        let root = bisection(|x| x - 0.3, 0.0, 1.0, loose);
+        assert!(root.converged() && root.iterations < 12);
+        assert!((root.x - 0.3).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn newton_is_safeguarded() {
+        // atan overshoots wildly from x = 2 without damping
+        let root = newton(|x| x.atan(), 2.0, Tolerance::default());
+        assert!(root.converged(), "{:?}", root);
+        assert!(root.x.abs() < PRECISION);
+        let (a, b) = bracket(|x| x.atan() - 1.0, 0.0, 0.1).unwrap();
+        let root = newton_bracketed(|x| x.atan() - 1.0, a, b, Tolerance::default());
+        assert!((root.x - 1f64.tan()).abs() < PRECISION, "{:?}", root);
+    }
+
+    #[test]
+    fn exact_derivative() {
+        // an exact derivative costs one evaluation where a difference costs two
+        let f = |x: Dual| x.atan() - 1.0;
+        let value = |x| f(Dual::constant(x)).value;
+        let exact = newton_with_derivative(value, |x| derivative(f, x), 0.5, Tolerance::default());
+        let estimated = newton(value, 0.5, Tolerance::default());
+        assert!(exact.converged() && estimated.converged());
+        assert!((exact.x - 1f64.tan()).abs() < PRECISION, "{:?}", exact);
+        assert!(
+            exact.evaluations < estimated.evaluations,
+            "{:?} {:?}",
+            exact,
+            estimated
+        );
+        // and it is guarded like `newton`
+        let root = newton_with_derivative(
+            |x| x.atan(),
+            |x| 1.0 / (1.0 + x * x),
+            2.0,
+            Tolerance::default(),
+        );
+        assert!(root.converged() && root.x.abs() < PRECISION, "{:?}", root);
+    }
+}
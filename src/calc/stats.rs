@@ -0,0 +1,1146 @@
+//! # stats
+//!
+//! Probability distributions and the statistics used to fit them: flood
+//! frequency analysis, reliability and load factors, Monte Carlo estimates.
+//!
+//! Each distribution implements `Distribution`, with its density, its
+//! cumulative distribution and the inverse of that, the quantile. Each can
+//! be fit to a sample by the method of moments (`fit_moments`) or by
+//! L-moments (`fit_l_moments`), which are less swayed by outliers and are
+//! usually the better choice for short records.
+//!
+//! - `Normal`
+//! - `LogNormal`, normal in the natural logarithm
+//! - `Gumbel`, extreme value type I
+//! - `Gev`, generalized extreme value
+//! - `PearsonIII`, parameterized by mean, standard deviation and skew
+//! - `LogPearsonIII`, Pearson III in the base 10 logarithm, as used by
+//!   Bulletin 17
+//!
+//! `describe` and `l_moments` summarize a sample, and `plotting_positions`
+//! assigns each value an exceedance probability for plotting against a
+//! fitted curve.
+//!
+//! ```rust
+//! use civil::calc::stats::{Distribution, LogPearsonIII};
+//!
+//! // annual peak flows, cfs
+//! let peaks = [
+//!     4_210.0, 2_650.0, 7_930.0, 3_100.0, 5_480.0, 1_920.0, 12_600.0, 3_870.0, 2_440.0, 6_150.0,
+//!     4_780.0, 3_330.0, 9_020.0, 2_980.0, 5_050.0, 1_650.0, 4_460.0, 3_640.0, 7_210.0, 2_870.0,
+//! ];
+//! let lp3 = LogPearsonIII::fit_moments(&peaks).unwrap();
+//! let q100 = lp3.return_level(100.0);
+//! assert!(q100 > lp3.return_level(10.0));
+//! // the 100 year flood is exceeded with probability 0.01 in any year
+//! assert!((lp3.cdf(q100) - 0.99).abs() < 1e-9);
+//! ```
+
+use super::roots::{brent, Tolerance};
+use std::error::Error;
+use std::f64::consts::{LN_2, PI};
+use std::fmt;
+
+/// EULER is the Euler-Mascheroni constant, the mean of the standard Gumbel
+/// distribution.
+pub const EULER: f64 = 0.577_215_664_901_532_9;
+
+/// Distribution is a continuous probability distribution.
+pub trait Distribution {
+    /// pdf is the probability density at `x`.
+    fn pdf(&self, x: f64) -> f64;
+
+    /// cdf is the probability of a value no greater than `x`.
+    fn cdf(&self, x: f64) -> f64;
+
+    /// quantile is the value with non-exceedance probability `p`, the
+    /// inverse of `cdf`. It is NaN unless `0 <= p <= 1`.
+    fn quantile(&self, p: f64) -> f64;
+
+    /// return_level is the value exceeded on average once in `period`
+    /// trials, ie the 100 year flood for annual maxima and a period of 100.
+    fn return_level(&self, period: f64) -> f64 {
+        self.quantile(1.0 - 1.0 / period)
+    }
+}
+
+/// StatsError explains why a distribution could not be fit to a sample.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatsError {
+    /// The sample needs at least `needed` values.
+    TooFew { needed: usize, found: usize },
+    /// A value which needs a logarithm is not positive.
+    NotPositive { index: usize },
+    /// A value is not finite.
+    NotFinite { index: usize },
+    /// No distribution of the family has the skew of the sample.
+    Skew { skew: f64 },
+    /// Every value in the sample is the same, so there is no spread to fit.
+    NoSpread,
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatsError::TooFew { needed, found } => {
+                write!(f, "at least {} values are needed, found {}", needed, found)
+            }
+            StatsError::NotPositive { index } => {
+                write!(f, "value at index {} is not positive", index)
+            }
+            StatsError::NotFinite { index } => write!(f, "value at index {} is not finite", index),
+            StatsError::Skew { skew } => write!(f, "no distribution fits a skew of {}", skew),
+            StatsError::NoSpread => write!(f, "every value is the same"),
+        }
+    }
+}
+
+impl Error for StatsError {}
+
+/// Summary describes a sample.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    /// Sample standard deviation, with n - 1 degrees of freedom
+    pub std_dev: f64,
+    /// Sample skew, adjusted for bias as in Bulletin 17:
+    /// `n Σ(x - mean)³ / ((n - 1)(n - 2) s³)`
+    pub skew: f64,
+    pub min: f64,
+    pub max: f64,
+    pub median: f64,
+}
+
+impl Summary {
+    /// cv is the coefficient of variation, the standard deviation over the
+    /// mean.
+    pub fn cv(&self) -> f64 {
+        self.std_dev / self.mean
+    }
+}
+
+/// describe summarizes a sample of at least three values.
+pub fn describe(data: &[f64]) -> Result<Summary, StatsError> {
+    let sorted = checked(data, 3)?;
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let m2: f64 = data.iter().map(|x| (x - mean).powi(2)).sum();
+    let m3: f64 = data.iter().map(|x| (x - mean).powi(3)).sum();
+    let std_dev = (m2 / (n - 1.0)).sqrt();
+    let skew = if std_dev > 0.0 {
+        n * m3 / ((n - 1.0) * (n - 2.0) * std_dev.powi(3))
+    } else {
+        0.0
+    };
+    let k = sorted.len();
+    let median = if k % 2 == 1 {
+        sorted[k / 2]
+    } else {
+        (sorted[k / 2 - 1] + sorted[k / 2]) / 2.0
+    };
+    Ok(Summary {
+        count: k,
+        mean,
+        std_dev,
+        skew,
+        min: sorted[0],
+        max: sorted[k - 1],
+        median,
+    })
+}
+
+/// LMoments are the first four sample L-moments, as ratios past the
+/// second (Hosking).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LMoments {
+    /// The mean
+    pub l1: f64,
+    /// A measure of scale, half the mean difference between two values
+    pub l2: f64,
+    /// L-skewness, `l3 / l2`
+    pub t3: f64,
+    /// L-kurtosis, `l4 / l2`
+    pub t4: f64,
+}
+
+/// l_moments computes the unbiased sample L-moments of at least four
+/// values from probability weighted moments.
+pub fn l_moments(data: &[f64]) -> Result<LMoments, StatsError> {
+    let x = checked(data, 4)?;
+    let n = x.len() as f64;
+    let mut b = [0.0; 4];
+    for (j, v) in x.iter().enumerate() {
+        let j = j as f64;
+        let w1 = j / (n - 1.0);
+        let w2 = w1 * (j - 1.0) / (n - 2.0);
+        let w3 = w2 * (j - 2.0) / (n - 3.0);
+        b[0] += v;
+        b[1] += w1 * v;
+        b[2] += w2 * v;
+        b[3] += w3 * v;
+    }
+    for b in b.iter_mut() {
+        *b /= n;
+    }
+    let l2 = 2.0 * b[1] - b[0];
+    if l2 <= 0.0 {
+        return Err(StatsError::NoSpread);
+    }
+    let l3 = 6.0 * b[2] - 6.0 * b[1] + b[0];
+    let l4 = 20.0 * b[3] - 30.0 * b[2] + 12.0 * b[1] - b[0];
+    Ok(LMoments {
+        l1: b[0],
+        l2,
+        t3: l3 / l2,
+        t4: l4 / l2,
+    })
+}
+
+/// PlottingPosition is a formula for the exceedance probability of the
+/// value of a given rank, `(rank - a) / (n + 1 - 2a)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlottingPosition {
+    /// a = 0, unbiased exceedance probabilities for any distribution
+    Weibull,
+    /// a = 0.44, nearly unbiased quantiles for Gumbel and GEV
+    Gringorten,
+    /// a = 0.4, a compromise suited to most distributions
+    Cunnane,
+    /// a = 0.5
+    Hazen,
+}
+
+impl PlottingPosition {
+    /// exceedance is the probability assigned to the value of `rank` in a
+    /// sample of `n`, where the largest value has rank 1.
+    pub fn exceedance(self, rank: usize, n: usize) -> f64 {
+        let a = match self {
+            PlottingPosition::Weibull => 0.0,
+            PlottingPosition::Gringorten => 0.44,
+            PlottingPosition::Cunnane => 0.4,
+            PlottingPosition::Hazen => 0.5,
+        };
+        (rank as f64 - a) / (n as f64 + 1.0 - 2.0 * a)
+    }
+}
+
+/// plotting_positions ranks a sample, largest first, and pairs each value
+/// with its exceedance probability. The return period is its reciprocal.
+///
+/// ```rust
+/// use civil::calc::stats::{plotting_positions, PlottingPosition};
+///
+/// let p = plotting_positions(&[3.0, 9.0, 5.0, 1.0], PlottingPosition::Weibull);
+/// assert_eq!(p[0], (9.0, 0.2));
+/// assert_eq!(p[3], (1.0, 0.8));
+/// ```
+pub fn plotting_positions(data: &[f64], method: PlottingPosition) -> Vec<(f64, f64)> {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    let n = sorted.len();
+    sorted
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| (x, method.exceedance(i + 1, n)))
+        .collect()
+}
+
+/// Normal is the normal, or Gaussian, distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normal {
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl Normal {
+    pub fn new(mean: f64, std_dev: f64) -> Normal {
+        Normal { mean, std_dev }
+    }
+
+    /// standard is the normal distribution with mean 0 and standard
+    /// deviation 1.
+    pub fn standard() -> Normal {
+        Normal::new(0.0, 1.0)
+    }
+
+    /// fit_moments matches the sample mean and standard deviation.
+    pub fn fit_moments(data: &[f64]) -> Result<Normal, StatsError> {
+        let s = describe(data)?;
+        spread(s.std_dev)?;
+        Ok(Normal::new(s.mean, s.std_dev))
+    }
+
+    /// fit_l_moments matches the first two sample L-moments.
+    pub fn fit_l_moments(data: &[f64]) -> Result<Normal, StatsError> {
+        let l = l_moments(data)?;
+        Ok(Normal::new(l.l1, l.l2 * PI.sqrt()))
+    }
+}
+
+impl Distribution for Normal {
+    fn pdf(&self, x: f64) -> f64 {
+        let z = (x - self.mean) / self.std_dev;
+        (-z * z / 2.0).exp() / (self.std_dev * (2.0 * PI).sqrt())
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        phi((x - self.mean) / self.std_dev)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        self.mean + self.std_dev * phi_inverse(p)
+    }
+}
+
+/// LogNormal is the distribution of `x` when `ln x` is normal with mean
+/// `mu` and standard deviation `sigma`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogNormal {
+    pub mu: f64,
+    pub sigma: f64,
+}
+
+impl LogNormal {
+    pub fn new(mu: f64, sigma: f64) -> LogNormal {
+        LogNormal { mu, sigma }
+    }
+
+    /// fit_moments matches the mean and standard deviation of the
+    /// logarithms of the sample.
+    pub fn fit_moments(data: &[f64]) -> Result<LogNormal, StatsError> {
+        let n = Normal::fit_moments(&logarithms(data, f64::ln)?)?;
+        Ok(LogNormal::new(n.mean, n.std_dev))
+    }
+
+    /// fit_l_moments matches the first two L-moments of the logarithms of
+    /// the sample.
+    pub fn fit_l_moments(data: &[f64]) -> Result<LogNormal, StatsError> {
+        let n = Normal::fit_l_moments(&logarithms(data, f64::ln)?)?;
+        Ok(LogNormal::new(n.mean, n.std_dev))
+    }
+
+    /// mean is the mean of the distribution itself, not of its logarithm.
+    pub fn mean(&self) -> f64 {
+        (self.mu + self.sigma * self.sigma / 2.0).exp()
+    }
+}
+
+impl Distribution for LogNormal {
+    fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        Normal::new(self.mu, self.sigma).pdf(x.ln()) / x
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        phi((x.ln() - self.mu) / self.sigma)
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        Normal::new(self.mu, self.sigma).quantile(p).exp()
+    }
+}
+
+/// Gumbel is the extreme value type I distribution, the limit of the
+/// largest of many values with an exponential tail:
+/// `F(x) = exp(-exp(-(x - location) / scale))`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gumbel {
+    pub location: f64,
+    pub scale: f64,
+}
+
+impl Gumbel {
+    pub fn new(location: f64, scale: f64) -> Gumbel {
+        Gumbel { location, scale }
+    }
+
+    /// fit_moments matches the sample mean and standard deviation.
+    pub fn fit_moments(data: &[f64]) -> Result<Gumbel, StatsError> {
+        let s = describe(data)?;
+        spread(s.std_dev)?;
+        let scale = s.std_dev * 6f64.sqrt() / PI;
+        Ok(Gumbel::new(s.mean - EULER * scale, scale))
+    }
+
+    /// fit_l_moments matches the first two sample L-moments.
+    pub fn fit_l_moments(data: &[f64]) -> Result<Gumbel, StatsError> {
+        let l = l_moments(data)?;
+        let scale = l.l2 / LN_2;
+        Ok(Gumbel::new(l.l1 - EULER * scale, scale))
+    }
+}
+
+impl Distribution for Gumbel {
+    fn pdf(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+        (-z - (-z).exp()).exp() / self.scale
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        (-(-(x - self.location) / self.scale).exp()).exp()
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        self.location - self.scale * (-p.ln()).ln()
+    }
+}
+
+/// Gev is the generalized extreme value distribution, in Hosking's form:
+/// `F(x) = exp(-(1 - shape (x - location) / scale)^(1 / shape))`. A
+/// positive shape has an upper bound, a negative shape a heavy upper tail,
+/// and a zero shape is `Gumbel`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gev {
+    pub location: f64,
+    pub scale: f64,
+    pub shape: f64,
+}
+
+// Shapes closer to zero than this are treated as Gumbel
+const GUMBEL_SHAPE: f64 = 1.0e-9;
+
+impl Gev {
+    pub fn new(location: f64, scale: f64, shape: f64) -> Gev {
+        Gev {
+            location,
+            scale,
+            shape,
+        }
+    }
+
+    /// fit_moments matches the sample mean, standard deviation and skew.
+    /// The skew must be more than -2, and less than about 50 since the
+    /// third moment must exist (shape > -1/3).
+    pub fn fit_moments(data: &[f64]) -> Result<Gev, StatsError> {
+        let s = describe(data)?;
+        spread(s.std_dev)?;
+        let (low, high) = (-1.0 / 3.0 + 1.0e-3, 1.0);
+        if s.skew >= gev_skew(low) || s.skew <= gev_skew(high) {
+            return Err(StatsError::Skew { skew: s.skew });
+        }
+        let k = brent(|k| gev_skew(k) - s.skew, low, high, Tolerance::default()).x;
+        if k.abs() < GUMBEL_SHAPE {
+            let g = Gumbel::fit_moments(data)?;
+            return Ok(Gev::new(g.location, g.scale, 0.0));
+        }
+        let (g1, g2) = (gamma(1.0 + k), gamma(1.0 + 2.0 * k));
+        let scale = s.std_dev * k.abs() / (g2 - g1 * g1).sqrt();
+        Ok(Gev::new(s.mean - scale * (1.0 - g1) / k, scale, k))
+    }
+
+    /// fit_l_moments matches the first three sample L-moments, with
+    /// Hosking's approximation for the shape, which is accurate for
+    /// `-0.5 < t3 < 0.5`.
+    pub fn fit_l_moments(data: &[f64]) -> Result<Gev, StatsError> {
+        let l = l_moments(data)?;
+        let c = 2.0 / (3.0 + l.t3) - LN_2 / 3f64.ln();
+        let k = 7.8590 * c + 2.9554 * c * c;
+        if k <= -1.0 {
+            return Err(StatsError::Skew { skew: l.t3 });
+        }
+        if k.abs() < GUMBEL_SHAPE {
+            let g = Gumbel::fit_l_moments(data)?;
+            return Ok(Gev::new(g.location, g.scale, 0.0));
+        }
+        let g1 = gamma(1.0 + k);
+        let scale = l.l2 * k / ((1.0 - 2f64.powf(-k)) * g1);
+        Ok(Gev::new(l.l1 - scale * (1.0 - g1) / k, scale, k))
+    }
+
+    // The reduced variate, -ln(-ln F), or None outside the support
+    fn reduced(&self, x: f64) -> Option<f64> {
+        let z = (x - self.location) / self.scale;
+        if self.shape.abs() < GUMBEL_SHAPE {
+            return Some(z);
+        }
+        let arg = 1.0 - self.shape * z;
+        if arg <= 0.0 {
+            None
+        } else {
+            Some(-arg.ln() / self.shape)
+        }
+    }
+}
+
+// Skew of a GEV as a function of its shape; the limit at zero is Gumbel's
+fn gev_skew(k: f64) -> f64 {
+    const NEAR: f64 = 1.0e-3;
+    if k.abs() < NEAR {
+        // Avoid the cancellation near zero
+        let (a, b) = (gev_skew(-NEAR), gev_skew(NEAR));
+        return a + (b - a) * (k + NEAR) / (2.0 * NEAR);
+    }
+    let (g1, g2, g3) = (gamma(1.0 + k), gamma(1.0 + 2.0 * k), gamma(1.0 + 3.0 * k));
+    k.signum() * (-g3 + 3.0 * g1 * g2 - 2.0 * g1.powi(3)) / (g2 - g1 * g1).powf(1.5)
+}
+
+impl Distribution for Gev {
+    fn pdf(&self, x: f64) -> f64 {
+        match self.reduced(x) {
+            Some(y) => (-(1.0 - self.shape) * y - (-y).exp()).exp() / self.scale,
+            None => 0.0,
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        match self.reduced(x) {
+            Some(y) => (-(-y).exp()).exp(),
+            // Beyond the upper bound for a positive shape, below the lower
+            // bound for a negative one
+            None if self.shape > 0.0 => 1.0,
+            None => 0.0,
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        let y = -p.ln();
+        if self.shape.abs() < GUMBEL_SHAPE {
+            return self.location - self.scale * y.ln();
+        }
+        self.location + self.scale * (1.0 - y.powf(self.shape)) / self.shape
+    }
+}
+
+/// PearsonIII is the Pearson type III distribution, a gamma distribution
+/// shifted and scaled to have the given mean, standard deviation and skew.
+/// A negative skew reflects it, giving an upper bound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PearsonIII {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub skew: f64,
+}
+
+// Skews closer to zero than this use the Wilson-Hilferty transformation of
+// the normal distribution, which is exact in the limit, rather than a gamma
+// distribution with an enormous shape
+const SMALL_SKEW: f64 = 0.01;
+
+impl PearsonIII {
+    pub fn new(mean: f64, std_dev: f64, skew: f64) -> PearsonIII {
+        PearsonIII {
+            mean,
+            std_dev,
+            skew,
+        }
+    }
+
+    /// fit_moments matches the sample mean, standard deviation and skew.
+    pub fn fit_moments(data: &[f64]) -> Result<PearsonIII, StatsError> {
+        let s = describe(data)?;
+        spread(s.std_dev)?;
+        Ok(PearsonIII::new(s.mean, s.std_dev, s.skew))
+    }
+
+    /// fit_l_moments matches the first three sample L-moments, with
+    /// Hosking's rational approximation for the skew.
+    pub fn fit_l_moments(data: &[f64]) -> Result<PearsonIII, StatsError> {
+        let l = l_moments(data)?;
+        let t = l.t3.abs();
+        if t >= 1.0 {
+            return Err(StatsError::Skew { skew: l.t3 });
+        }
+        if t < 1.0e-6 {
+            return Ok(PearsonIII::new(l.l1, l.l2 * PI.sqrt(), 0.0));
+        }
+        // The shape of the underlying gamma distribution
+        let alpha = if t < 1.0 / 3.0 {
+            let z = 3.0 * PI * t * t;
+            (1.0 + 0.2906 * z) / (z + 0.1882 * z * z + 0.0442 * z.powi(3))
+        } else {
+            let z = 1.0 - t;
+            (0.36067 * z - 0.59567 * z * z + 0.25361 * z.powi(3))
+                / (1.0 - 2.78861 * z + 2.56096 * z * z - 0.77045 * z.powi(3))
+        };
+        let skew = 2.0 / alpha.sqrt() * l.t3.signum();
+        let std_dev =
+            l.l2 * PI.sqrt() * alpha.sqrt() * (ln_gamma(alpha) - ln_gamma(alpha + 0.5)).exp();
+        Ok(PearsonIII::new(l.l1, std_dev, skew))
+    }
+
+    /// frequency_factor is the number of standard deviations from the mean
+    /// to the quantile `p`, the K of Bulletin 17 tables.
+    pub fn frequency_factor(&self, p: f64) -> f64 {
+        if self.skew.abs() < SMALL_SKEW {
+            return wilson_hilferty(phi_inverse(p), self.skew);
+        }
+        let (alpha, _, _) = self.gamma();
+        let y = gamma_p_inverse(alpha, if self.skew > 0.0 { p } else { 1.0 - p });
+        // y is (x - location) / scale, and x = mean + K std_dev
+        self.skew / 2.0 * y - 2.0 / self.skew
+    }
+
+    // Shape, scale and location of the underlying gamma distribution
+    fn gamma(&self) -> (f64, f64, f64) {
+        let g = self.skew;
+        let alpha = 4.0 / (g * g);
+        let scale = self.std_dev * g / 2.0;
+        (alpha, scale, self.mean - 2.0 * self.std_dev / g)
+    }
+}
+
+// The Wilson-Hilferty frequency factor for a standard normal deviate
+fn wilson_hilferty(z: f64, g: f64) -> f64 {
+    if g == 0.0 {
+        return z;
+    }
+    2.0 / g * ((1.0 + g * z / 6.0 - g * g / 36.0).powi(3) - 1.0)
+}
+
+impl Distribution for PearsonIII {
+    fn pdf(&self, x: f64) -> f64 {
+        let k = (x - self.mean) / self.std_dev;
+        let g = self.skew;
+        if g.abs() < SMALL_SKEW {
+            if g == 0.0 {
+                return Normal::standard().pdf(k) / self.std_dev;
+            }
+            let w = 1.0 + g * k / 2.0;
+            if w <= 0.0 {
+                return 0.0;
+            }
+            let z = 6.0 / g * (w.cbrt() - 1.0) + g / 6.0;
+            return Normal::standard().pdf(z) / (w.cbrt().powi(2) * self.std_dev);
+        }
+        let (alpha, scale, location) = self.gamma();
+        let y = (x - location) / scale;
+        if y <= 0.0 {
+            return 0.0;
+        }
+        ((alpha - 1.0) * y.ln() - y - ln_gamma(alpha)).exp() / scale.abs()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        let k = (x - self.mean) / self.std_dev;
+        let g = self.skew;
+        if g.abs() < SMALL_SKEW {
+            if g == 0.0 {
+                return phi(k);
+            }
+            let z = 6.0 / g * ((1.0 + g * k / 2.0).cbrt() - 1.0) + g / 6.0;
+            return phi(z);
+        }
+        let (alpha, scale, location) = self.gamma();
+        let y = (x - location) / scale;
+        if g > 0.0 {
+            gamma_p(alpha, y)
+        } else {
+            gamma_q(alpha, y)
+        }
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        self.mean + self.frequency_factor(p) * self.std_dev
+    }
+}
+
+/// LogPearsonIII is the distribution of `x` when `log10 x` has a Pearson
+/// type III distribution with the given mean, standard deviation and skew.
+/// It is the distribution of Bulletin 17 for annual flood peaks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogPearsonIII {
+    /// Distribution of the base 10 logarithm
+    pub log: PearsonIII,
+}
+
+impl LogPearsonIII {
+    pub fn new(mean: f64, std_dev: f64, skew: f64) -> LogPearsonIII {
+        LogPearsonIII {
+            log: PearsonIII::new(mean, std_dev, skew),
+        }
+    }
+
+    /// fit_moments matches the mean, standard deviation and skew of the
+    /// logarithms of the sample.
+    pub fn fit_moments(data: &[f64]) -> Result<LogPearsonIII, StatsError> {
+        let log = PearsonIII::fit_moments(&logarithms(data, f64::log10)?)?;
+        Ok(LogPearsonIII { log })
+    }
+
+    /// fit_l_moments matches the first three L-moments of the logarithms
+    /// of the sample.
+    pub fn fit_l_moments(data: &[f64]) -> Result<LogPearsonIII, StatsError> {
+        let log = PearsonIII::fit_l_moments(&logarithms(data, f64::log10)?)?;
+        Ok(LogPearsonIII { log })
+    }
+}
+
+impl Distribution for LogPearsonIII {
+    fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        self.log.pdf(x.log10()) / (x * std::f64::consts::LN_10)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        self.log.cdf(x.log10())
+    }
+
+    fn quantile(&self, p: f64) -> f64 {
+        10f64.powf(self.log.quantile(p))
+    }
+}
+
+// A sorted copy of a sample, checking its length and values
+fn checked(data: &[f64], needed: usize) -> Result<Vec<f64>, StatsError> {
+    if data.len() < needed {
+        return Err(StatsError::TooFew {
+            needed,
+            found: data.len(),
+        });
+    }
+    if let Some(index) = data.iter().position(|x| !x.is_finite()) {
+        return Err(StatsError::NotFinite { index });
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    Ok(sorted)
+}
+
+fn spread(std_dev: f64) -> Result<(), StatsError> {
+    if std_dev > 0.0 {
+        Ok(())
+    } else {
+        Err(StatsError::NoSpread)
+    }
+}
+
+fn logarithms(data: &[f64], log: fn(f64) -> f64) -> Result<Vec<f64>, StatsError> {
+    if let Some(index) = data.iter().position(|x| *x <= 0.0) {
+        return Err(StatsError::NotPositive { index });
+    }
+    Ok(data.iter().map(|x| log(*x)).collect())
+}
+
+// Standard normal cumulative distribution, from the incomplete gamma
+// function so that the tails keep their relative accuracy
+fn phi(z: f64) -> f64 {
+    let q = gamma_q(0.5, z * z / 2.0) / 2.0;
+    if z < 0.0 {
+        q
+    } else {
+        1.0 - q
+    }
+}
+
+// Standard normal quantile: Acklam's approximation, polished with a step
+// of Halley's method
+fn phi_inverse(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.024_25;
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1.0 {
+        return f64::INFINITY;
+    }
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    let x = if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    };
+    let e = phi(x) - p;
+    let u = e * (2.0 * PI).sqrt() * (x * x / 2.0).exp();
+    x - u / (1.0 + x * u / 2.0)
+}
+
+// Lanczos approximation to ln Γ(x), g = 7
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const G: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection
+        return (PI / (PI * x).sin()).abs().ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let a = G[1..]
+        .iter()
+        .enumerate()
+        .fold(G[0], |a, (i, g)| a + g / (x + i as f64 + 1.0));
+    let t = x + 7.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
+
+// Γ(x) for positive x
+pub(crate) fn gamma(x: f64) -> f64 {
+    ln_gamma(x).exp()
+}
+
+// Iterations allowed for the incomplete gamma series and continued fraction;
+// near x = a the series needs about 8 sqrt(a) terms, so this covers shapes up
+// to roughly 1e8
+const GAMMA_ITERATIONS: usize = 100_000;
+
+// Regularized lower incomplete gamma function P(a, x)
+fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        0.0
+    } else if x == f64::INFINITY {
+        1.0
+    } else if x < a + 1.0 {
+        gamma_series(a, x)
+    } else {
+        1.0 - gamma_fraction(a, x)
+    }
+}
+
+// Regularized upper incomplete gamma function Q(a, x) = 1 - P(a, x)
+fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        1.0
+    } else if x == f64::INFINITY {
+        0.0
+    } else if x < a + 1.0 {
+        1.0 - gamma_series(a, x)
+    } else {
+        gamma_fraction(a, x)
+    }
+}
+
+// The series for P(a, x), converging quickly for x < a + 1
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let (mut ap, mut term) = (a, 1.0 / a);
+    let mut sum = term;
+    for _ in 0..GAMMA_ITERATIONS {
+        ap += 1.0;
+        term *= x / ap;
+        sum += term;
+        if term.abs() < sum.abs() * f64::EPSILON {
+            break;
+        }
+    }
+    sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+// The continued fraction for Q(a, x) by Lentz's method, converging
+// quickly for x > a + 1
+fn gamma_fraction(a: f64, x: f64) -> f64 {
+    const TINY: f64 = 1.0e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..GAMMA_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < f64::EPSILON {
+            break;
+        }
+    }
+    (-x + a * x.ln() - ln_gamma(a)).exp() * h
+}
+
+// The y with P(a, y) = p
+fn gamma_p_inverse(a: f64, p: f64) -> f64 {
+    if p <= 0.0 {
+        return 0.0;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let mut high = a.max(1.0);
+    while gamma_p(a, high) < p {
+        high *= 2.0;
+    }
+    let tol = Tolerance::new(f64::MIN_POSITIVE, 4.0 * f64::EPSILON);
+    brent(|y| gamma_p(a, y) - p, 0.0, high, tol).x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::calculus::quadrature;
+
+    #[test]
+    fn special_functions() {
+        assert!((gamma(5.0) - 24.0).abs() < 1e-12);
+        assert!((gamma(0.5) - PI.sqrt()).abs() < 1e-14);
+        assert!((ln_gamma(100.0) - 359.134_205_369_575_4).abs() < 1e-10);
+        let n = Normal::standard();
+        assert!((n.cdf(1.96) - 0.975_002_104_851_779_5).abs() < 1e-15);
+        assert!((n.cdf(-5.0) - 2.866_515_718_791_939e-7).abs() < 1e-20);
+        assert!((n.quantile(0.975) - 1.959_963_984_540_054).abs() < 1e-14);
+        assert!((n.quantile(1e-10) + 6.361_340_902_404_056).abs() < 1e-9);
+        assert_eq!(n.quantile(0.5), 0.0);
+        assert!(n.quantile(1.5).is_nan());
+    }
+
+    #[test]
+    fn table_test() {
+        struct Row {
+            d: Box<dyn Distribution>,
+        }
+        let table = [
+            Row {
+                d: Box::new(Normal::new(10.0, 2.0)),
+            },
+            Row {
+                d: Box::new(LogNormal::new(1.0, 0.5)),
+            },
+            Row {
+                d: Box::new(Gumbel::new(100.0, 25.0)),
+            },
+            Row {
+                d: Box::new(Gev::new(100.0, 25.0, 0.2)),
+            },
+            Row {
+                d: Box::new(Gev::new(100.0, 25.0, -0.2)),
+            },
+            Row {
+                d: Box::new(PearsonIII::new(50.0, 10.0, 1.2)),
+            },
+            Row {
+                d: Box::new(PearsonIII::new(50.0, 10.0, -0.7)),
+            },
+            Row {
+                d: Box::new(PearsonIII::new(50.0, 10.0, 0.005)),
+            },
+            Row {
+                d: Box::new(LogPearsonIII::new(3.5, 0.25, 0.3)),
+            },
+        ];
+        for (i, r) in table.iter().enumerate() {
+            for p in [0.001, 0.1, 0.5, 0.9, 0.99, 0.999].iter() {
+                let x = r.d.quantile(*p);
+                assert!((r.d.cdf(x) - p).abs() < 1e-10, "{} {} {}", i, p, x);
+            }
+            // the density integrates to the distribution
+            let (a, b) = (r.d.quantile(0.05), r.d.quantile(0.95));
+            let area = quadrature(|x| r.d.pdf(x), a, b, 1e-12).value;
+            assert!((area - 0.9).abs() < 1e-8, "{} {}", i, area);
+            assert_eq!(r.d.cdf(f64::NEG_INFINITY), 0.0, "{}", i);
+            assert_eq!(r.d.cdf(f64::INFINITY), 1.0, "{}", i);
+        }
+    }
+
+    #[test]
+    fn frequency_factors() {
+        // Bulletin 17B, appendix 3
+        struct Row {
+            skew: f64,
+            p: f64,
+            k: f64,
+        }
+        let table = [
+            Row {
+                skew: 0.0,
+                p: 0.99,
+                k: 2.32635,
+            },
+            Row {
+                skew: 1.0,
+                p: 0.99,
+                k: 3.02256,
+            },
+            Row {
+                skew: 0.5,
+                p: 0.99,
+                k: 2.68572,
+            },
+            Row {
+                skew: -0.5,
+                p: 0.99,
+                k: 1.95472,
+            },
+            Row {
+                skew: 1.0,
+                p: 0.5,
+                k: -0.16397,
+            },
+            Row {
+                skew: -1.0,
+                p: 0.1,
+                k: -1.34039,
+            },
+            Row {
+                skew: 2.0,
+                p: 0.998,
+                k: 5.21461,
+            },
+        ];
+        for r in table.iter() {
+            let k = PearsonIII::new(0.0, 1.0, r.skew).frequency_factor(r.p);
+            assert!((k - r.k).abs() < 1e-5, "{} {} {}", r.skew, r.p, k);
+        }
+    }
+
+    #[test]
+    fn fitting() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let s = describe(&data).unwrap();
+        assert_eq!((s.count, s.min, s.max, s.median), (8, 2.0, 9.0, 4.5));
+        assert!((s.mean - 5.0).abs() < 1e-12);
+        assert!((s.std_dev - (32f64 / 7.0).sqrt()).abs() < 1e-12);
+        let l = l_moments(&data).unwrap();
+        assert!((l.l1 - 5.0).abs() < 1e-12);
+        // half the mean absolute difference between pairs
+        let mut diff = 0.0;
+        for a in data.iter() {
+            for b in data.iter() {
+                diff += (a - b).abs();
+            }
+        }
+        let pairs = (data.len() * (data.len() - 1)) as f64;
+        assert!((l.l2 - diff / pairs / 2.0).abs() < 1e-12);
+        let g = Gumbel::fit_moments(&data).unwrap();
+        assert!((g.scale - s.std_dev * 6f64.sqrt() / PI).abs() < 1e-12);
+
+        // Samples spread evenly through known distributions are fit closely
+        let probabilities: Vec<f64> = (1..=2000).map(|i| (i as f64 - 0.35) / 2000.0).collect();
+        let gev = Gev::new(100.0, 25.0, -0.1);
+        let sample: Vec<f64> = probabilities.iter().map(|p| gev.quantile(*p)).collect();
+        for fit in [
+            Gev::fit_l_moments(&sample).unwrap(),
+            Gev::fit_moments(&sample).unwrap(),
+        ]
+        .iter()
+        {
+            assert!((fit.location - 100.0).abs() < 0.5, "{:?}", fit);
+            assert!((fit.scale - 25.0).abs() < 0.5, "{:?}", fit);
+            assert!((fit.shape + 0.1).abs() < 0.01, "{:?}", fit);
+        }
+        let p3 = PearsonIII::new(50.0, 10.0, 0.8);
+        let sample: Vec<f64> = probabilities.iter().map(|p| p3.quantile(*p)).collect();
+        for fit in [
+            PearsonIII::fit_l_moments(&sample).unwrap(),
+            PearsonIII::fit_moments(&sample).unwrap(),
+        ]
+        .iter()
+        {
+            assert!((fit.mean - 50.0).abs() < 0.01, "{:?}", fit);
+            assert!((fit.std_dev - 10.0).abs() < 0.1, "{:?}", fit);
+            assert!((fit.skew - 0.8).abs() < 0.05, "{:?}", fit);
+        }
+        let ln = LogNormal::new(2.0, 0.3);
+        let sample: Vec<f64> = probabilities.iter().map(|p| ln.quantile(*p)).collect();
+        let fit = LogNormal::fit_l_moments(&sample).unwrap();
+        assert!((fit.mu - 2.0).abs() < 1e-3 && (fit.sigma - 0.3).abs() < 1e-3);
+    }
+
+    #[test]
+    fn plotting() {
+        let data = [10.0, 30.0, 20.0];
+        let p = plotting_positions(&data, PlottingPosition::Gringorten);
+        assert_eq!(
+            p.iter().map(|(x, _)| *x).collect::<Vec<_>>(),
+            vec![30.0, 20.0, 10.0]
+        );
+        assert!((p[0].1 - 0.56 / 3.12).abs() < 1e-12);
+        assert!((PlottingPosition::Hazen.exceedance(1, 10) - 0.05).abs() < 1e-12);
+        assert!((PlottingPosition::Cunnane.exceedance(2, 10) - 1.6 / 10.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn failures() {
+        assert_eq!(
+            describe(&[1.0, 2.0]),
+            Err(StatsError::TooFew {
+                needed: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            l_moments(&[1.0, 2.0, 3.0]),
+            Err(StatsError::TooFew {
+                needed: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            LogPearsonIII::fit_moments(&[1.0, 2.0, 0.0, 3.0]),
+            Err(StatsError::NotPositive { index: 2 })
+        );
+        assert_eq!(
+            Normal::fit_moments(&[1.0, f64::NAN, 2.0]),
+            Err(StatsError::NotFinite { index: 1 })
+        );
+        assert_eq!(Gumbel::fit_l_moments(&[4.0; 5]), Err(StatsError::NoSpread));
+        // A sample skewed further to the left than any GEV
+        let skewed = [
+            100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 0.0,
+        ];
+        assert!(matches!(
+            Gev::fit_moments(&skewed),
+            Err(StatsError::Skew { .. })
+        ));
+    }
+}
@@ -21,16 +21,32 @@
 //! - R = outer radius & r = inner radius (used interchangeably with H & h where practical)
 //! - D = deflection
 //!
+//! ## Sensitivity: ##
+//! The sections are generic over `calc::dual::Float`, so building one from
+//! `Dual` dimensions gives the derivative of every property along with it:
+//!
+//! ```rust
+//! use civil::calc::dual::Dual;
+//! use civil::structural::beams::{Beam, CircularBeam};
+//!
+//! // how fast does S = πR³/4 grow with the radius?
+//! let beam = CircularBeam::new(Dual::variable(0.1));
+//! let s = beam.section_modulus();
+//! assert!((s.derivative - 0.75 * std::f64::consts::PI * 0.01).abs() < 1e-15);
+//! ```
+//!
+
+use crate::calc::dual::Float;
 
 const PI: f64 = std::f64::consts::PI;
 
 /// Beam provides a common interface to the formulas used for determining the
 /// properties of beams which vary with a particular beam cross section.
-pub trait Beam {
-    fn area(&self) -> f64;
-    fn moment_of_inertia(&self) -> f64;
-    fn section_modulus(&self) -> f64;
-    fn radius_of_gyration(&self) -> f64 {
+pub trait Beam<T: Float = f64> {
+    fn area(&self) -> T;
+    fn moment_of_inertia(&self) -> T;
+    fn section_modulus(&self) -> T;
+    fn radius_of_gyration(&self) -> T {
         (self.moment_of_inertia() / self.area()).powf(0.5)
     }
     // More to come . . .
@@ -42,48 +58,48 @@ pub trait Beam {
 /// Gere, James M., "Mechanics of Materials," 6th Ed.
 #[derive(Debug)]
 #[allow(non_snake_case)]
-pub struct PolygonalBeam {
-    pub R: f64,         // Circumscribed radius
-    pub r: f64,         // Inscribed radius (apothem)
+pub struct PolygonalBeam<T = f64> {
+    pub R: T,           // Circumscribed radius
+    pub r: T,           // Inscribed radius (apothem)
     pub sides: i32,     // Number of sides
-    pub side_len: f64,  // Side length
-    pub A: f64,         // Area
-    pub I: f64,         // Moment of Inertia
-    pub S: f64,         // Section Modulus
-    pub k: f64,         // Radius of gyration
+    pub side_len: T,    // Side length
+    pub A: T,           // Area
+    pub I: T,           // Moment of Inertia
+    pub S: T,           // Section Modulus
+    pub k: T,           // Radius of gyration
 
 }
 
 
-impl PolygonalBeam {
+impl<T: Float> PolygonalBeam<T> {
     /// new creates a new polygonal beam. Arguments 
-    pub fn new(side_len: f64, sides: i32) -> PolygonalBeam {
+    pub fn new(side_len: T, sides: i32) -> PolygonalBeam<T> {
         let mut pg = PolygonalBeam {
             R: side_len / 2.0 / (PI / f64::from(sides)).sin(),
             r: side_len / 2.0 / (PI / f64::from(sides)).tan(),
-            sides: sides,
-            side_len: side_len,
-            A: 0.0,
-            I: 0.0,
-            S: 0.0,
-            k: 0.0
+            sides,
+            side_len,
+            A: T::from(0.0),
+            I: T::from(0.0),
+            S: T::from(0.0),
+            k: T::from(0.0),
         };
         pg.A = pg.area();
         pg.I = pg.moment_of_inertia();
         pg.S = pg.section_modulus();
         pg.k = pg.radius_of_gyration();
-        return pg;
+        pg
     }
 }
 
-impl Beam for PolygonalBeam {
-    fn area(&self) -> f64 {
-        f64::from(self.sides) * self.side_len * self.r / 2.0
+impl<T: Float> Beam<T> for PolygonalBeam<T> {
+    fn area(&self) -> T {
+        self.side_len * self.r * f64::from(self.sides) / 2.0
     }
-    fn moment_of_inertia(&self) -> f64 {
-        self.area() / 24.0 * (6.0 * self.R.powi(2) - self.side_len.powi(2))
+    fn moment_of_inertia(&self) -> T {
+        self.area() / 24.0 * (self.R.powi(2) * 6.0 - self.side_len.powi(2))
     }
-    fn section_modulus(&self) -> f64 {
+    fn section_modulus(&self) -> T {
         self.moment_of_inertia() / self.r
     }
 }
@@ -94,54 +110,54 @@ impl Beam for PolygonalBeam {
 /// Gere, James M., "Mechanics of Materials," 6th Ed.
 #[derive(Debug)]
 #[allow(non_snake_case)]
-pub struct TrapezoidalBeam {
-    pub B: f64,         // Major width
-    pub b: f64,         // Minor width
-    dlen: f64,          // Difference between B and b
-    pub H: f64,         // Height
-    pub A: f64,         // Area
-    pub I: f64,         // Moment of Inertia
-    pub S: f64,         // Section Modulus
-    pub k: f64,         // Radius of gyration
+pub struct TrapezoidalBeam<T = f64> {
+    pub B: T,           // Major width
+    pub b: T,           // Minor width
+    dlen: T,            // Difference between B and b
+    pub H: T,           // Height
+    pub A: T,           // Area
+    pub I: T,           // Moment of Inertia
+    pub S: T,           // Section Modulus
+    pub k: T,           // Radius of gyration
 }
 
 
-impl TrapezoidalBeam {
-    pub fn new(minor: f64, major: f64, height: f64) -> TrapezoidalBeam {
+impl<T: Float> TrapezoidalBeam<T> {
+    pub fn new(minor: T, major: T, height: T) -> TrapezoidalBeam<T> {
         let mut tb = TrapezoidalBeam {
             B: major,
             b: minor,
             dlen: major - minor,
             H: height,
-            A: 0.0,
-            I: 0.0,
-            S: 0.0,
-            k: 0.0,
+            A: T::from(0.0),
+            I: T::from(0.0),
+            S: T::from(0.0),
+            k: T::from(0.0),
         };
         tb.A = tb.area();
         tb.I = tb.moment_of_inertia();
         tb.S = tb.section_modulus();
         tb.k = tb.radius_of_gyration();
-        return tb;
+        tb
     }
 }
 
-impl Beam for TrapezoidalBeam {
-    fn area(&self) -> f64 {
+impl<T: Float> Beam<T> for TrapezoidalBeam<T> {
+    fn area(&self) -> T {
         (self.b + self.B) / 2.0 * self.H
     }
-    fn moment_of_inertia(&self) -> f64 {
-        (6.0 * self.b.powi(2)
-            + 6.0 * self.b * self.dlen
+    fn moment_of_inertia(&self) -> T {
+        (self.b.powi(2) * 6.0
+            + self.b * self.dlen * 6.0
             + self.dlen.powi(2))
-            / (36.0 * (2.0 * self.b + self.dlen))
+            / ((self.b * 2.0 + self.dlen) * 36.0)
             * self.H.powi(3)
     }
-    fn section_modulus(&self) -> f64 {
-        (6.0 * self.b.powi(2)
-            + 6.0 * self.b * self.dlen
+    fn section_modulus(&self) -> T {
+        (self.b.powi(2) * 6.0
+            + self.b * self.dlen * 6.0
             + self.dlen.powi(2))
-            / (12.0 * (3.0 * self.b + 2.0 * self.dlen))
+            / ((self.b * 3.0 + self.dlen * 2.0) * 12.0)
             * self.H.powi(2)
     }
 }
@@ -153,54 +169,55 @@ impl Beam for TrapezoidalBeam {
 /// - H = height (outter distance between flanges)
 /// - t = flange thickness
 /// - b = web thickness
+///
 /// Gere, James M., "Mechanics of Materials," 6th Ed.
 #[derive(Debug)]
 #[allow(non_snake_case)]
-pub struct IBeam {
-    pub B: f64,     // Width
-    pub H: f64,     // Height
-    pub t: f64,     // flange thickness
-    pub b: f64,     // web thickness
-    pub A: f64,     // Area
-    pub I: f64,     // Moment of Inertia
-    pub S: f64,     // Section Modulus
-    pub k: f64,     // Radius of gyration
+pub struct IBeam<T = f64> {
+    pub B: T,       // Width
+    pub H: T,       // Height
+    pub t: T,       // flange thickness
+    pub b: T,       // web thickness
+    pub A: T,       // Area
+    pub I: T,       // Moment of Inertia
+    pub S: T,       // Section Modulus
+    pub k: T,       // Radius of gyration
 }
 
 
-impl IBeam {
+impl<T: Float> IBeam<T> {
     #[allow(non_snake_case)]
-    pub fn new(B: f64, H: f64, t: f64, b: f64) -> IBeam {
+    pub fn new(B: T, H: T, t: T, b: T) -> IBeam<T> {
         let mut ib = IBeam {
             B,
             H,
             t,
             b,
-            A: 0.0,
-            I: 0.0,
-            S: 0.0,
-            k: 0.0,
+            A: T::from(0.0),
+            I: T::from(0.0),
+            S: T::from(0.0),
+            k: T::from(0.0),
         };
         ib.A = ib.area();
         ib.I = ib.moment_of_inertia();
         ib.S = ib.section_modulus();
         ib.k = ib.radius_of_gyration();
-        return ib;
+        ib
     }
 }
 
-impl Beam for IBeam {
-    fn area(&self) -> f64 {
-        2.0 * self.B * self.t + self.b * (self.H - 2.0 * self.t)
+impl<T: Float> Beam<T> for IBeam<T> {
+    fn area(&self) -> T {
+        self.B * self.t * 2.0 + self.b * (self.H - self.t * 2.0)
     }
-    fn moment_of_inertia(&self) -> f64 {
-        (self.B * self.H.powi(3) - self.B * (self.H - 2.0 * self.t).powi(3)
-            + self.b * (self.H - 2.0 * self.t).powi(3))
+    fn moment_of_inertia(&self) -> T {
+        (self.B * self.H.powi(3) - self.B * (self.H - self.t * 2.0).powi(3)
+            + self.b * (self.H - self.t * 2.0).powi(3))
             / 12.0
     }
-    fn section_modulus(&self) -> f64 {
+    fn section_modulus(&self) -> T {
         (self.B * self.H.powi(2)
-            - (self.H - 2.0 * self.t).powi(3) / self.H * (self.B - self.b))
+            - (self.H - self.t * 2.0).powi(3) / self.H * (self.B - self.b))
             / 6.0
     }
 }
@@ -209,40 +226,40 @@ impl Beam for IBeam {
 /// Gere, James M., "Mechanics of Materials," 6th Ed.
 #[derive(Debug)]
 #[allow(non_snake_case)]
-pub struct CircularBeam {
-    pub R: f64,     // Radius
-    pub A: f64,     // Area
-    pub I: f64,     // Moment of Inertia
-    pub S: f64,     // Section Modulus
-    pub k: f64,     // Radius of gyration
+pub struct CircularBeam<T = f64> {
+    pub R: T,       // Radius
+    pub A: T,       // Area
+    pub I: T,       // Moment of Inertia
+    pub S: T,       // Section Modulus
+    pub k: T,       // Radius of gyration
 }
 
-impl CircularBeam {
+impl<T: Float> CircularBeam<T> {
     #[allow(non_snake_case)]
-    pub fn new(R: f64) -> CircularBeam {
+    pub fn new(R: T) -> CircularBeam<T> {
         let mut cb = CircularBeam {
             R,
-            A: 0.0,
-            I: 0.0,
-            S: 0.0,
-            k: 0.0,
+            A: T::from(0.0),
+            I: T::from(0.0),
+            S: T::from(0.0),
+            k: T::from(0.0),
         };
         cb.A = cb.area();
         cb.I = cb.moment_of_inertia();
         cb.S = cb.section_modulus();
         cb.k = cb.radius_of_gyration();
-        return cb;
+        cb
     }
 }
 
-impl Beam for CircularBeam {
-    fn area(&self) -> f64 {
-        PI * self.R.powi(2)
+impl<T: Float> Beam<T> for CircularBeam<T> {
+    fn area(&self) -> T {
+        self.R.powi(2) * PI
     }
-    fn moment_of_inertia(&self) -> f64 {
+    fn moment_of_inertia(&self) -> T {
         self.area() / 4.0 * self.R.powi(2)
     }
-    fn section_modulus(&self) -> f64 {
+    fn section_modulus(&self) -> T {
         self.moment_of_inertia() / self.R
     }
 }
@@ -252,44 +269,44 @@ impl Beam for CircularBeam {
 /// Gere, James M., "Mechanics of Materials," 6th Ed.
 #[derive(Debug)]
 #[allow(non_snake_case)]
-pub struct CircularTube {
-    pub r: f64,     // Inner radius
-    pub R: f64,     // Outer radius
-    pub A: f64,     // Area
-    pub I: f64,     // Moment of Inertia
-    pub S: f64,     // Section Modulus
-    pub k: f64,     // Radius of gyration
+pub struct CircularTube<T = f64> {
+    pub r: T,       // Inner radius
+    pub R: T,       // Outer radius
+    pub A: T,       // Area
+    pub I: T,       // Moment of Inertia
+    pub S: T,       // Section Modulus
+    pub k: T,       // Radius of gyration
 }
 
 
-impl CircularTube {
+impl<T: Float> CircularTube<T> {
     #[allow(non_snake_case)]
-    pub fn new(r: f64, R: f64) -> CircularTube {
+    pub fn new(r: T, R: T) -> CircularTube<T> {
         let mut ct = CircularTube {
             r,
             R,
-            A: 0.0,
-            I: 0.0,
-            S: 0.0,
-            k: 0.0,
+            A: T::from(0.0),
+            I: T::from(0.0),
+            S: T::from(0.0),
+            k: T::from(0.0),
         };
         ct.A = ct.area();
         ct.I = ct.moment_of_inertia();
         ct.S = ct.section_modulus();
         ct.k = ct.radius_of_gyration();
-        return ct;
+        ct
     }
 }
 
-impl Beam for CircularTube {
-    fn area(&self) -> f64 {
-        PI * (self.R.powi(2) - self.r.powi(2))
+impl<T: Float> Beam<T> for CircularTube<T> {
+    fn area(&self) -> T {
+        (self.R.powi(2) - self.r.powi(2)) * PI
     }
-    fn moment_of_inertia(&self) -> f64 {
-        PI / 4.0 * (self.R.powi(4) - self.r.powi(4))
+    fn moment_of_inertia(&self) -> T {
+        (self.R.powi(4) - self.r.powi(4)) * (PI / 4.0)
     }
-    fn section_modulus(&self) -> f64 {
-        PI / 4.0 * (self.R.powi(4) - self.r.powi(4)) / self.R
+    fn section_modulus(&self) -> T {
+        (self.R.powi(4) - self.r.powi(4)) * (PI / 4.0) / self.R
     }
 }
 
@@ -303,11 +320,11 @@ impl Beam for CircularTube {
 /// 1. origin: relative position beyond which the force acts on the beam
 /// 2. end: relative position where the force stops acting on the beam
 /// 3. magnitude: a function which accepts a relative position as an argument
-/// and returns some value in units of force. ie f(x) = x * 2
+///    and returns some value in units of force. ie f(x) = x * 2
 pub struct Load {
     pub origin: f64,
     pub end: f64,
-    pub magnitude: Box<Fn(f64) -> f64>,
+    pub magnitude: Box<dyn Fn(f64) -> f64>,
 }
 
 
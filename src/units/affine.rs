@@ -0,0 +1,152 @@
+//! # affine
+//!
+//! Conversions which need an offset as well as a factor. A temperature
+//! reading of 20 °C is 293.15 K, but a temperature *rise* of 20 °C is a rise
+//! of 20 K; in the same way a gauge pressure only means something once you
+//! say which atmosphere it is measured against. `Table::factor` only ever
+//! multiplies, which is right for differences, so readings go through here
+//! instead.
+//!
+//! ```rust
+//! use civil::units::affine::Atmosphere;
+//! use civil::units::conversions::table;
+//!
+//! let t = table();
+//! // a reading
+//! assert!((t.temperature(68.0, "°F", "°C").unwrap() - 20.0).abs() < 1e-12);
+//! // a difference
+//! assert!((t.temperature_difference(18.0, "°F", "°C").unwrap() - 10.0).abs() < 1e-12);
+//! // 0 psig at sea level is one standard atmosphere
+//! let p = t.pressure(0.0, "psig", "psia", Atmosphere::standard()).unwrap();
+//! assert!((p - 14.695_948_775_5).abs() < 1e-9);
+//! ```
+
+use super::names;
+
+/// Scale is a temperature scale: the size of its degree comes from
+/// `units::si`, and `zero` places it against absolute zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale {
+    /// Canonical unit id
+    pub name: &'static str,
+    /// Kelvin at zero on this scale
+    pub zero: f64,
+}
+
+/// SCALES lists every temperature scale which can convert readings.
+pub static SCALES: &[Scale] = &[
+    Scale {
+        name: "kelvin",
+        zero: 0.0,
+    },
+    Scale {
+        name: "celsius",
+        zero: 273.15,
+    },
+    Scale {
+        name: "fahrenheit",
+        zero: 459.67 * 5.0 / 9.0,
+    },
+    Scale {
+        name: "rankine",
+        zero: 0.0,
+    },
+];
+
+/// scale finds the temperature scale for a canonical unit id.
+pub fn scale(id: &str) -> Option<&'static Scale> {
+    SCALES.iter().find(|s| s.name == id)
+}
+
+/// Atmosphere is the reference pressure a gauge reads against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Atmosphere {
+    /// Absolute pressure in pascals
+    pub pressure: f64,
+}
+
+impl Atmosphere {
+    /// new creates a reference atmosphere from an absolute pressure in
+    /// pascals, ie a barometer reading.
+    pub fn new(pressure: f64) -> Atmosphere {
+        Atmosphere { pressure }
+    }
+
+    /// standard is the standard atmosphere at sea level, 101325 Pa.
+    pub fn standard() -> Atmosphere {
+        Atmosphere::new(101_325.0)
+    }
+
+    /// at_elevation estimates the mean atmospheric pressure at an elevation
+    /// in meters above sea level from the 1976 US Standard Atmosphere
+    /// (valid below 11 km). Useful for NPSH checks at altitude.
+    pub fn at_elevation(elevation: f64) -> Atmosphere {
+        Atmosphere::new(101_325.0 * (1.0 - 2.255_77e-5 * elevation).powf(5.255_88))
+    }
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Atmosphere::standard()
+    }
+}
+
+/// gauge splits a pressure unit name into the canonical id of its absolute
+/// unit and whether it was a gauge reading. Gauge units are written with a
+/// "(gauge)" qualifier, a trailing "gauge", or as one of the usual
+/// abbreviations: psig, kPag, barg.
+///
+/// ```rust
+/// use civil::units::affine::gauge;
+///
+/// assert_eq!(gauge("psig"), ("pound per square inch".to_string(), true));
+/// assert_eq!(gauge("kPa (gauge)"), ("kilopascal".to_string(), true));
+/// assert_eq!(gauge("psia"), ("pound per square inch".to_string(), false));
+/// ```
+pub fn gauge(name: &str) -> (String, bool) {
+    let id = names::normalize(name);
+    for suffix in [" (gauge)", " gauge"].iter() {
+        if let Some(base) = id.strip_suffix(suffix) {
+            return (base.to_string(), true);
+        }
+    }
+    let base = match id.as_str() {
+        "psig" => "pound per square inch",
+        "psfg" => "pound per square foot",
+        "kpag" => "kilopascal",
+        "barg" => "bar",
+        _ => return (id, false),
+    };
+    (base.to_string(), true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gauge, scale, Atmosphere};
+
+    #[test]
+    fn fixed_points() {
+        let f = scale("fahrenheit").unwrap();
+        let c = scale("celsius").unwrap();
+        // water freezes at 32 F and 0 C
+        assert!((f.zero + 32.0 * 5.0 / 9.0 - c.zero).abs() < 1e-12);
+        assert_eq!(scale("kelvin").unwrap().zero, 0.0);
+        assert!(scale("foot").is_none());
+    }
+
+    #[test]
+    fn elevation() {
+        assert_eq!(Atmosphere::at_elevation(0.0), Atmosphere::standard());
+        // about 83.4 kPa in Denver
+        let denver = Atmosphere::at_elevation(1609.0).pressure;
+        assert!((denver - 83_430.0).abs() < 100.0);
+    }
+
+    #[test]
+    fn gauge_names() {
+        assert!(gauge("bar gauge").1);
+        assert!(gauge("barg").1);
+        assert!(!gauge("bar").1);
+        assert_eq!(gauge("Pa (gauge)").0, "pascal");
+    }
+}
@@ -0,0 +1,340 @@
+//! # audit
+//!
+//! Consistency checks for the conversion table. Any loop of conversions,
+//! like acre -> hectare -> square meter -> acre, should multiply out to
+//! exactly one. In a table scraped together from several sources they often
+//! don't, and the loops that miss by the most point at the entries which
+//! need fixing.
+//!
+//! Enumerating every simple cycle in the graph is exponential, so the audit
+//! works from a cycle basis instead. A spanning forest is built from the most
+//! precise factors first (Kruskal's algorithm, ranked by
+//! `graph::precision`); every remaining entry then closes exactly one
+//! fundamental cycle with the forest, and every other cycle in the table is a
+//! combination of those. Because the forest holds the most trusted factors,
+//! the entry closing a bad cycle is the likeliest culprit.
+//!
+//! The audit also reports keys which appear more than once in
+//! `conversions::ENTRIES`, since `HashMap::insert` silently keeps only the
+//! last one.
+
+use super::graph::precision;
+use super::names;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+/// Audit is the result of `Table::audit`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audit {
+    /// One fundamental cycle for every entry outside the spanning forest
+    pub cycles: Vec<Cycle>,
+    /// Keys which were inserted more than once
+    pub duplicates: Vec<Duplicate>,
+}
+
+/// Cycle is a loop of conversions closed by a single table entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    /// Raw key of the entry which closes the cycle
+    pub from: &'static str,
+    pub to: &'static str,
+    /// Factor stored for that entry
+    pub factor: f64,
+    /// Canonical units around the loop, starting and ending at `from`
+    pub path: Vec<String>,
+    /// Product of every factor around the loop. Ideally 1.0.
+    pub product: f64,
+}
+
+impl Cycle {
+    /// error is the relative round-trip error of the cycle.
+    pub fn error(&self) -> f64 {
+        (self.product - 1.0).abs()
+    }
+}
+
+/// Duplicate is a key that was given more than one factor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Duplicate {
+    pub from: &'static str,
+    pub to: &'static str,
+    /// Every factor given for the key, in order. The last one is in the table.
+    pub values: Vec<f64>,
+}
+
+impl Duplicate {
+    /// conflicting is true when the duplicated values disagree.
+    pub fn conflicting(&self) -> bool {
+        self.values.iter().any(|v| *v != self.values[0])
+    }
+}
+
+impl Audit {
+    /// audit checks a set of raw entries. `entries` is every factor in
+    /// insertion order, duplicates and all.
+    pub fn new(entries: &[(&'static str, &'static str, f64)]) -> Audit {
+        Audit {
+            cycles: cycles(&last_values(entries)),
+            duplicates: duplicates(entries),
+        }
+    }
+
+    /// flagged returns the cycles whose round-trip error exceeds `tolerance`,
+    /// worst first.
+    pub fn flagged(&self, tolerance: f64) -> Vec<&Cycle> {
+        let mut bad: Vec<&Cycle> = self
+            .cycles
+            .iter()
+            .filter(|c| c.error() > tolerance)
+            .collect();
+        bad.sort_by(|a, b| b.error().partial_cmp(&a.error()).unwrap());
+        bad
+    }
+
+    /// report formats the flagged cycles and the duplicate keys for a human.
+    pub fn report(&self, tolerance: f64) -> String {
+        let mut out = String::new();
+        let flagged = self.flagged(tolerance);
+        writeln!(
+            out,
+            "{} of {} cycles exceed a round-trip error of {:e}",
+            flagged.len(),
+            self.cycles.len(),
+            tolerance
+        )
+        .unwrap();
+        for c in flagged.iter() {
+            writeln!(
+                out,
+                "  {:.3e}  ({:?}, {:?}) = {}  via {}",
+                c.error(),
+                c.from,
+                c.to,
+                c.factor,
+                c.path.join(" -> ")
+            )
+            .unwrap();
+        }
+        writeln!(out, "{} duplicate keys", self.duplicates.len()).unwrap();
+        for d in self.duplicates.iter() {
+            writeln!(out, "  ({:?}, {:?}) = {:?}", d.from, d.to, d.values).unwrap();
+        }
+        out
+    }
+}
+
+// The entries as they end up in the table, with later keys overwriting
+// earlier ones, in first-seen order.
+fn last_values(
+    entries: &[(&'static str, &'static str, f64)],
+) -> Vec<(&'static str, &'static str, f64)> {
+    let mut order: Vec<(&'static str, &'static str)> = Vec::new();
+    let mut values: HashMap<(&str, &str), f64> = HashMap::new();
+    for (from, to, f) in entries.iter() {
+        if values.insert((from, to), *f).is_none() {
+            order.push((from, to));
+        }
+    }
+    order.into_iter().map(|k| (k.0, k.1, values[&k])).collect()
+}
+
+fn duplicates(entries: &[(&'static str, &'static str, f64)]) -> Vec<Duplicate> {
+    let mut seen: Vec<Duplicate> = Vec::new();
+    let mut index: HashMap<(&str, &str), usize> = HashMap::new();
+    for (from, to, f) in entries.iter() {
+        match index.get(&(*from, *to)) {
+            Some(i) => seen[*i].values.push(*f),
+            None => {
+                index.insert((from, to), seen.len());
+                seen.push(Duplicate {
+                    from,
+                    to,
+                    values: vec![*f],
+                });
+            }
+        }
+    }
+    seen.into_iter().filter(|d| d.values.len() > 1).collect()
+}
+
+fn cycles(entries: &[(&'static str, &'static str, f64)]) -> Vec<Cycle> {
+    // Number the canonical units
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    let mut labels: Vec<String> = Vec::new();
+    let mut edges: Vec<(usize, usize, usize)> = Vec::new();
+    for (i, (from, to, _)) in entries.iter().enumerate() {
+        let mut id = |name: &str| {
+            let key = names::normalize(name);
+            *ids.entry(key.clone()).or_insert_with(|| {
+                labels.push(key);
+                labels.len() - 1
+            })
+        };
+        let (a, b) = (id(from), id(to));
+        edges.push((a, b, i));
+    }
+
+    // Kruskal, most precise factors first
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|x, y| {
+        let (px, py) = (precision(entries[*x].2), precision(entries[*y].2));
+        px.partial_cmp(&py).unwrap().then(x.cmp(y))
+    });
+    let mut sets = DisjointSet::new(labels.len());
+    let mut tree: Vec<Vec<(usize, f64)>> = vec![Vec::new(); labels.len()];
+    let mut closing: Vec<usize> = Vec::new();
+    for e in order.into_iter() {
+        let (a, b, i) = edges[e];
+        let f = entries[i].2;
+        if a != b && f != 0.0 && f.is_finite() && sets.union(a, b) {
+            tree[a].push((b, f));
+            tree[b].push((a, 1.0 / f));
+        } else {
+            closing.push(e);
+        }
+    }
+
+    // Root every tree. `value[x]` is the number of root units in one x.
+    let n = labels.len();
+    let mut parent: Vec<Option<usize>> = vec![None; n];
+    let mut depth = vec![0; n];
+    let mut value = vec![1.0; n];
+    let mut visited = vec![false; n];
+    for root in 0..n {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+        while let Some(x) = queue.pop_front() {
+            for (y, f) in tree[x].iter() {
+                if !visited[*y] {
+                    visited[*y] = true;
+                    parent[*y] = Some(x);
+                    depth[*y] = depth[x] + 1;
+                    // one y is 1/f x
+                    value[*y] = value[x] / f;
+                    queue.push_back(*y);
+                }
+            }
+        }
+    }
+
+    closing.sort_by_key(|e| edges[*e].2);
+    closing
+        .into_iter()
+        .map(|e| {
+            let (a, b, i) = edges[e];
+            let (from, to, factor) = entries[i];
+            // out along the entry, then back through the forest
+            let product = factor * value[b] / value[a];
+            let mut up = vec![b];
+            let mut down = vec![a];
+            let (mut x, mut y) = (b, a);
+            while x != y {
+                if depth[x] >= depth[y] {
+                    x = parent[x].unwrap();
+                    up.push(x);
+                } else {
+                    y = parent[y].unwrap();
+                    down.push(y);
+                }
+            }
+            down.pop();
+            let mut path = vec![labels[a].clone()];
+            path.extend(up.iter().map(|k| labels[*k].clone()));
+            path.extend(down.iter().rev().map(|k| labels[*k].clone()));
+            Cycle {
+                from,
+                to,
+                factor,
+                path,
+                product,
+            }
+        })
+        .collect()
+}
+
+// Union-find with path halving
+struct DisjointSet {
+    parent: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(n: usize) -> DisjointSet {
+        DisjointSet {
+            parent: (0..n).collect(),
+        }
+    }
+
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    // Merge the sets holding a and b. False if they were already joined.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+        self.parent[ra] = rb;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Audit;
+
+    const SAMPLE: [(&str, &str, f64); 6] = [
+        ("acre", "hectare", 0.4047),
+        ("acre", "square meter", 4046.873),
+        ("hectare", "square meter", 10000.0),
+        ("square foot", "square meter", 0.09290304),
+        ("acre", "square foot", 43560.0),
+        ("acre", "square foot", 43561.0),
+    ];
+
+    #[test]
+    fn finds_fundamental_cycles() {
+        let audit = Audit::new(&SAMPLE);
+        // 4 units and 5 distinct keys leave 2 independent loops
+        assert_eq!(audit.cycles.len(), 2);
+        for c in audit.cycles.iter() {
+            assert_eq!(c.path.first(), c.path.last());
+        }
+    }
+
+    #[test]
+    fn blames_the_imprecise_entry() {
+        let audit = Audit::new(&SAMPLE);
+        let bad = audit.flagged(1.0e-5);
+        assert_eq!(bad.len(), 2);
+        assert_eq!(bad[0].to, "hectare");
+        assert!((bad[0].error() - (0.4047 / 0.4046873 - 1.0)).abs() < 1.0e-9);
+        // the overwriting 43561 is what's left in the table
+        assert_eq!(bad[1].to, "square foot");
+        assert_eq!(bad[1].factor, 43561.0);
+        assert!(audit.flagged(1.0e-3).is_empty());
+    }
+
+    #[test]
+    fn finds_duplicates() {
+        let audit = Audit::new(&SAMPLE);
+        assert_eq!(audit.duplicates.len(), 1);
+        assert_eq!(audit.duplicates[0].values, vec![43560.0, 43561.0]);
+        assert!(audit.duplicates[0].conflicting());
+    }
+
+    #[test]
+    fn report_lists_problems() {
+        let report = Audit::new(&SAMPLE).report(1.0e-5);
+        assert!(report.starts_with("2 of 2 cycles"));
+        assert!(report.contains("1 duplicate keys"));
+    }
+}
@@ -0,0 +1,257 @@
+//! # category
+//!
+//! What a unit measures: length, area, pressure and so on. Every SI unit
+//! used as a `base` in `units::si` is its own category, so units can only be
+//! converted within a category. Energy and moment share a dimension but are
+//! kept apart, as are frequency and angular velocity, since mixing them up is
+//! nearly always a mistake.
+//!
+//! ```rust
+//! use civil::units::category::Category;
+//! use civil::units::conversions::table;
+//!
+//! let t = table();
+//! assert_eq!(t.category("psi").unwrap(), Some(Category::Pressure));
+//! // everything which can go in a dropdown next to "psi"
+//! let pressures = t.units_in(Category::Pressure);
+//! assert!(pressures.contains(&"kilopascal"));
+//! assert!(t.factor("psi", "ft").is_err());
+//! ```
+
+use super::dimension::Dimension;
+use super::si;
+use std::fmt;
+
+/// Category is the kind of quantity a unit measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Category {
+    Length,
+    Area,
+    Volume,
+    Mass,
+    Time,
+    Temperature,
+    Current,
+    Angle,
+    Frequency,
+    AngularVelocity,
+    Velocity,
+    Acceleration,
+    Flow,
+    KinematicViscosity,
+    Force,
+    Pressure,
+    Energy,
+    Moment,
+    Power,
+    Density,
+    UnitWeight,
+    Viscosity,
+    Charge,
+    Voltage,
+    Resistance,
+    Capacitance,
+    Inductance,
+    Conductance,
+    MagneticFlux,
+    MagneticFluxDensity,
+    MagneticFieldStrength,
+    ElectricField,
+    DipoleMoment,
+    ChargeDensity,
+    MolarCharge,
+    Luminance,
+    Illuminance,
+    Activity,
+    AbsorbedDose,
+    EquivalentDose,
+    Exposure,
+    HeatFlux,
+    RadiantExposure,
+    ThermalInsulance,
+    VaporPermeability,
+    VaporPermeance,
+    LinearDensity,
+    LineLoad,
+    SecondMomentOfArea,
+    Action,
+    Fluidity,
+    AngularAcceleration,
+    ReciprocalLength,
+    Slope,
+    Level,
+    SolidAngle,
+}
+
+// Each category with its SI unit, in the same order as `si::BASES`
+static BASES: &[(Category, &str)] = &[
+    (Category::Length, "meter"),
+    (Category::Area, "square meter"),
+    (Category::Volume, "cubic meter"),
+    (Category::Mass, "kilogram"),
+    (Category::Time, "second"),
+    (Category::Temperature, "kelvin"),
+    (Category::Current, "ampere"),
+    (Category::Angle, "radian"),
+    (Category::Frequency, "hertz"),
+    (Category::AngularVelocity, "radian per second"),
+    (Category::Velocity, "meter per second"),
+    (Category::Acceleration, "meter per square second"),
+    (Category::Flow, "cubic meter per second"),
+    (Category::KinematicViscosity, "square meter per second"),
+    (Category::Force, "newton"),
+    (Category::Pressure, "pascal"),
+    (Category::Energy, "joule"),
+    (Category::Moment, "newton-meter"),
+    (Category::Power, "watt"),
+    (Category::Density, "kilogram per cubic meter"),
+    (Category::UnitWeight, "newton per cubic meter"),
+    (Category::Viscosity, "pascal second"),
+    (Category::Charge, "coulomb"),
+    (Category::Voltage, "volt"),
+    (Category::Resistance, "ohm"),
+    (Category::Capacitance, "farad"),
+    (Category::Inductance, "henry"),
+    (Category::Conductance, "siemens"),
+    (Category::MagneticFlux, "weber"),
+    (Category::MagneticFluxDensity, "tesla"),
+    (Category::MagneticFieldStrength, "ampere per meter"),
+    (Category::ElectricField, "volt per meter"),
+    (Category::DipoleMoment, "coulomb meter"),
+    (Category::ChargeDensity, "coulomb per square meter"),
+    (Category::MolarCharge, "coulomb per mole"),
+    (Category::Luminance, "candela per square meter"),
+    (Category::Illuminance, "lux"),
+    (Category::Activity, "becquerel"),
+    (Category::AbsorbedDose, "gray"),
+    (Category::EquivalentDose, "sievert"),
+    (Category::Exposure, "coulomb per kilogram"),
+    (Category::HeatFlux, "watt per square meter"),
+    (Category::RadiantExposure, "joule per square meter"),
+    (Category::ThermalInsulance, "kelvin square meter per watt"),
+    (
+        Category::VaporPermeability,
+        "kilogram per pascal second meter",
+    ),
+    (
+        Category::VaporPermeance,
+        "kilogram per pascal second square meter",
+    ),
+    (Category::LinearDensity, "kilogram per meter"),
+    (Category::LineLoad, "newton per meter"),
+    (Category::SecondMomentOfArea, "meter to the fourth power"),
+    (Category::Action, "joule second"),
+    (Category::Fluidity, "per pascal second"),
+    (Category::AngularAcceleration, "radian per square second"),
+    (Category::ReciprocalLength, "reciprocal meter"),
+    (Category::Slope, "meter per meter"),
+    (Category::Level, "neper"),
+    (Category::SolidAngle, "steradian"),
+];
+
+impl Category {
+    /// all lists every category in a fixed order, suitable for a menu.
+    pub fn all() -> impl Iterator<Item = Category> {
+        BASES.iter().map(|(c, _)| *c)
+    }
+
+    /// from_base finds the category measured by an SI base unit from
+    /// `units::si`, ie "pascal" for pressure.
+    pub fn from_base(base: &str) -> Option<Category> {
+        BASES.iter().find(|(_, b)| *b == base).map(|(c, _)| *c)
+    }
+
+    /// base is the canonical id of the SI unit of this category.
+    pub fn base(self) -> &'static str {
+        BASES.iter().find(|(c, _)| *c == self).unwrap().1
+    }
+
+    /// dimension is the dimension shared by every unit in this category.
+    pub fn dimension(self) -> Dimension {
+        si::dimension(self.base()).unwrap()
+    }
+
+    /// name is a lower case name for display, ie "unit weight".
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Length => "length",
+            Category::Area => "area",
+            Category::Volume => "volume",
+            Category::Mass => "mass",
+            Category::Time => "time",
+            Category::Temperature => "temperature",
+            Category::Current => "electric current",
+            Category::Angle => "angle",
+            Category::Frequency => "frequency",
+            Category::AngularVelocity => "angular velocity",
+            Category::Velocity => "velocity",
+            Category::Acceleration => "acceleration",
+            Category::Flow => "flow",
+            Category::KinematicViscosity => "kinematic viscosity",
+            Category::Force => "force",
+            Category::Pressure => "pressure",
+            Category::Energy => "energy",
+            Category::Moment => "moment",
+            Category::Power => "power",
+            Category::Density => "density",
+            Category::UnitWeight => "unit weight",
+            Category::Viscosity => "viscosity",
+            Category::Charge => "electric charge",
+            Category::Voltage => "voltage",
+            Category::Resistance => "resistance",
+            Category::Capacitance => "capacitance",
+            Category::Inductance => "inductance",
+            Category::Conductance => "conductance",
+            Category::MagneticFlux => "magnetic flux",
+            Category::MagneticFluxDensity => "magnetic flux density",
+            Category::MagneticFieldStrength => "magnetic field strength",
+            Category::ElectricField => "electric field",
+            Category::DipoleMoment => "electric dipole moment",
+            Category::ChargeDensity => "surface charge density",
+            Category::MolarCharge => "molar charge",
+            Category::Luminance => "luminance",
+            Category::Illuminance => "illuminance",
+            Category::Activity => "radioactivity",
+            Category::AbsorbedDose => "absorbed dose",
+            Category::EquivalentDose => "equivalent dose",
+            Category::Exposure => "radiation exposure",
+            Category::HeatFlux => "heat flux",
+            Category::RadiantExposure => "radiant exposure",
+            Category::ThermalInsulance => "thermal insulance",
+            Category::VaporPermeability => "vapor permeability",
+            Category::VaporPermeance => "vapor permeance",
+            Category::LinearDensity => "linear density",
+            Category::LineLoad => "force per length",
+            Category::SecondMomentOfArea => "second moment of area",
+            Category::Action => "action",
+            Category::Fluidity => "fluidity",
+            Category::AngularAcceleration => "angular acceleration",
+            Category::ReciprocalLength => "reciprocal length",
+            Category::Slope => "slope",
+            Category::Level => "level",
+            Category::SolidAngle => "solid angle",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Category;
+    use crate::units::si;
+
+    #[test]
+    fn one_category_per_base() {
+        assert_eq!(Category::all().count(), si::BASES.len());
+        for (base, dimension) in si::BASES.iter() {
+            let c = Category::from_base(base).unwrap();
+            assert_eq!(c.base(), *base);
+            assert_eq!(c.dimension(), *dimension);
+        }
+    }
+}
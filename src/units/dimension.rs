@@ -0,0 +1,236 @@
+//! # dimension
+//!
+//! Physical dimensions as exponents of the seven SI base quantities. A force
+//! is mass times length over time squared, `L M T^-2`, so its dimension is
+//! `[1, 1, -2, 0, 0, 0, 0]` in the order length, mass, time, temperature,
+//! current, amount of substance and luminous intensity.
+//!
+//! Angles are ratios of lengths and so are dimensionless, as in the SI.
+//!
+//! ```rust
+//! use civil::units::dimension::Dimension;
+//!
+//! let force = Dimension::MASS * Dimension::LENGTH / Dimension::TIME.powi(2);
+//! let pressure = force / Dimension::LENGTH.powi(2);
+//! assert_eq!(pressure.to_string(), "L^-1 M T^-2");
+//! ```
+
+use std::fmt;
+use std::ops::{Div, Mul};
+
+/// Dimension holds the exponent of each SI base quantity: length, mass,
+/// time, temperature, electric current, amount of substance and luminous
+/// intensity, in that order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dimension(pub [i8; 7]);
+
+// Symbols for Display, in the same order as the exponents
+const SYMBOLS: [&str; 7] = ["L", "M", "T", "Θ", "I", "N", "J"];
+
+impl Dimension {
+    pub const NONE: Dimension = Dimension([0, 0, 0, 0, 0, 0, 0]);
+    pub const LENGTH: Dimension = Dimension([1, 0, 0, 0, 0, 0, 0]);
+    pub const MASS: Dimension = Dimension([0, 1, 0, 0, 0, 0, 0]);
+    pub const TIME: Dimension = Dimension([0, 0, 1, 0, 0, 0, 0]);
+    pub const TEMPERATURE: Dimension = Dimension([0, 0, 0, 1, 0, 0, 0]);
+    pub const CURRENT: Dimension = Dimension([0, 0, 0, 0, 1, 0, 0]);
+    pub const AMOUNT: Dimension = Dimension([0, 0, 0, 0, 0, 1, 0]);
+    pub const LUMINOSITY: Dimension = Dimension([0, 0, 0, 0, 0, 0, 1]);
+
+    /// new builds a dimension from its exponents: length, mass, time,
+    /// temperature, current, amount and luminosity.
+    pub const fn new(exponents: [i8; 7]) -> Dimension {
+        Dimension(exponents)
+    }
+
+    /// powi raises a dimension to an integer power, ie `LENGTH.powi(2)` is
+    /// an area.
+    pub fn powi(self, n: i8) -> Dimension {
+        let mut out = self.0;
+        for e in out.iter_mut() {
+            *e *= n;
+        }
+        Dimension(out)
+    }
+
+    /// checked_powi is `powi`, or `None` if an exponent leaves the range
+    /// of an `i8`.
+    pub fn checked_powi(self, n: i8) -> Option<Dimension> {
+        let mut out = self.0;
+        for e in out.iter_mut() {
+            *e = e.checked_mul(n)?;
+        }
+        Some(Dimension(out))
+    }
+
+    /// checked_mul is `self * other`, or `None` if an exponent leaves the
+    /// range of an `i8`.
+    pub fn checked_mul(self, other: Dimension) -> Option<Dimension> {
+        let mut out = self.0;
+        for (e, o) in out.iter_mut().zip(other.0.iter()) {
+            *e = e.checked_add(*o)?;
+        }
+        Some(Dimension(out))
+    }
+
+    /// checked_div is `self / other`, or `None` if an exponent leaves the
+    /// range of an `i8`.
+    pub fn checked_div(self, other: Dimension) -> Option<Dimension> {
+        self.checked_mul(other.checked_powi(-1)?)
+    }
+
+    /// root takes the nth root of a dimension, which only exists when every
+    /// exponent divides evenly.
+    pub fn root(self, n: i8) -> Option<Dimension> {
+        if n == 0 || self.0.iter().any(|e| e.checked_rem(n) != Some(0)) {
+            return None;
+        }
+        let mut out = self.0;
+        for e in out.iter_mut() {
+            *e = e.checked_div(n)?;
+        }
+        Some(Dimension(out))
+    }
+
+    /// is_dimensionless is true for pure numbers, ratios and angles.
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Dimension::NONE
+    }
+}
+
+// Multiplying quantities adds the exponents of their dimensions
+impl Mul for Dimension {
+    type Output = Dimension;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: Dimension) -> Dimension {
+        let mut out = self.0;
+        for (e, o) in out.iter_mut().zip(other.0.iter()) {
+            *e += o;
+        }
+        Dimension(out)
+    }
+}
+
+impl Div for Dimension {
+    type Output = Dimension;
+
+    fn div(self, other: Dimension) -> Dimension {
+        self * other.powi(-1)
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_dimensionless() {
+            return write!(f, "1");
+        }
+        let mut first = true;
+        for (e, s) in self.0.iter().zip(SYMBOLS.iter()) {
+            if *e == 0 {
+                continue;
+            }
+            if !first {
+                write!(f, " ")?;
+            }
+            first = false;
+            if *e == 1 {
+                write!(f, "{}", s)?;
+            } else {
+                write!(f, "{}^{}", s, e)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Unit is a unit of measure reduced to the SI: one of it is `factor`
+/// coherent SI units of the given dimension. A kip-foot is 1355.8 N m, so
+/// its factor is 1355.8 and its dimension is `L^2 M T^-2`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unit {
+    pub factor: f64,
+    pub dimension: Dimension,
+}
+
+impl Unit {
+    /// powi raises a unit to an integer power, ie foot to square foot.
+    pub fn powi(self, n: i8) -> Unit {
+        Unit {
+            factor: self.factor.powi(n.into()),
+            dimension: self.dimension.powi(n),
+        }
+    }
+
+    /// checked_powi is `powi`, or `None` if the dimension overflows.
+    pub fn checked_powi(self, n: i8) -> Option<Unit> {
+        Some(Unit {
+            factor: self.factor.powi(n.into()),
+            dimension: self.dimension.checked_powi(n)?,
+        })
+    }
+
+    /// checked_mul is `self * other`, or `None` if the dimension overflows.
+    pub fn checked_mul(self, other: Unit) -> Option<Unit> {
+        Some(Unit {
+            factor: self.factor * other.factor,
+            dimension: self.dimension.checked_mul(other.dimension)?,
+        })
+    }
+
+    /// checked_div is `self / other`, or `None` if the dimension overflows.
+    pub fn checked_div(self, other: Unit) -> Option<Unit> {
+        Some(Unit {
+            factor: self.factor / other.factor,
+            dimension: self.dimension.checked_div(other.dimension)?,
+        })
+    }
+}
+
+impl Mul for Unit {
+    type Output = Unit;
+
+    fn mul(self, other: Unit) -> Unit {
+        Unit {
+            factor: self.factor * other.factor,
+            dimension: self.dimension * other.dimension,
+        }
+    }
+}
+
+impl Div for Unit {
+    type Output = Unit;
+
+    fn div(self, other: Unit) -> Unit {
+        Unit {
+            factor: self.factor / other.factor,
+            dimension: self.dimension / other.dimension,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dimension;
+
+    #[test]
+    fn algebra() {
+        let area = Dimension::LENGTH.powi(2);
+        assert_eq!(area * Dimension::LENGTH, Dimension::LENGTH.powi(3));
+        assert_eq!(area / area, Dimension::NONE);
+        assert_eq!(area.root(2), Some(Dimension::LENGTH));
+        assert_eq!(Dimension::LENGTH.root(2), None);
+        let big = Dimension::LENGTH.powi(100);
+        assert_eq!(big.checked_powi(2), None);
+        assert_eq!(big.checked_mul(big), None);
+        assert_eq!(Dimension::LENGTH.powi(-128).checked_div(big), None);
+        assert_eq!(big.checked_div(big), Some(Dimension::NONE));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Dimension::NONE.to_string(), "1");
+        let flow = Dimension::LENGTH.powi(3) / Dimension::TIME;
+        assert_eq!(flow.to_string(), "L^3 T^-1");
+    }
+}
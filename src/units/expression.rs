@@ -0,0 +1,338 @@
+//! # expression
+//!
+//! Parsing of compound unit expressions such as `"kN*m/s^2"`, `"lb/ft³"` or
+//! `"gpm/ft^2"`. An expression is reduced to a `Unit`: a factor to coherent
+//! SI units and a dimension. Two expressions with the same dimension can
+//! then be converted by dividing their factors.
+//!
+//! The grammar is the usual one, read left to right:
+//!
+//! - `*`, `·` and `×` multiply, `/` divides: `W/m^2/K` is `W/(m^2 K)`
+//! - `^n` (or a trailing `²`, `³`) raises the preceding unit to an integer
+//!   power, which may be negative: `s^-1`
+//! - parentheses group: `kN/(m*s)`
+//! - a bare number is a dimensionless factor: `1/s`
+//!
+//! Names between the operators are looked up whole first, so anything the
+//! table knows by name (`"pound per square inch"`, `"acre-foot"`,
+//! `"foot (us survey)"`) works as is. Names it doesn't know are taken apart
+//! following the table's naming conventions: "per" divides, hyphens
+//! multiply ("kip-ft"), and a leading "square" or "cubic" is a power.
+//!
+//! How a plain "lb" or "pound" is read is described in
+//! `units::conversions`.
+//!
+//! ```rust
+//! use civil::units::conversions::table;
+//!
+//! let t = table();
+//! let f = t.factor("kip-ft", "kN*m").unwrap();
+//! assert!((f - 1.355_817_948).abs() < 1e-9);
+//! let f = t.factor("lbm/ft³", "kg/m^3").unwrap();
+//! assert!((f - 16.018_463_37).abs() < 1e-8);
+//! let f = t.factor("lb/ft³", "kN/m^3").unwrap();
+//! assert!((f - 0.157_087_463_8).abs() < 1e-9);
+//! ```
+
+use super::dimension::{Dimension, Unit};
+use super::UnitError;
+
+/// reduce parses a unit expression, looking up each name with `resolve`.
+pub fn reduce<F>(expression: &str, resolve: F) -> Result<Unit, UnitError>
+where
+    F: Fn(&str) -> Result<Unit, UnitError>,
+{
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser {
+        expression,
+        tokens,
+        pos: 0,
+        resolve: &resolve,
+    };
+    let unit = parser.product()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(parser.error("unexpected \")\""));
+    }
+    Ok(unit)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Mul,
+    Div,
+    Pow(i8),
+    Open,
+    Close,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, UnitError> {
+    let invalid = |reason: &str| UnitError::InvalidExpression {
+        expression: expression.to_string(),
+        reason: reason.to_string(),
+    };
+    let mut tokens = Vec::new();
+    let mut name = String::new();
+    let mut chars = expression.chars().peekable();
+    // Flush the name being read, if any
+    let flush = |name: &mut String, tokens: &mut Vec<Token>| {
+        let trimmed = name.trim();
+        if !trimmed.is_empty() {
+            tokens.push(Token::Name(trimmed.to_string()));
+        }
+        name.clear();
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '·' | '×' | '/' | '^' | '²' | '³' | ')' => {
+                flush(&mut name, &mut tokens);
+                tokens.push(match c {
+                    '/' => Token::Div,
+                    '²' => Token::Pow(2),
+                    '³' => Token::Pow(3),
+                    ')' => Token::Close,
+                    '^' => {
+                        let mut digits = String::new();
+                        while let Some(d) = chars.peek() {
+                            let sign = digits.is_empty() && (*d == '-' || *d == '+');
+                            if d.is_ascii_digit() || sign {
+                                digits.push(*d);
+                                chars.next();
+                            } else if d.is_whitespace() && digits.is_empty() {
+                                chars.next();
+                            } else {
+                                break;
+                            }
+                        }
+                        Token::Pow(digits.parse().map_err(|_| invalid("bad exponent"))?)
+                    }
+                    _ => Token::Mul,
+                });
+            }
+            '(' if name.trim().is_empty() => {
+                tokens.push(Token::Open);
+                name.clear();
+            }
+            // A parenthesis after a name is a qualifier, ie "gallon (imperial)"
+            '(' => {
+                name.push(c);
+                let mut depth = 1;
+                for c in chars.by_ref() {
+                    name.push(c);
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            _ => name.push(c),
+        }
+    }
+    flush(&mut name, &mut tokens);
+    if tokens.is_empty() {
+        return Err(invalid("empty expression"));
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a, F> {
+    expression: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    resolve: &'a F,
+}
+
+impl<'a, F> Parser<'a, F>
+where
+    F: Fn(&str) -> Result<Unit, UnitError>,
+{
+    fn error(&self, reason: &str) -> UnitError {
+        UnitError::InvalidExpression {
+            expression: self.expression.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    // The result of a checked operation on units, which fails when an
+    // exponent of the dimension no longer fits
+    fn checked(&self, unit: Option<Unit>) -> Result<Unit, UnitError> {
+        unit.ok_or_else(|| self.error("exponent out of range"))
+    }
+
+    // product := power (('*' | '/') power)*
+    fn product(&mut self) -> Result<Unit, UnitError> {
+        let mut unit = self.power()?;
+        while let Some(t) = self.tokens.get(self.pos) {
+            match t {
+                Token::Mul => {
+                    self.pos += 1;
+                    let other = self.power()?;
+                    unit = self.checked(unit.checked_mul(other))?;
+                }
+                Token::Div => {
+                    self.pos += 1;
+                    let other = self.power()?;
+                    unit = self.checked(unit.checked_div(other))?;
+                }
+                _ => break,
+            }
+        }
+        Ok(unit)
+    }
+
+    // power := primary ('^' n)*
+    fn power(&mut self) -> Result<Unit, UnitError> {
+        let mut unit = self.primary()?;
+        while let Some(Token::Pow(n)) = self.tokens.get(self.pos) {
+            unit = self.checked(unit.checked_powi(*n))?;
+            self.pos += 1;
+        }
+        Ok(unit)
+    }
+
+    // primary := name | '(' product ')'
+    fn primary(&mut self) -> Result<Unit, UnitError> {
+        let token = match self.tokens.get(self.pos) {
+            Some(t) => t.clone(),
+            None => return Err(self.error("expected a unit")),
+        };
+        self.pos += 1;
+        match token {
+            Token::Name(name) => self.name(&name),
+            Token::Open => {
+                let unit = self.product()?;
+                if self.tokens.get(self.pos) != Some(&Token::Close) {
+                    return Err(self.error("missing \")\""));
+                }
+                self.pos += 1;
+                Ok(unit)
+            }
+            _ => Err(self.error("expected a unit")),
+        }
+    }
+
+    // Look a name up whole, then try taking it apart.
+    fn name(&self, name: &str) -> Result<Unit, UnitError> {
+        if let Ok(n) = name.parse::<f64>() {
+            return Ok(Unit {
+                factor: n,
+                dimension: Dimension::NONE,
+            });
+        }
+        let whole = (self.resolve)(name);
+        if whole.is_ok() {
+            return whole;
+        }
+        match self.pieces(name) {
+            Some(Ok(unit)) => Ok(unit),
+            // Report the name as a whole; it's what the caller wrote
+            _ => whole,
+        }
+    }
+
+    // Take a name apart following the table's naming conventions. `None`
+    // when there is nothing to take apart.
+    fn pieces(&self, name: &str) -> Option<Result<Unit, UnitError>> {
+        let one = Unit {
+            factor: 1.0,
+            dimension: Dimension::NONE,
+        };
+        if name.contains(" per ") {
+            let mut parts = name.split(" per ");
+            let numerator = parts.next().unwrap().trim();
+            let mut unit = if numerator.is_empty() {
+                Ok(one)
+            } else {
+                self.name(numerator)
+            };
+            for p in parts {
+                unit = unit.and_then(|u| self.checked(u.checked_div(self.name(p.trim())?)));
+            }
+            return Some(unit);
+        }
+        if name.contains('-') {
+            let mut unit = Ok(one);
+            for p in name.split('-').filter(|p| !p.trim().is_empty()) {
+                unit = unit.and_then(|u| self.checked(u.checked_mul(self.name(p.trim())?)));
+            }
+            return Some(unit);
+        }
+        if let Some(rest) = name.strip_prefix("square ") {
+            return Some(
+                self.name(rest)
+                    .and_then(|u| self.checked(u.checked_powi(2))),
+            );
+        }
+        name.strip_prefix("cubic ").map(|rest| {
+            self.name(rest)
+                .and_then(|u| self.checked(u.checked_powi(3)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reduce;
+    use crate::units::dimension::{Dimension, Unit};
+    use crate::units::UnitError;
+
+    // A tiny stand-in for the table
+    fn resolve(name: &str) -> Result<Unit, UnitError> {
+        let (factor, dimension) = match name {
+            "m" | "meter" => (1.0, Dimension::LENGTH),
+            "ft" | "foot" => (0.3048, Dimension::LENGTH),
+            "s" => (1.0, Dimension::TIME),
+            "kN" => (1000.0, Dimension::new([1, 1, -2, 0, 0, 0, 0])),
+            "gallon (imperial)" => (4.546_09e-3, Dimension::LENGTH.powi(3)),
+            _ => {
+                return Err(UnitError::UnknownUnit {
+                    name: name.to_string(),
+                    suggestions: vec![],
+                })
+            }
+        };
+        Ok(Unit { factor, dimension })
+    }
+
+    #[test]
+    fn operators() {
+        let u = reduce("kN*m/s^2", resolve).unwrap();
+        assert_eq!(u.factor, 1000.0);
+        assert_eq!(u.dimension, Dimension::new([2, 1, -4, 0, 0, 0, 0]));
+        let u = reduce("ft²", resolve).unwrap();
+        assert!((u.factor - 0.092_903_04).abs() < 1e-15);
+        let u = reduce("1/s", resolve).unwrap();
+        assert_eq!(u.dimension, Dimension::TIME.powi(-1));
+        assert_eq!(reduce("s^-1", resolve), reduce("1/s", resolve));
+        // left to right
+        assert_eq!(reduce("m/s/s", resolve), reduce("m/(s*s)", resolve));
+    }
+
+    #[test]
+    fn names() {
+        let u = reduce("ft-kN", resolve).unwrap();
+        assert!((u.factor - 304.8).abs() < 1e-12);
+        assert_eq!(reduce("foot per s", resolve), reduce("ft/s", resolve));
+        assert_eq!(reduce("cubic foot", resolve), reduce("ft^3", resolve));
+        let u = reduce("gallon (imperial)/s", resolve).unwrap();
+        assert_eq!(u.factor, 4.546_09e-3);
+    }
+
+    #[test]
+    fn errors() {
+        for bad in ["", "m^", "(m", "m)", "m/", "m^x", "m^100^2", "m^127*m"].iter() {
+            match reduce(bad, resolve) {
+                Err(UnitError::InvalidExpression { .. }) => {}
+                other => panic!("{:?} gave {:?}", bad, other),
+            }
+        }
+        match reduce("m/furlongg", resolve) {
+            Err(UnitError::UnknownUnit { name, .. }) => assert_eq!(name, "furlongg"),
+            other => panic!("expected an unknown unit, got {:?}", other),
+        }
+    }
+}
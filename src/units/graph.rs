@@ -0,0 +1,278 @@
+//! # graph
+//!
+//! The conversion table only stores one direction of each pair, and only the
+//! pairs somebody bothered to write down. Graph treats every canonical unit
+//! as a node and every factor as an edge (plus its reciprocal going the other
+//! way), so any two units which are connected through other entries can be
+//! converted: acre -> square meter -> square foot, for instance.
+//!
+//! Paths are found with the fewest hops, since every hop compounds whatever
+//! rounding is in the table. Among paths of the same length, edges are
+//! weighted by the relative rounding error implied by the significant
+//! figures of the stored factor, so the most precise chain wins. That matters
+//! when the table holds both `0.000247` and `4046.873` for the same pair.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Conversion is the result of a path search through the graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    /// Number of `to` units in one `from` unit
+    pub factor: f64,
+    /// Canonical unit ids visited, starting with `from` and ending with `to`
+    pub path: Vec<String>,
+}
+
+/// Edge is a single directed conversion between two canonical units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    pub to: String,
+    pub factor: f64,
+    /// Estimated relative error of `factor`
+    pub error: f64,
+}
+
+/// Graph is an adjacency list of canonical unit ids.
+#[derive(Debug, Default)]
+pub struct Graph {
+    edges: HashMap<String, Vec<Edge>>,
+}
+
+/// EXACT is the relative error assumed for factors which look like
+/// definitions.
+pub const EXACT: f64 = 1.0e-9;
+
+impl Graph {
+    pub fn new() -> Graph {
+        Graph {
+            edges: HashMap::new(),
+        }
+    }
+
+    /// insert adds `from -> to` with the given factor, along with the inverse
+    /// edge `to -> from`. Zero and non-finite factors are ignored since they
+    /// cannot be inverted.
+    pub fn insert(&mut self, from: &str, to: &str, factor: f64) {
+        self.insert_with_error(from, to, factor, precision(factor));
+    }
+
+    /// insert_with_error is `insert` for a factor whose relative error is
+    /// already known, such as one derived from an exact definition.
+    pub fn insert_with_error(&mut self, from: &str, to: &str, factor: f64, error: f64) {
+        if from == to || factor == 0.0 || !factor.is_finite() {
+            return;
+        }
+        self.edges.entry(from.to_string()).or_default().push(Edge {
+            to: to.to_string(),
+            factor,
+            error,
+        });
+        self.edges.entry(to.to_string()).or_default().push(Edge {
+            to: from.to_string(),
+            factor: 1.0 / factor,
+            error,
+        });
+    }
+
+    /// add_node adds a unit with no edges yet, such as an SI unit nothing
+    /// else is defined against.
+    pub fn add_node(&mut self, id: &str) {
+        self.edges.entry(id.to_string()).or_default();
+    }
+
+    /// contains reports whether a unit id is a node of the graph.
+    pub fn contains(&self, id: &str) -> bool {
+        self.edges.contains_key(id)
+    }
+
+    /// nodes iterates over every unit id in the graph, in no particular order.
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.edges.keys().map(|k| k.as_str())
+    }
+
+    /// neighbors returns the edges leaving a unit, including the inverse
+    /// edges derived from entries pointing at it.
+    pub fn neighbors(&self, id: &str) -> &[Edge] {
+        self.edges.get(id).map_or(&[], |e| e.as_slice())
+    }
+
+    /// path finds the shortest, then most precise, chain of factors from one
+    /// unit to another using Dijkstra's algorithm. Returns `None` when the
+    /// units are not connected.
+    pub fn path(&self, from: &str, to: &str) -> Option<Conversion> {
+        if !self.contains(from) || !self.contains(to) {
+            return None;
+        }
+        if from == to {
+            return Some(Conversion {
+                factor: 1.0,
+                path: vec![from.to_string()],
+            });
+        }
+        let mut best: HashMap<&str, (Cost, f64, Option<&str>)> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        best.insert(from, (Cost(0, 0.0), 1.0, None));
+        heap.push(State {
+            cost: Cost(0, 0.0),
+            node: from,
+        });
+        while let Some(State { cost, node }) = heap.pop() {
+            if node == to {
+                break;
+            }
+            let (known, factor, _) = best[node];
+            if cost > known {
+                continue;
+            }
+            for e in self.neighbors(node) {
+                let next = Cost(cost.0 + 1, cost.1 + e.error);
+                let better = best.get(e.to.as_str()).is_none_or(|b| next < b.0);
+                if better {
+                    best.insert(e.to.as_str(), (next, factor * e.factor, Some(node)));
+                    heap.push(State {
+                        cost: next,
+                        node: e.to.as_str(),
+                    });
+                }
+            }
+        }
+        let factor = best.get(to)?.1;
+        let mut path = vec![to.to_string()];
+        let mut cur = to;
+        while let Some(prev) = best[cur].2 {
+            path.push(prev.to_string());
+            cur = prev;
+        }
+        path.reverse();
+        Some(Conversion { factor, path })
+    }
+}
+
+/// precision estimates the relative rounding error of a tabulated factor from
+/// its significant figures, ie `0.4047` is good to about 1 part in 2000.
+/// Small whole numbers, powers of ten and their reciprocals (12 inches,
+/// 1/1000 of a kilometer) are usually definitions, so they are taken as
+/// exact. Large round numbers like `1200000.0` are not; they are just as
+/// likely to be rounded.
+pub fn precision(factor: f64) -> f64 {
+    let exact = |x: f64| {
+        let whole = (x - x.round()).abs() <= x.abs() * 1.0e-12;
+        let log = x.log10();
+        whole && (x < 10000.0 || (log - log.round()).abs() < 1.0e-12)
+    };
+    let factor = factor.abs();
+    if exact(factor) || exact(1.0 / factor) {
+        return EXACT;
+    }
+    let s = format!("{:e}", factor);
+    let mantissa = s.split('e').next().unwrap_or("");
+    let digits = mantissa.chars().filter(|c| c.is_ascii_digit()).count();
+    0.5 * 10f64.powi(1 - digits as i32)
+}
+
+// Path cost: number of hops, then accumulated relative error
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cost(usize, f64);
+
+impl PartialOrd for Cost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.0.cmp(&other.0) {
+            Ordering::Equal => self.1.partial_cmp(&other.1),
+            o => Some(o),
+        }
+    }
+}
+
+// Min-heap entry for Dijkstra
+#[derive(Debug, PartialEq)]
+struct State<'a> {
+    cost: Cost,
+    node: &'a str,
+}
+
+impl<'a> Eq for State<'a> {}
+
+impl<'a> Ord for State<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.node.cmp(other.node))
+    }
+}
+
+impl<'a> PartialOrd for State<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{precision, Graph};
+
+    fn sample() -> Graph {
+        let mut g = Graph::new();
+        g.insert("acre", "square meter", 4046.873);
+        g.insert("acre", "hectare", 0.4047);
+        g.insert("square meter", "square foot", 10.76391);
+        g.insert("hectare", "square meter", 10000.0);
+        g
+    }
+
+    #[test]
+    fn inverse_edges() {
+        let g = sample();
+        let c = g.path("square meter", "acre").unwrap();
+        assert!((c.factor - 1.0 / 4046.873).abs() < 1.0e-12);
+        assert_eq!(c.path, vec!["square meter", "acre"]);
+    }
+
+    #[test]
+    fn multi_hop() {
+        let g = sample();
+        let c = g.path("acre", "square foot").unwrap();
+        assert!((c.factor - 43560.0).abs() < 0.5);
+        assert_eq!(c.path, vec!["acre", "square meter", "square foot"]);
+    }
+
+    #[test]
+    fn prefers_precise_edges() {
+        let mut g = sample();
+        g.insert("square meter", "acre", 0.000247);
+        let c = g.path("square meter", "acre").unwrap();
+        assert!((c.factor - 1.0 / 4046.873).abs() < 1.0e-12);
+        // two hops through square meters beat two hops through hectares,
+        // which carries a 4 digit factor
+        g.insert("hectare", "square foot", 107600.0);
+        let c = g.path("acre", "square foot").unwrap();
+        assert_eq!(c.path, vec!["acre", "square meter", "square foot"]);
+    }
+
+    #[test]
+    fn prefers_fewer_hops() {
+        let mut g = sample();
+        g.insert("acre", "square foot", 43560.0);
+        let c = g.path("acre", "square foot").unwrap();
+        assert_eq!(c.path, vec!["acre", "square foot"]);
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut g = sample();
+        g.insert("second", "minute", 1.0 / 60.0);
+        assert!(g.path("acre", "second").is_none());
+        assert!(g.path("acre", "nonsense").is_none());
+    }
+
+    #[test]
+    fn significant_figures() {
+        assert!((precision(0.4047) - 0.5e-3).abs() < 1e-12);
+        assert!(precision(4046.873) < 1.0e-6);
+        assert!(precision(12.0) < precision(4046.873));
+        assert!(precision(0.001) < precision(4046.873));
+        assert!(precision(1200000.0) > precision(4046.873));
+    }
+}
@@ -1,3 +1,95 @@
 //! Units contains utilities for converting units of measure
 
+use self::category::Category;
+use self::dimension::Dimension;
+use std::error::Error;
+use std::fmt;
+
+pub mod affine;
+pub mod audit;
+pub mod category;
 pub mod conversions;
+pub mod dimension;
+pub mod expression;
+pub mod graph;
+pub mod names;
+pub mod notation;
+pub mod quantity;
+pub mod si;
+
+/// UnitError is returned when a unit name cannot be resolved or when two
+/// units cannot be converted between.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnitError {
+    /// The name did not match any unit in the table. `suggestions` holds the
+    /// closest known unit ids, best first, and may be empty.
+    UnknownUnit {
+        name: String,
+        suggestions: Vec<String>,
+    },
+    /// Both units are known, but no chain of factors connects them.
+    NoConversion { from: String, to: String },
+    /// The units measure different things, ie an area and a time, so no
+    /// conversion between them makes sense.
+    CategoryMismatch {
+        from: String,
+        from_category: Category,
+        to: String,
+        to_category: Category,
+    },
+    /// The unit is known but measures the wrong kind of quantity for the
+    /// operation, ie a length passed as a temperature.
+    WrongKind { name: String, expected: String },
+    /// Two quantities were combined, or a quantity was expressed in a unit,
+    /// with incompatible dimensions.
+    DimensionMismatch {
+        expected: Dimension,
+        found: Dimension,
+    },
+    /// A unit expression could not be parsed.
+    InvalidExpression { expression: String, reason: String },
+    /// A length, station, bearing or slope could not be read. `expected`
+    /// describes the notation with an example.
+    InvalidNotation { text: String, expected: String },
+}
+
+impl fmt::Display for UnitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnitError::UnknownUnit { name, suggestions } => {
+                write!(f, "unknown unit {:?}", name)?;
+                if !suggestions.is_empty() {
+                    write!(f, "; did you mean {}?", suggestions.join(", "))?;
+                }
+                Ok(())
+            }
+            UnitError::NoConversion { from, to } => {
+                write!(f, "no conversion from {:?} to {:?}", from, to)
+            }
+            UnitError::CategoryMismatch {
+                from,
+                from_category,
+                to,
+                to_category,
+            } => write!(
+                f,
+                "cannot convert {:?} ({}) to {:?} ({})",
+                from, from_category, to, to_category
+            ),
+            UnitError::WrongKind { name, expected } => {
+                write!(f, "{:?} is not a unit of {}", name, expected)
+            }
+            UnitError::DimensionMismatch { expected, found } => {
+                write!(f, "expected dimension {}, found {}", expected, found)
+            }
+            UnitError::InvalidExpression { expression, reason } => {
+                write!(f, "invalid unit expression {:?}: {}", expression, reason)
+            }
+            UnitError::InvalidNotation { text, expected } => {
+                write!(f, "could not read {:?}; expected {}", text, expected)
+            }
+        }
+    }
+}
+
+impl Error for UnitError {}
@@ -0,0 +1,716 @@
+//! # names
+//!
+//! Normalization of unit names. The keys in the conversion table were scraped
+//! from a few different sources, so the same unit shows up under several
+//! spellings: `"square  foot"` and `"square foot"`, `"foot (English
+//! Imperial)"` and `"foot"`, `"revs per minch"` and `"revolution per minute"`.
+//! Everything in here boils a name down to a single canonical unit id so
+//! that callers can type what they would write on a plan sheet.
+//!
+//! Names are matched in lower case, except for prefixed SI symbols, whose
+//! case matters: `"MPa"` is a megapascal and `"mPa"` a millipascal.
+//!
+//! A canonical id follows the same conventions as the table keys: singular,
+//! lower case, exponents named first ("square foot"), quotients written with
+//! "per" and products hyphenated ("foot-pound"). Parenthesized qualifiers are
+//! kept only when they distinguish one unit from another, so
+//! `"foot (US survey)"` stays `"foot (us survey)"` while `"pound ( lb )"`
+//! becomes `"pound"`.
+//!
+//! ```rust
+//! use civil::units::names::normalize;
+//!
+//! assert_eq!(normalize("Sq. Ft"), "square foot");
+//! assert_eq!(normalize("cfs"), "cubic foot per second");
+//! assert_eq!(normalize("foot (English Imperial)"), "foot");
+//! ```
+
+/// normalize reduces a unit name to its canonical id. Normalization never
+/// fails; a name that is not a known unit simply normalizes to a tidy
+/// version of itself which will not be found in the table.
+pub fn normalize(name: &str) -> String {
+    let (base, groups) = split_qualifiers(name);
+    // Some sources put the noun first: "acceleration, standard"
+    let base = match base.split_once(',') {
+        Some((a, b)) => {
+            let (a, b) = (normalize_base(a), normalize_base(b));
+            if a == b {
+                a
+            } else {
+                normalize_base(&format!("{} {}", b, a))
+            }
+        }
+        None => normalize_base(&base),
+    };
+    let mut quals: Vec<String> = Vec::new();
+    // "imperial gallon" is a "gallon (imperial)"
+    let base = match base.strip_prefix("imperial ") {
+        Some(rest) => {
+            quals.push("imperial".to_string());
+            rest.to_string()
+        }
+        None => base,
+    };
+    // "(US, liquid)" is two qualifiers
+    for g in groups.iter().flat_map(|g| g.split(',')) {
+        if let Some(q) = normalize_qualifier(g, &base) {
+            if !quals.contains(&q) {
+                quals.push(q);
+            }
+        }
+    }
+    // A ton with nothing to say which is the short ton
+    if base == "ton" && quals.is_empty() {
+        quals.push("short".to_string());
+    }
+    if quals.is_empty() {
+        base
+    } else {
+        format!("{} ({})", base, quals.join(", "))
+    }
+}
+
+/// distance is the Levenshtein edit distance between two names. It is used
+/// to suggest known units when a lookup misses.
+pub fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let sub = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + sub).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// closest returns up to `n` candidates nearest to `name`, best first. Names
+/// which contain the query outright are ranked ahead of mere typos.
+pub fn closest<'a, I>(name: &str, candidates: I, n: usize) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let limit = (name.chars().count() / 3).max(2);
+    let mut scored: Vec<(usize, usize, &str)> = candidates
+        .into_iter()
+        .filter_map(|c| {
+            let d = if !name.is_empty() && c.contains(name) {
+                0
+            } else {
+                distance(name, c)
+            };
+            if d <= limit {
+                Some((d, c.len(), c))
+            } else {
+                None
+            }
+        })
+        .collect();
+    scored.sort();
+    scored.dedup();
+    scored
+        .into_iter()
+        .take(n)
+        .map(|(_, _, c)| c.to_string())
+        .collect()
+}
+
+// Pull every parenthesized group out of a name. Whatever is left over is the
+// base name. Unbalanced parentheses are treated as running to the end.
+fn split_qualifiers(name: &str) -> (String, Vec<String>) {
+    let mut base = String::new();
+    let mut groups = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in name.chars() {
+        match c {
+            '(' | '[' => {
+                if depth == 0 {
+                    base.push(' ');
+                    current.clear();
+                } else {
+                    current.push(' ');
+                }
+                depth += 1;
+            }
+            ')' | ']' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    groups.push(current.clone());
+                    base.push(' ');
+                } else {
+                    current.push(' ');
+                }
+            }
+            _ if depth > 0 => current.push(c),
+            _ => base.push(c),
+        }
+    }
+    if depth > 0 {
+        groups.push(current);
+    }
+    (base, groups)
+}
+
+fn normalize_base(base: &str) -> String {
+    // Case is kept until each word is looked at, since "Mm" and "mm" are
+    // different units
+    let text = base
+        .replace('/', " per ")
+        .replace('.', " ")
+        .replace('²', "2")
+        .replace('³', "3");
+    // Hyphens join the factors of a product, so whitespace around them is
+    // noise ("centimeter -dyne").
+    let mut words: Vec<String> = Vec::new();
+    for raw in text.split_whitespace() {
+        if raw.starts_with('-') || raw.starts_with('^') {
+            if let Some(last) = words.last_mut() {
+                last.push_str(raw);
+                continue;
+            }
+        }
+        if words.last().is_some_and(|w| w.ends_with('-')) {
+            words.last_mut().unwrap().push_str(raw);
+            continue;
+        }
+        words.push(raw.to_string());
+    }
+
+    let mut tokens: Vec<String> = Vec::new();
+    for w in words.iter() {
+        let mut parts: Vec<String> = w
+            .split('-')
+            .filter(|p| !p.is_empty())
+            .map(normalize_part)
+            .collect();
+        if parts.is_empty() {
+            continue;
+        }
+        if parts.len() > 1 {
+            // Products name the pound plainly ("foot-lbf" is a
+            // "foot-pound"), and moments name the force first.
+            for p in parts.iter_mut().filter(|p| *p == "pound force") {
+                *p = "pound".to_string();
+            }
+            if let [length, force] = parts.as_slice() {
+                if (length == "inch" && force == "pound")
+                    || ((length == "foot" || length == "inch") && force == "kip")
+                {
+                    parts.swap(0, 1);
+                }
+            }
+        }
+        tokens.push(parts.join("-"));
+    }
+    // "kilo pascal" is a kilopascal
+    let tokens: Vec<String> = tokens
+        .join(" ")
+        .replace("kilo ", "kilo")
+        .split_whitespace()
+        .map(|t| t.to_string())
+        .collect();
+    let tokens = reorder_powers(tokens);
+    // "degree fahrenheit" is just "fahrenheit", and a "gram calorie" the
+    // calorie
+    let tokens = match tokens.as_slice() {
+        [degree, scale] if degree == "degree" && TEMPERATURES.contains(&scale.as_str()) => {
+            vec![scale.clone()]
+        }
+        [gram, calorie] if gram == "gram" && calorie == "calorie" => vec![calorie.clone()],
+        _ => tokens,
+    };
+
+    // Pressures are named after the pound, which there means pound-force,
+    // while a pound of water is a mass.
+    let joined = tokens
+        .join(" ")
+        .replace("pound force per square ", "pound per square ")
+        .replace("pound force of ", "pound of ");
+
+    // Some scraped names repeat themselves, e.g. "foot or foot"
+    if let Some(i) = joined.find(" or ") {
+        let (a, b) = (&joined[..i], &joined[i + 4..]);
+        if a == b {
+            return a.to_string();
+        }
+    }
+    joined
+}
+
+// Normalize one factor of a product. SI symbols are matched with their
+// case ("mPa", "MPa"), anything else in lower case.
+fn normalize_part(part: &str) -> String {
+    let stem = part.trim_end_matches(['2', '3']).trim_end_matches('^');
+    if let Some(name) = symbol(stem) {
+        match &part[stem.len()..] {
+            "" => return name,
+            "2" | "^2" => return format!("square {}", name),
+            "3" | "^3" => return format!("cubic {}", name),
+            _ => {}
+        }
+    }
+    normalize_word(&part.to_lowercase())
+}
+
+// SI prefixes: symbol, name and size
+const PREFIXES: [(&str, &str, f64); 9] = [
+    ("p", "pico", 1.0e-12),
+    ("n", "nano", 1.0e-9),
+    ("µ", "micro", 1.0e-6),
+    ("m", "milli", 1.0e-3),
+    ("c", "centi", 1.0e-2),
+    ("d", "deci", 1.0e-1),
+    ("k", "kilo", 1.0e3),
+    ("M", "mega", 1.0e6),
+    ("G", "giga", 1.0e9),
+];
+
+// SI units which take a prefix: symbol and canonical id
+const SYMBOLS: [(&str, &str); 12] = [
+    ("m", "meter"),
+    ("g", "gram"),
+    ("s", "second"),
+    ("L", "liter"),
+    ("l", "liter"),
+    ("Pa", "pascal"),
+    ("N", "newton"),
+    ("J", "joule"),
+    ("W", "watt"),
+    ("Hz", "hertz"),
+    ("V", "volt"),
+    ("A", "ampere"),
+];
+
+// The name of a prefixed SI symbol, ie "kilopascal" for "kPa". Bare
+// symbols are left to `alias`.
+fn symbol(word: &str) -> Option<String> {
+    PREFIXES.iter().find_map(|(p, prefix, _)| {
+        let rest = word.strip_prefix(p)?;
+        SYMBOLS
+            .iter()
+            .find(|(s, _)| *s == rest)
+            .map(|(_, name)| format!("{}{}", prefix, name))
+    })
+}
+
+/// prefixed splits an SI prefix off a canonical id, giving the size of the
+/// prefix and the unit it is applied to: "megameter" is 1e6 "meter". Only
+/// the coherent SI units, the gram and the liter take a prefix.
+///
+/// ```rust
+/// use civil::units::names::prefixed;
+///
+/// assert_eq!(prefixed("millipascal"), Some((1.0e-3, "pascal")));
+/// assert_eq!(prefixed("kilofoot"), None);
+/// ```
+pub fn prefixed(id: &str) -> Option<(f64, &str)> {
+    PREFIXES.iter().find_map(|(_, prefix, size)| {
+        let rest = id.strip_prefix(prefix)?;
+        SYMBOLS
+            .iter()
+            .find(|(_, name)| *name == rest)
+            .map(|_| (*size, rest))
+    })
+}
+
+// Normalize one word of a base name, which may expand into several words
+// (ie "psi" -> "pound per square inch").
+fn normalize_word(word: &str) -> String {
+    if let Some(a) = alias(word) {
+        return a.to_string();
+    }
+    // Trailing exponents: "ft2", "m^3"
+    let trimmed = word.trim_end_matches(['2', '3']);
+    if trimmed.len() < word.len() && trimmed.len() + 2 >= word.len() {
+        let power = if word.ends_with('2') {
+            "square"
+        } else {
+            "cubic"
+        };
+        let stem = trimmed.trim_end_matches('^');
+        if !stem.is_empty() && stem.chars().all(|c| c.is_alphabetic()) {
+            return format!("{} {}", power, normalize_word(stem));
+        }
+    }
+    let single = singular(word);
+    match alias(&single) {
+        Some(a) => a.to_string(),
+        None => single,
+    }
+}
+
+// Strip plurals. Short words are left alone because they are nearly always
+// abbreviations ("gas", "cfs") which are handled as aliases.
+fn singular(word: &str) -> String {
+    match word {
+        "feet" => return "foot".to_string(),
+        "inches" => return "inch".to_string(),
+        "henries" => return "henry".to_string(),
+        "celsius" | "gauss" | "siemens" | "series" | "lux" => return word.to_string(),
+        _ => {}
+    }
+    if word.len() > 3
+        && !word.ends_with("'s")
+        && word.ends_with('s')
+        && !word.ends_with("ss")
+        && !word.ends_with("us")
+        && !word.ends_with("is")
+    {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+// Postfix powers ("meter square per sec") and repeated denominators
+// ("foot per sec per sec") are rewritten with the exponent named first.
+fn reorder_powers(tokens: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let t = tokens[i].as_str();
+        let next_is_unit = tokens.get(i + 1).is_some_and(|n| n != "per");
+        if (t == "square" || t == "squared") && !out.is_empty() && !next_is_unit {
+            let last = out.pop().unwrap();
+            if last == "square" || last == "cubic" || last == "per" {
+                out.push(last);
+                out.push("square".to_string());
+            } else {
+                out.push("square".to_string());
+                out.push(last);
+            }
+        } else if t == "per"
+            && out.len() >= 2
+            && out[out.len() - 2] == "per"
+            && tokens.get(i + 1) == out.last()
+        {
+            let last = out.pop().unwrap();
+            out.push("square".to_string());
+            out.push(last);
+            i += 1;
+        } else if t == "squared" {
+            out.push("square".to_string());
+        } else {
+            out.push(tokens[i].clone());
+        }
+        i += 1;
+    }
+    out
+}
+
+fn normalize_qualifier(q: &str, base: &str) -> Option<String> {
+    let q = q.to_lowercase();
+    let q = q.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if q.is_empty() || is_noise(&q) {
+        return None;
+    }
+    // A qualifier that restates the base name ("pound ( lb )") adds nothing.
+    if normalize_base(&q) == base {
+        return None;
+    }
+    if let Some(s) = qualifier_synonym(&q) {
+        return if s.is_empty() {
+            None
+        } else {
+            Some(s.to_string())
+        };
+    }
+    let mut words: Vec<&str> = Vec::new();
+    for w in q.split_whitespace() {
+        let w = match w {
+            "us" | "usa" | "liq" | "liquid" | "statute" | "stat" | "avoirdupois" | "english"
+            | "international" | "int" | "table" | "absolute" | "time" | "avoirdupoi" => continue,
+            "brit" | "british" | "uk" | "imp" | "imperial" => "imperial",
+            "naut" => "nautical",
+            "thermo" => "thermochemical",
+            "angles" => "angle",
+            w => w,
+        };
+        if !words.contains(&w) {
+            words.push(w);
+        }
+    }
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+// Qualifiers which are symbols, abbreviations or editorial notes rather than
+// anything that changes the size of the unit.
+fn is_noise(q: &str) -> bool {
+    const NOTES: [&str; 10] = [
+        "see ",
+        "more",
+        "medical",
+        "singular",
+        "calc ",
+        "alternative",
+        "not ",
+        "=",
+        " per",
+        "short for",
+    ];
+    if NOTES.iter().any(|n| q.contains(n)) || q.starts_with("to ") {
+        return true;
+    }
+    // Temperatures ("0°c") are short but they matter
+    let short = |s: &str| {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        s.chars().count() <= 3
+            && !s.chars().any(|c| c.is_ascii_digit())
+            && !KEEP.contains(&s.as_str())
+    };
+    if q.split(" or ").all(short) {
+        return true;
+    }
+    const SYMBOLS: [&str; 6] = [
+        "kwhr",
+        "circ",
+        "pa-s",
+        "lbs",
+        "ozs",
+        "second moment of area",
+    ];
+    SYMBOLS.contains(&q)
+}
+
+// Temperature scales, which drop a leading "degree"
+const TEMPERATURES: [&str; 4] = ["kelvin", "celsius", "fahrenheit", "rankine"];
+
+// Short qualifiers which are meaningful and must survive `is_noise`
+const KEEP: [&str; 8] = ["us", "uk", "usa", "imp", "dry", "oil", "int", "liq"];
+
+fn qualifier_synonym(q: &str) -> Option<&'static str> {
+    let s = match q {
+        "us survey" | "us survey foot" => "us survey",
+        "surveyor's or gunter's" | "gunter's" | "surveyor's" => "gunter's",
+        "engineer or ramden's" | "engineer's" => "engineer's",
+        "english imperial" | "international table" | "mean of 4 year period" => "",
+        "international of 1948" | "international 1948" => "international 1948",
+        "tonne" | "metric" | "tonne metric" => "metric",
+        _ => return None,
+    };
+    Some(s)
+}
+
+// Abbreviations, misspellings and scraping artifacts, keyed by lower case
+// word.
+fn alias(word: &str) -> Option<&'static str> {
+    let a = match word {
+        // length
+        "ft" | "foot" => "foot",
+        "in" | "ins" => "inch",
+        "yd" | "yds" => "yard",
+        "mi" => "mile",
+        "astromical" => "astronomical",
+        "m" | "metre" => "meter",
+        "km" | "kilometre" => "kilometer",
+        "cm" | "centimetre" => "centimeter",
+        "mm" | "millimetre" => "millimeter",
+        "chainch" => "chain",
+        // area
+        "ac" => "acre",
+        "ha" => "hectare",
+        "sq" => "square",
+        "sf" => "square foot",
+        // volume
+        "cu" => "cubic",
+        "cc" => "cubic centimeter",
+        "cf" => "cubic foot",
+        "cy" => "cubic yard",
+        "l" | "litre" | "liter" => "liter",
+        "ml" | "millilitre" | "millitre" => "milliliter",
+        "centilitre" => "centiliter",
+        "decilitre" => "deciliter",
+        "dekalitre" => "dekaliter",
+        "hectolitre" => "hectoliter",
+        "kilolitre" => "kiloliter",
+        "microlitre" => "microliter",
+        "gal" | "gals" => "gallon",
+        "ac-ft" => "acre-foot",
+        // flow
+        "cfs" => "cubic foot per second",
+        "cfm" => "cubic foot per minute",
+        "gpm" => "gallon per minute",
+        "gpd" => "gallon per day",
+        "mgd" => "million gallon per day",
+        "cms" => "cubic meter per second",
+        "lps" => "liter per second",
+        // mass and force
+        // a plain pound is the mass, see `units::conversions`
+        "lb" | "lbs" | "lbm" => "pound",
+        "lbf" => "pound force",
+        "kg" | "kgs" => "kilogram",
+        "kiloram" => "kilogram",
+        "g" | "gm" => "gram",
+        "mg" => "milligram",
+        "oz" => "ounce",
+        "n" => "newton",
+        "kn" => "kilonewton",
+        "kip" | "kips" => "kip",
+        // pressure
+        "pa" => "pascal",
+        "kpa" => "kilopascal",
+        "mpa" => "megapascal",
+        "psi" => "pound per square inch",
+        "psf" => "pound per square foot",
+        "pcf" => "pound force per cubic foot",
+        "ksi" => "kip per square inch",
+        "ksf" => "kip per square foot",
+        "atm" | "amosphere" => "atmosphere",
+        "psia" => "pound per square inch",
+        // temperature
+        "k" | "°k" | "degk" => "kelvin",
+        "°c" | "degc" | "centigrade" => "celsius",
+        "°f" | "degf" => "fahrenheit",
+        "°r" | "degr" => "rankine",
+        // time
+        "s" | "sec" | "secs" => "second",
+        "min" | "mins" | "minch" => "minute",
+        "h" | "hr" | "hrs" => "hour",
+        "d" => "day",
+        "yr" | "yrs" => "year",
+        // velocity and rotation
+        "mph" => "mile per hour",
+        "fps" => "foot per second",
+        "fpm" => "foot per minute",
+        "kph" => "kilometer per hour",
+        "rpm" => "revolution per minute",
+        "rev" | "revs" => "revolution",
+        "hz" => "hertz",
+        // energy and power
+        "j" => "joule",
+        "kj" => "kilojoule",
+        "w" => "watt",
+        "kw" => "kilowatt",
+        "hp" => "horsepower",
+        "cal" => "calorie",
+        "kwh" => "kilowatt-hour",
+        // electromagnetism and the rest of the scraped table
+        "us" | "statute" => "",
+        "imp" => "imperial",
+        "celsiu" => "celsius",
+        "gaus" | "gause" | "gausse" => "gauss",
+        "henrie" => "henry",
+        "millihenrie" => "millihenry",
+        "siemen" => "siemens",
+        "franklinch" => "franklin",
+        "kelvinch" => "kelvin",
+        _ => return None,
+    };
+    Some(a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closest, distance, normalize};
+
+    #[test]
+    fn whitespace_and_case() {
+        assert_eq!(normalize("square  foot"), "square foot");
+        assert_eq!(normalize("  Square Foot "), "square foot");
+        assert_eq!(normalize("centimeter -dyne"), "centimeter-dyne");
+    }
+
+    #[test]
+    fn plurals_and_abbreviations() {
+        let table = [
+            ("feet", "foot"),
+            ("Inches", "inch"),
+            ("ft", "foot"),
+            ("m", "meter"),
+            ("metres", "meter"),
+            ("sq ft", "square foot"),
+            ("ft^2", "square foot"),
+            ("m³", "cubic meter"),
+            ("psi", "pound per square inch"),
+            ("cfs", "cubic foot per second"),
+            ("gallons per minute", "gallon per minute"),
+            ("revs per minch", "revolution per minute"),
+            ("foot-lb", "foot-pound"),
+            ("ft/s", "foot per second"),
+            ("lb/ft3", "pound per cubic foot"),
+            ("lbm/ft3", "pound per cubic foot"),
+            ("pcf", "pound force per cubic foot"),
+            ("lb/in^2", "pound per square inch"),
+            ("lb", "pound"),
+            ("lbf", "pound force"),
+            ("lbm", "pound"),
+            ("in-lb", "pound-inch"),
+            ("lb-in", "pound-inch"),
+            ("ft-kip", "kip-foot"),
+            ("mPa", "millipascal"),
+            ("MPa", "megapascal"),
+            ("Mm", "megameter"),
+            ("Mm2", "square megameter"),
+            ("kN-m", "kilonewton-meter"),
+            ("ton", "ton (short)"),
+            ("tons", "ton (short)"),
+            ("ton (long)", "ton (long)"),
+            ("ksf", "kip per square foot"),
+            ("Hz", "hertz"),
+            ("imperial gallon", "gallon (imperial)"),
+            ("imp gal", "gallon (imperial)"),
+            ("inch-pound", "pound-inch"),
+            ("foot-kip", "kip-foot"),
+        ];
+        for (raw, expected) in table.iter() {
+            assert_eq!(&normalize(raw), expected, "normalizing {:?}", raw);
+        }
+    }
+
+    #[test]
+    fn powers_are_named_first() {
+        assert_eq!(normalize("meter per sec square"), "meter per square second");
+        assert_eq!(normalize("foot per sec per sec"), "foot per square second");
+        assert_eq!(normalize("meter square per sec"), "square meter per second");
+    }
+
+    #[test]
+    fn qualifiers() {
+        assert_eq!(normalize("foot (English Imperial)"), "foot");
+        assert_eq!(normalize("pound ( lb )"), "pound");
+        assert_eq!(normalize("pascal (Pa)"), "pascal");
+        assert_eq!(normalize("foot (US survey)"), "foot (us survey)");
+        assert_eq!(normalize("gallon (US)"), "gallon");
+        assert_eq!(normalize("gallon (Brit)"), "gallon (imperial)");
+        assert_eq!(normalize("gallon (liq British imp)"), "gallon (imperial)");
+        assert_eq!(normalize("mile (naut)"), "mile (nautical)");
+        assert_eq!(normalize("mile (statute)"), "mile");
+        assert_eq!(normalize("foot of water(at 4°C)"), "foot of water (at 4°c)");
+        assert_eq!(normalize("foot or foot"), "foot");
+        assert_eq!(normalize("barrel (US, liquid)"), "barrel");
+        assert_eq!(
+            normalize("drachm (fluid, imperial)"),
+            "drachm (fluid, imperial)"
+        );
+    }
+
+    #[test]
+    fn noun_first() {
+        assert_eq!(normalize("acceleration, standard"), "standard acceleration");
+        assert_eq!(normalize("calorie, gram (mean)"), "calorie (mean)");
+        assert_eq!(normalize("lb, lb (short for 'pounds')"), "pound");
+    }
+
+    #[test]
+    fn edit_distance() {
+        assert_eq!(distance("foot", "foot"), 0);
+        assert_eq!(distance("fot", "foot"), 1);
+        assert_eq!(distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn closest_names() {
+        let known = ["foot", "meter", "square foot", "furlong"];
+        let found = closest("fot", known.iter().cloned(), 2);
+        assert_eq!(found[0], "foot");
+        assert!(closest("xyzzy", known.iter().cloned(), 3).is_empty());
+    }
+}
@@ -0,0 +1,499 @@
+//! # notation
+//!
+//! Parsing and formatting of the notations used on plans and in the field:
+//!
+//! - feet and inches: `5'-6 1/2"`
+//! - stations: `12+34.56` (100 ft stations) or `1+234.567` (1 km stations)
+//! - angles and bearings: `45°30'15"`, `N 45°30'15" E`
+//! - slopes: `2:1` (horizontal to vertical), `3%`
+//!
+//! Parsers return SI values (meters, radians, or rise over run), and every
+//! formatter takes the precision it should round to.
+//!
+//! ```rust
+//! use civil::units::notation::{format_feet_inches, parse_feet_inches};
+//!
+//! let m = parse_feet_inches("5'-6 1/2\"").unwrap();
+//! assert!((m - 1.6891).abs() < 1e-12);
+//! assert_eq!(format_feet_inches(m, 16), "5'-6 1/2\"");
+//! assert_eq!(format_feet_inches(m, 1), "5'-7\"");
+//! ```
+
+use super::UnitError;
+use std::f64::consts::PI;
+
+const INCH: f64 = 0.0254;
+const FOOT: f64 = 0.3048;
+
+fn invalid(text: &str, expected: &str) -> UnitError {
+    UnitError::InvalidNotation {
+        text: text.to_string(),
+        expected: expected.to_string(),
+    }
+}
+
+// Unicode primes and doubled single quotes are common in pasted text
+fn tidy(text: &str) -> String {
+    text.trim()
+        .replace(['′', '’'], "'")
+        .replace(['″', '”'], "\"")
+        .replace("''", "\"")
+}
+
+/// parse_feet_inches reads an architectural length such as `5'-6 1/2"`,
+/// `5' 6.5"`, `12'` or `3/4"` and returns meters. A bare number is refused,
+/// since there is no telling whether it is feet or inches.
+pub fn parse_feet_inches(text: &str) -> Result<f64, UnitError> {
+    const EXPECTED: &str = "feet and inches, like 5'-6 1/2\"";
+    let s = tidy(text);
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, s.as_str()),
+    };
+    let (feet, inches) = match s.find('\'') {
+        Some(i) => {
+            let feet: f64 = s[..i].trim().parse().map_err(|_| invalid(text, EXPECTED))?;
+            let rest = s[i + 1..].trim().trim_start_matches('-').trim();
+            let inches = match rest.strip_suffix('"') {
+                Some(inches) => mixed_number(inches).ok_or_else(|| invalid(text, EXPECTED))?,
+                None if rest.is_empty() => 0.0,
+                None => return Err(invalid(text, EXPECTED)),
+            };
+            (feet, inches)
+        }
+        None => match s.strip_suffix('"') {
+            Some(inches) => (
+                0.0,
+                mixed_number(inches).ok_or_else(|| invalid(text, EXPECTED))?,
+            ),
+            None => return Err(invalid(text, EXPECTED)),
+        },
+    };
+    let m = feet * FOOT + inches * INCH;
+    Ok(if negative { -m } else { m })
+}
+
+// A whole number, decimal, fraction or whole number and fraction, with the
+// two separated by a space or hyphen: "6", "6.5", "1/2", "6 1/2", "6-1/2"
+fn mixed_number(text: &str) -> Option<f64> {
+    let parts: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|p| !p.is_empty())
+        .collect();
+    let fraction = |p: &str| -> Option<f64> {
+        let (n, d) = p.split_once('/')?;
+        let (n, d): (f64, f64) = (n.parse().ok()?, d.parse().ok()?);
+        if d == 0.0 {
+            None
+        } else {
+            Some(n / d)
+        }
+    };
+    match parts.as_slice() {
+        [one] if one.contains('/') => fraction(one),
+        [one] => one.parse().ok(),
+        [whole, frac] if !whole.contains('/') => Some(whole.parse::<f64>().ok()? + fraction(frac)?),
+        _ => None,
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// format_feet_inches writes a length in meters as feet and inches rounded
+/// to the nearest `1/denominator` inch, with the fraction reduced: with a
+/// denominator of 16, 1.6891 m is `5'-6 1/2"`.
+///
+/// # Panics
+///
+/// If `denominator` is zero.
+pub fn format_feet_inches(meters: f64, denominator: u32) -> String {
+    assert!(denominator > 0, "denominator must be positive");
+    let den = i64::from(denominator);
+    let n = (meters.abs() / INCH * den as f64).round() as i64;
+    let (feet, rest) = (n / (12 * den), n % (12 * den));
+    let (inches, num) = (rest / den, rest % den);
+    let sign = if meters < 0.0 && n != 0 { "-" } else { "" };
+    if num == 0 {
+        format!("{}{}'-{}\"", sign, feet, inches)
+    } else {
+        let g = gcd(num, den);
+        format!("{}{}'-{} {}/{}\"", sign, feet, inches, num / g, den / g)
+    }
+}
+
+/// Stationing picks the length of one station.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stationing {
+    /// 100 ft stations, written `12+34.56`
+    Feet,
+    /// 1000 m stations, written `1+234.567`
+    Meters,
+}
+
+impl Stationing {
+    // Length of a station in its own units, the size of that unit in meters,
+    // and the number of digits after the plus
+    fn spec(self) -> (f64, f64, usize) {
+        match self {
+            Stationing::Feet => (100.0, FOOT, 2),
+            Stationing::Meters => (1000.0, 1.0, 3),
+        }
+    }
+}
+
+/// parse_station reads a station such as `12+34.56` and returns the distance
+/// along the alignment in meters.
+///
+/// ```rust
+/// use civil::units::notation::{parse_station, Stationing};
+///
+/// let m = parse_station("12+34.56", Stationing::Feet).unwrap();
+/// assert!((m / 0.3048 - 1234.56).abs() < 1e-9);
+/// ```
+pub fn parse_station(text: &str, stationing: Stationing) -> Result<f64, UnitError> {
+    const EXPECTED: &str = "a station, like 12+34.56";
+    let (length, unit, _) = stationing.spec();
+    let s = text.trim();
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (sta, plus) = s.split_once('+').ok_or_else(|| invalid(text, EXPECTED))?;
+    let sta: u64 = sta.trim().parse().map_err(|_| invalid(text, EXPECTED))?;
+    let plus: f64 = plus.trim().parse().map_err(|_| invalid(text, EXPECTED))?;
+    if !(0.0..length).contains(&plus) {
+        return Err(invalid(text, EXPECTED));
+    }
+    let m = (sta as f64 * length + plus) * unit;
+    Ok(if negative { -m } else { m })
+}
+
+/// format_station writes a distance in meters as a station, rounded to
+/// `decimals` places: `format_station(376.2943, Stationing::Feet, 2)` is
+/// `12+34.56`.
+pub fn format_station(meters: f64, stationing: Stationing, decimals: usize) -> String {
+    let (length, unit, digits) = stationing.spec();
+    let scale = 10_i64.pow(decimals as u32);
+    let n = (meters.abs() / unit * scale as f64).round() as i64;
+    let per = length as i64 * scale;
+    let (sta, rest) = (n / per, n % per);
+    let sign = if meters < 0.0 && n != 0 { "-" } else { "" };
+    let plus = if decimals == 0 {
+        format!("{:0w$}", rest, w = digits)
+    } else {
+        format!(
+            "{:0w$}.{:0p$}",
+            rest / scale,
+            rest % scale,
+            w = digits,
+            p = decimals
+        )
+    };
+    format!("{}{}+{}", sign, sta, plus)
+}
+
+/// parse_dms reads an angle in degrees, minutes and seconds and returns
+/// radians. Any of `45°30'15"`, `45-30-15`, `45d30m15s`, `45 30 15.5` or a
+/// plain decimal `45.504` is accepted.
+pub fn parse_dms(text: &str) -> Result<f64, UnitError> {
+    const EXPECTED: &str = "an angle, like 45°30'15\"";
+    let s = tidy(text);
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.as_str()),
+    };
+    let parts: Vec<&str> = s
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .filter(|p| !p.is_empty())
+        .collect();
+    let numbers: Vec<f64> = parts
+        .iter()
+        .map(|p| p.parse::<f64>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid(text, EXPECTED))?;
+    // Anything other than separators between the numbers is a typo
+    let stray = s.chars().any(|c| {
+        !(c.is_ascii_digit() || c == '.' || c.is_whitespace() || "°'\"-dmsDMS".contains(c))
+    });
+    if numbers.is_empty() || numbers.len() > 3 || stray {
+        return Err(invalid(text, EXPECTED));
+    }
+    if numbers[1..].iter().any(|n| *n >= 60.0) {
+        return Err(invalid(text, EXPECTED));
+    }
+    let degrees = numbers
+        .iter()
+        .zip([1.0, 60.0, 3600.0].iter())
+        .map(|(n, d)| n / d)
+        .sum::<f64>();
+    let r = degrees.to_radians();
+    Ok(if negative { -r } else { r })
+}
+
+// Whole degrees, minutes and seconds scaled by 10^decimals, rounded as a
+// whole so that 59.9996" carries into the minutes.
+fn dms(radians: f64, decimals: usize) -> (i64, i64, i64) {
+    let scale = 10_i64.pow(decimals as u32);
+    let n = (radians.abs().to_degrees() * 3600.0 * scale as f64).round() as i64;
+    (n / (3600 * scale), n / (60 * scale) % 60, n % (60 * scale))
+}
+
+fn write_dms(radians: f64, decimals: usize) -> String {
+    let (d, m, s) = dms(radians, decimals);
+    let scale = 10_i64.pow(decimals as u32);
+    if decimals == 0 {
+        format!("{}°{:02}'{:02}\"", d, m, s)
+    } else {
+        format!(
+            "{}°{:02}'{:02}.{:0p$}\"",
+            d,
+            m,
+            s / scale,
+            s % scale,
+            p = decimals
+        )
+    }
+}
+
+/// format_dms writes an angle in radians as degrees, minutes and seconds,
+/// with `decimals` places on the seconds.
+///
+/// ```rust
+/// use civil::units::notation::{format_dms, parse_dms};
+///
+/// let a = parse_dms("45°30'15\"").unwrap();
+/// assert_eq!(format_dms(a, 0), "45°30'15\"");
+/// assert_eq!(format_dms(a, 1), "45°30'15.0\"");
+/// ```
+pub fn format_dms(radians: f64, decimals: usize) -> String {
+    let sign = if radians < 0.0 && dms(radians, decimals) != (0, 0, 0) {
+        "-"
+    } else {
+        ""
+    };
+    format!("{}{}", sign, write_dms(radians, decimals))
+}
+
+/// parse_bearing reads a quadrant bearing such as `N 45°30'15" E` and
+/// returns the azimuth in radians, clockwise from north.
+///
+/// ```rust
+/// use civil::units::notation::parse_bearing;
+///
+/// let az = parse_bearing("S 30°00'00\" W").unwrap();
+/// assert!((az.to_degrees() - 210.0).abs() < 1e-12);
+/// ```
+pub fn parse_bearing(text: &str) -> Result<f64, UnitError> {
+    const EXPECTED: &str = "a bearing, like N 45°30'15\" E";
+    let s = text.trim().to_uppercase();
+    let mut chars = s.chars();
+    let ns = chars.next().ok_or_else(|| invalid(text, EXPECTED))?;
+    let ew = chars.next_back().ok_or_else(|| invalid(text, EXPECTED))?;
+    let angle = parse_dms(chars.as_str()).map_err(|_| invalid(text, EXPECTED))?;
+    if !(0.0..=PI / 2.0 + 1.0e-12).contains(&angle) {
+        return Err(invalid(text, EXPECTED));
+    }
+    let azimuth = match (ns, ew) {
+        ('N', 'E') => angle,
+        ('S', 'E') => PI - angle,
+        ('S', 'W') => PI + angle,
+        ('N', 'W') => 2.0 * PI - angle,
+        _ => return Err(invalid(text, EXPECTED)),
+    };
+    Ok(azimuth.rem_euclid(2.0 * PI))
+}
+
+/// format_bearing writes an azimuth in radians, clockwise from north, as a
+/// quadrant bearing with `decimals` places on the seconds.
+///
+/// ```rust
+/// use civil::units::notation::format_bearing;
+///
+/// assert_eq!(format_bearing(300f64.to_radians(), 0), "N 60°00'00\" W");
+/// ```
+pub fn format_bearing(azimuth: f64, decimals: usize) -> String {
+    let az = azimuth.rem_euclid(2.0 * PI);
+    let (ns, ew, angle) = if az <= PI / 2.0 {
+        ('N', 'E', az)
+    } else if az <= PI {
+        ('S', 'E', PI - az)
+    } else if az <= 1.5 * PI {
+        ('S', 'W', az - PI)
+    } else {
+        ('N', 'W', 2.0 * PI - az)
+    };
+    format!("{} {} {}", ns, write_dms(angle, decimals), ew)
+}
+
+/// parse_slope reads a slope and returns it as rise over run. Ratios are
+/// horizontal to vertical, as side slopes are written on plans, so `2:1` is
+/// 0.5. Either side may be labelled to be explicit: `1V:2H`. Percentages
+/// (`3%`) and plain decimals (`0.03`) are grades.
+///
+/// ```rust
+/// use civil::units::notation::parse_slope;
+///
+/// assert_eq!(parse_slope("2:1").unwrap(), 0.5);
+/// assert_eq!(parse_slope("1V:4H").unwrap(), 0.25);
+/// assert_eq!(parse_slope("3%").unwrap(), 0.03);
+/// ```
+pub fn parse_slope(text: &str) -> Result<f64, UnitError> {
+    const EXPECTED: &str = "a slope, like 2:1 or 3%";
+    let s = text.trim().to_uppercase();
+    let number = |p: &str| -> Result<f64, UnitError> {
+        p.trim()
+            .trim_end_matches(['H', 'V'])
+            .trim()
+            .parse()
+            .map_err(|_| invalid(text, EXPECTED))
+    };
+    if let Some(p) = s.strip_suffix('%') {
+        return Ok(number(p)? / 100.0);
+    }
+    match s.split_once(':') {
+        Some((a, b)) => {
+            let (a_value, b_value) = (number(a)?, number(b)?);
+            let vertical_first = a.trim().ends_with('V') || b.trim().ends_with('H');
+            let (h, v) = if vertical_first {
+                (b_value, a_value)
+            } else {
+                (a_value, b_value)
+            };
+            if h == 0.0 {
+                return Err(invalid(text, EXPECTED));
+            }
+            Ok(v / h)
+        }
+        None => number(&s),
+    }
+}
+
+/// format_slope_ratio writes a grade (rise over run) as a horizontal to
+/// vertical ratio: 0.5 is `2:1`.
+pub fn format_slope_ratio(grade: f64, decimals: usize) -> String {
+    format!("{:.p$}:1", 1.0 / grade, p = decimals)
+}
+
+/// format_slope_percent writes a grade (rise over run) as a percentage:
+/// 0.03 is `3.00%` to two places.
+pub fn format_slope_percent(grade: f64, decimals: usize) -> String {
+    format!("{:.p$}%", grade * 100.0, p = decimals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1.0e-9
+    }
+
+    #[test]
+    fn feet_and_inches() {
+        let table_test = [
+            ("5'-6 1/2\"", 5.0 * 12.0 + 6.5),
+            ("5' 6.5\"", 66.5),
+            ("12'", 144.0),
+            ("12'-0\"", 144.0),
+            ("3/4\"", 0.75),
+            ("6-1/2\"", 6.5),
+            ("-1'-3\"", -15.0),
+            ("0'-11 15/16\"", 11.9375),
+        ];
+        for (text, inches) in table_test.iter() {
+            let m = parse_feet_inches(text).unwrap();
+            assert!(close(m / INCH, *inches), "{}", text);
+        }
+        assert_eq!(
+            parse_feet_inches("5′-6″").unwrap(),
+            parse_feet_inches("5'-6\"").unwrap()
+        );
+        for bad in ["5", "5'6", "a'-3\"", "1/0\"", "1/2 3\""].iter() {
+            assert!(parse_feet_inches(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn feet_and_inches_round_trip() {
+        assert_eq!(format_feet_inches(0.0, 16), "0'-0\"");
+        assert_eq!(format_feet_inches(11.99 * INCH, 16), "1'-0\"");
+        assert_eq!(format_feet_inches(-15.0 * INCH, 8), "-1'-3\"");
+        assert_eq!(format_feet_inches(0.25 * INCH, 16), "0'-0 1/4\"");
+        for text in ["5'-6 1/2\"", "0'-11 15/16\"", "100'-0 3/8\""].iter() {
+            let m = parse_feet_inches(text).unwrap();
+            assert_eq!(format_feet_inches(m, 16), *text);
+        }
+    }
+
+    #[test]
+    fn stations() {
+        let m = parse_station("0+50", Stationing::Feet).unwrap();
+        assert!(close(m, 50.0 * FOOT));
+        let m = parse_station("1+234.567", Stationing::Meters).unwrap();
+        assert!(close(m, 1234.567));
+        assert!(parse_station("12+134.56", Stationing::Feet).is_err());
+        assert!(parse_station("1234.56", Stationing::Feet).is_err());
+        let m = parse_station("12+34.56", Stationing::Feet).unwrap();
+        assert_eq!(format_station(m, Stationing::Feet, 2), "12+34.56");
+        assert_eq!(format_station(m, Stationing::Feet, 0), "12+35");
+        assert_eq!(
+            format_station(99.999 * FOOT, Stationing::Feet, 2),
+            "1+00.00"
+        );
+        assert_eq!(format_station(5.0, Stationing::Meters, 3), "0+005.000");
+        assert_eq!(format_station(-50.0 * FOOT, Stationing::Feet, 1), "-0+50.0");
+    }
+
+    #[test]
+    fn angles() {
+        let a = parse_dms("45°30'15\"").unwrap();
+        assert!(close(a.to_degrees(), 45.5 + 15.0 / 3600.0));
+        assert_eq!(parse_dms("45-30-15").unwrap(), a);
+        assert_eq!(parse_dms("45d30m15s").unwrap(), a);
+        assert!(parse_dms("45°75'").is_err());
+        assert!(parse_dms("45x30").is_err());
+        assert_eq!(format_dms(59.99999f64.to_radians(), 0), "60°00'00\"");
+        assert_eq!(format_dms(-(1.5f64).to_radians(), 0), "-1°30'00\"");
+    }
+
+    #[test]
+    fn bearings() {
+        let table_test = [
+            ("N 45°30'15\" E", 45.5 + 15.0 / 3600.0),
+            ("S 45°30'15\" E", 180.0 - 45.5 - 15.0 / 3600.0),
+            ("s 10 w", 190.0),
+            ("N10-00-00W", 350.0),
+            ("N 0°00'00\" E", 0.0),
+        ];
+        for (text, degrees) in table_test.iter() {
+            let az = parse_bearing(text).unwrap();
+            assert!(close(az.to_degrees(), *degrees), "{}", text);
+        }
+        for bad in ["N 95 E", "E 10 N", "N", "N 10°"].iter() {
+            assert!(parse_bearing(bad).is_err(), "{}", bad);
+        }
+        let az = parse_bearing("S 45°30'15\" E").unwrap();
+        assert_eq!(format_bearing(az, 0), "S 45°30'15\" E");
+        assert_eq!(format_bearing(az, 2), "S 45°30'15.00\" E");
+        assert_eq!(format_bearing(-(PI / 2.0), 0), "S 90°00'00\" W");
+    }
+
+    #[test]
+    fn slopes() {
+        assert_eq!(parse_slope("3:1").unwrap(), 1.0 / 3.0);
+        assert_eq!(parse_slope("2H:1V").unwrap(), 0.5);
+        assert_eq!(parse_slope("0.5%").unwrap(), 0.005);
+        assert_eq!(parse_slope("0.02").unwrap(), 0.02);
+        assert!(parse_slope("0:1").is_err());
+        assert!(parse_slope("steep").is_err());
+        assert_eq!(format_slope_ratio(0.5, 0), "2:1");
+        assert_eq!(format_slope_ratio(0.4, 1), "2.5:1");
+        assert_eq!(format_slope_percent(0.03, 2), "3.00%");
+    }
+}
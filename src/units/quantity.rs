@@ -0,0 +1,429 @@
+//! # quantity
+//!
+//! A value with its dimension attached. A `Quantity` stores its value in
+//! coherent SI units along with a `Dimension`, so quantities written in any
+//! units can be combined. Adding a length to an area or reading a pressure
+//! out in feet is caught instead of silently producing garbage.
+//!
+//! Quantities come in two flavours. `Quantity` (or `Quantity<kind::Any>`)
+//! checks dimensions at run time and is what multiplication and division
+//! produce. The typed aliases, `Length`, `Force`, `Pressure`, `Flow` and so
+//! on, fix the dimension in the type. A function taking a `Pressure` for an
+//! elastic modulus can't be handed an `Area` at all. `to_kind` moves from the
+//! first kind to the second once the dimensions have been checked.
+//!
+//! ```rust
+//! use civil::units::quantity::{Area, Force, Length, Pressure};
+//!
+//! let b = Length::new(12.0, "in").unwrap();
+//! let h = Length::new(2.0, "ft").unwrap();
+//! let p = Force::new(50.0, "kip").unwrap();
+//! let stress: Pressure = (p / (b * h)).to_kind().unwrap();
+//! assert!((stress.value_in("psi").unwrap() - 50_000.0 / 288.0).abs() < 1e-9);
+//!
+//! // the dimensions have to agree
+//! assert!(Area::new(1.0, "ft").is_err());
+//! let wrong: Result<Pressure, _> = (b * h).to_kind();
+//! assert!(wrong.is_err());
+//! ```
+//!
+//! Temperatures in a `Quantity` are differences. Use
+//! `Table::temperature` for readings.
+
+use super::conversions::table;
+use super::dimension::Dimension;
+use super::UnitError;
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Kind names what a `Quantity` measures.
+pub trait Kind {
+    /// Name used in error messages, ie "pressure"
+    const NAME: &'static str;
+    /// The dimension every quantity of this kind has. `None` for `kind::Any`,
+    /// which accepts any dimension.
+    const DIMENSION: Option<Dimension>;
+}
+
+/// Typed is implemented by every kind with a fixed dimension.
+pub trait Typed: Kind {}
+
+/// Marker types for the kinds of quantity. These only appear as the type
+/// parameter of `Quantity`; use the aliases like `Length` in signatures.
+pub mod kind {
+    use super::{Kind, Typed};
+    use crate::units::dimension::Dimension;
+
+    /// Any dimension, checked at run time
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Any;
+
+    impl Kind for Any {
+        const NAME: &'static str = "quantity";
+        const DIMENSION: Option<Dimension> = None;
+    }
+
+    // name, description, exponents of length, mass, time, temperature
+    macro_rules! kinds {
+        ($($name:ident, $desc:expr, [$l:expr, $m:expr, $t:expr, $k:expr];)*) => {
+            $(
+                #[derive(Debug, Clone, Copy, PartialEq)]
+                pub struct $name;
+
+                impl Kind for $name {
+                    const NAME: &'static str = $desc;
+                    const DIMENSION: Option<Dimension> =
+                        Some(Dimension::new([$l, $m, $t, $k, 0, 0, 0]));
+                }
+
+                impl Typed for $name {}
+            )*
+        };
+    }
+
+    kinds! {
+        Dimensionless, "dimensionless number", [0, 0, 0, 0];
+        Length, "length", [1, 0, 0, 0];
+        Area, "area", [2, 0, 0, 0];
+        Volume, "volume", [3, 0, 0, 0];
+        SecondMoment, "second moment of area", [4, 0, 0, 0];
+        Mass, "mass", [0, 1, 0, 0];
+        Time, "time", [0, 0, 1, 0];
+        Temperature, "temperature difference", [0, 0, 0, 1];
+        Velocity, "velocity", [1, 0, -1, 0];
+        Acceleration, "acceleration", [1, 0, -2, 0];
+        Flow, "flow rate", [3, 0, -1, 0];
+        Force, "force", [1, 1, -2, 0];
+        LineLoad, "force per length", [0, 1, -2, 0];
+        Pressure, "pressure", [-1, 1, -2, 0];
+        Moment, "moment", [2, 1, -2, 0];
+        Energy, "energy", [2, 1, -2, 0];
+        Power, "power", [2, 1, -3, 0];
+        Density, "density", [-3, 1, 0, 0];
+        UnitWeight, "unit weight", [-2, 1, -2, 0];
+    }
+}
+
+pub type Dimensionless = Quantity<kind::Dimensionless>;
+pub type Length = Quantity<kind::Length>;
+pub type Area = Quantity<kind::Area>;
+pub type Volume = Quantity<kind::Volume>;
+/// Second moment of area, ie I for a beam section
+pub type SecondMoment = Quantity<kind::SecondMoment>;
+pub type Mass = Quantity<kind::Mass>;
+pub type Time = Quantity<kind::Time>;
+/// A temperature difference. Readings need an offset; see
+/// `Table::temperature`.
+pub type Temperature = Quantity<kind::Temperature>;
+pub type Velocity = Quantity<kind::Velocity>;
+pub type Acceleration = Quantity<kind::Acceleration>;
+pub type Flow = Quantity<kind::Flow>;
+pub type Force = Quantity<kind::Force>;
+/// Force per unit length, ie a distributed load on a beam
+pub type LineLoad = Quantity<kind::LineLoad>;
+/// Pressure, stress or elastic modulus
+pub type Pressure = Quantity<kind::Pressure>;
+/// Bending moment or torque
+pub type Moment = Quantity<kind::Moment>;
+pub type Energy = Quantity<kind::Energy>;
+pub type Power = Quantity<kind::Power>;
+pub type Density = Quantity<kind::Density>;
+/// Weight per unit volume, ie 62.4 pcf for water
+pub type UnitWeight = Quantity<kind::UnitWeight>;
+
+/// Quantity is a value in coherent SI units with its dimension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity<K: Kind = kind::Any> {
+    value: f64,
+    dimension: Dimension,
+    kind: PhantomData<K>,
+}
+
+impl<K: Kind> Quantity<K> {
+    /// new creates a quantity from a value and the name of its unit, which
+    /// is resolved through the conversion table (see `Table::unit`).
+    /// Typed quantities refuse units of the wrong dimension, after weighing
+    /// any plain pounds (see `Table::unit_as`).
+    pub fn new(value: f64, unit: &str) -> Result<Self, UnitError> {
+        let u = table().unit(unit)?;
+        let u = K::DIMENSION
+            .and_then(|d| table().unit_as(unit, d).ok())
+            .unwrap_or(u);
+        Self::from_si(value * u.factor, u.dimension).map_err(|_| UnitError::WrongKind {
+            name: unit.to_string(),
+            expected: K::NAME.to_string(),
+        })
+    }
+
+    /// from_si creates a quantity from a value already in coherent SI units.
+    pub fn from_si(value: f64, dimension: Dimension) -> Result<Self, UnitError> {
+        match K::DIMENSION {
+            Some(expected) if expected != dimension => Err(UnitError::DimensionMismatch {
+                expected,
+                found: dimension,
+            }),
+            _ => Ok(Quantity {
+                value,
+                dimension,
+                kind: PhantomData,
+            }),
+        }
+    }
+
+    /// value is the magnitude in coherent SI units: meters, kilograms,
+    /// seconds, newtons, pascals and so on.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    /// value_in expresses the quantity in another unit, which must have the
+    /// same dimension.
+    pub fn value_in(&self, unit: &str) -> Result<f64, UnitError> {
+        let u = table().unit_as(unit, self.dimension)?;
+        Ok(self.value / u.factor)
+    }
+
+    /// to_kind checks the dimension of a quantity and retypes it, ie to turn
+    /// the product of two lengths into an `Area`.
+    pub fn to_kind<J: Kind>(self) -> Result<Quantity<J>, UnitError> {
+        Quantity::from_si(self.value, self.dimension)
+    }
+
+    /// into_any forgets the kind, keeping the dimension.
+    pub fn into_any(self) -> Quantity {
+        Quantity {
+            value: self.value,
+            dimension: self.dimension,
+            kind: PhantomData,
+        }
+    }
+
+    /// try_add adds two quantities, failing if their dimensions differ.
+    pub fn try_add(self, other: Self) -> Result<Self, UnitError> {
+        self.check(&other)?;
+        Ok(Quantity {
+            value: self.value + other.value,
+            ..self
+        })
+    }
+
+    /// try_sub subtracts two quantities, failing if their dimensions differ.
+    pub fn try_sub(self, other: Self) -> Result<Self, UnitError> {
+        self.try_add(-other)
+    }
+
+    /// powi raises a quantity to an integer power.
+    pub fn powi(self, n: i8) -> Quantity {
+        Quantity {
+            value: self.value.powi(n.into()),
+            dimension: self.dimension.powi(n),
+            kind: PhantomData,
+        }
+    }
+
+    /// sqrt takes the square root of a quantity, if its dimension has one.
+    pub fn sqrt(self) -> Option<Quantity> {
+        Some(Quantity {
+            value: self.value.sqrt(),
+            dimension: self.dimension.root(2)?,
+            kind: PhantomData,
+        })
+    }
+
+    pub fn abs(self) -> Self {
+        Quantity {
+            value: self.value.abs(),
+            ..self
+        }
+    }
+
+    fn check(&self, other: &Self) -> Result<(), UnitError> {
+        if self.dimension == other.dimension {
+            Ok(())
+        } else {
+            Err(UnitError::DimensionMismatch {
+                expected: self.dimension,
+                found: other.dimension,
+            })
+        }
+    }
+}
+
+impl<K: Typed> Quantity<K> {
+    /// si creates a typed quantity from a value in coherent SI units.
+    ///
+    /// ```rust
+    /// use civil::units::quantity::Length;
+    ///
+    /// let l = Length::si(0.3048);
+    /// assert!((l.value_in("ft").unwrap() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn si(value: f64) -> Self {
+        Quantity {
+            value,
+            dimension: K::DIMENSION.unwrap(),
+            kind: PhantomData,
+        }
+    }
+}
+
+/// Adding quantities of different dimensions panics; use `try_add` when that
+/// can happen. Typed quantities always agree.
+impl<K: Kind> Add for Quantity<K> {
+    type Output = Quantity<K>;
+
+    fn add(self, other: Self) -> Self {
+        self.try_add(other).unwrap()
+    }
+}
+
+/// Subtracting quantities of different dimensions panics; use `try_sub` when
+/// that can happen.
+impl<K: Kind> Sub for Quantity<K> {
+    type Output = Quantity<K>;
+
+    fn sub(self, other: Self) -> Self {
+        self.try_sub(other).unwrap()
+    }
+}
+
+impl<K: Kind> Neg for Quantity<K> {
+    type Output = Quantity<K>;
+
+    fn neg(self) -> Self {
+        Quantity {
+            value: -self.value,
+            ..self
+        }
+    }
+}
+
+impl<K: Kind, J: Kind> Mul<Quantity<J>> for Quantity<K> {
+    type Output = Quantity;
+
+    fn mul(self, other: Quantity<J>) -> Quantity {
+        Quantity {
+            value: self.value * other.value,
+            dimension: self.dimension * other.dimension,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<K: Kind, J: Kind> Div<Quantity<J>> for Quantity<K> {
+    type Output = Quantity;
+
+    fn div(self, other: Quantity<J>) -> Quantity {
+        Quantity {
+            value: self.value / other.value,
+            dimension: self.dimension / other.dimension,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<K: Kind> Mul<f64> for Quantity<K> {
+    type Output = Quantity<K>;
+
+    fn mul(self, k: f64) -> Self {
+        Quantity {
+            value: self.value * k,
+            ..self
+        }
+    }
+}
+
+impl<K: Kind> Mul<Quantity<K>> for f64 {
+    type Output = Quantity<K>;
+
+    fn mul(self, q: Quantity<K>) -> Quantity<K> {
+        q * self
+    }
+}
+
+impl<K: Kind> Div<f64> for Quantity<K> {
+    type Output = Quantity<K>;
+
+    fn div(self, k: f64) -> Self {
+        Quantity {
+            value: self.value / k,
+            ..self
+        }
+    }
+}
+
+/// Quantities of different dimensions are unordered.
+impl<K: Kind + PartialEq> PartialOrd for Quantity<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.dimension != other.dimension {
+            return None;
+        }
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<K: Kind> fmt::Display for Quantity<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.dimension.is_dimensionless() {
+            write!(f, "{}", self.value)
+        } else {
+            write!(f, "{} {}", self.value, self.dimension)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Area, Force, Length, Moment, Quantity};
+    use crate::units::dimension::Dimension;
+    use crate::units::UnitError;
+
+    #[test]
+    fn arithmetic() {
+        let a = Length::new(1.0, "ft").unwrap();
+        let b = Length::new(6.0, "in").unwrap();
+        assert!(((a + b).value_in("in").unwrap() - 18.0).abs() < 1e-12);
+        assert!(((a - b).value() - 0.1524).abs() < 1e-12);
+        let area: Area = (a * b).to_kind().unwrap();
+        assert!((area.value_in("sq ft").unwrap() - 0.5).abs() < 1e-12);
+        assert!(((2.0 * a) / a).dimension().is_dimensionless());
+        assert!(a > b);
+    }
+
+    #[test]
+    fn mismatched_dimensions() {
+        let l = Length::si(1.0).into_any();
+        let f = Force::si(1.0).into_any();
+        match l.try_add(f) {
+            Err(UnitError::DimensionMismatch { expected, found }) => {
+                assert_eq!(expected, Dimension::LENGTH);
+                assert_eq!(found, f.dimension());
+            }
+            other => panic!("expected a dimension mismatch, got {:?}", other),
+        }
+        assert!(l.partial_cmp(&f).is_none());
+        assert!(Length::si(1.0).value_in("kip").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn adding_mismatched_dimensions_panics() {
+        let _ = Length::si(1.0).into_any() + Force::si(1.0).into_any();
+    }
+
+    #[test]
+    fn moments() {
+        let m = Force::new(1.0, "kip").unwrap() * Length::new(1.0, "ft").unwrap();
+        let m: Moment = m.to_kind().unwrap();
+        assert!((m.value_in("kip-foot").unwrap() - 1.0).abs() < 1e-12);
+        assert!((m.value_in("kilonewton-meter").unwrap() - 1.355_817_948).abs() < 1e-9);
+        let q: Quantity = Quantity::new(3.0, "acre").unwrap();
+        assert_eq!(q.sqrt().unwrap().dimension(), Dimension::LENGTH);
+    }
+}
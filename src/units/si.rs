@@ -0,0 +1,982 @@
+//! # si
+//!
+//! Unit definitions in terms of the SI. Every unit here is defined by the
+//! number of coherent SI units it contains, and every factor is built up
+//! from a handful of exact definitions rather than copied from a table:
+//!
+//! - 1 yard = 0.9144 m (international yard and pound agreement, 1959)
+//! - 1 pound = 0.453 592 37 kg (same agreement)
+//! - 1 US survey foot = 1200/3937 m (Mendenhall order, 1893)
+//! - 1 US gallon = 231 cubic inches
+//! - 1 imperial gallon = 4.546 09 L (Weights and Measures Act, 1985)
+//! - standard gravity = 9.806 65 m/s^2 (3rd CGPM, 1901)
+//! - 1 standard atmosphere = 101 325 Pa (10th CGPM, 1954)
+//! - 1 calorie (international table) = 4.1868 J, 1 thermochemical calorie =
+//!   4.184 J
+//!
+//! Some units, like the foot of water, depend on a measured property and are
+//! marked as empirical. Everything else is exact to the precision of an
+//! `f64`, so a conversion between two defined units only ever involves two
+//! multiplications. Units in the conversion table that are not listed here
+//! still get a definition from `Table::definition`, marked as tabulated.
+//!
+//! Unit names are canonical ids as produced by `units::names::normalize`.
+//! Each unit also names the SI unit it is measured against (`base`); only
+//! units with the same base can be converted with a plain factor.
+
+use super::dimension::Dimension;
+use super::graph::EXACT;
+use std::f64::consts::PI;
+
+/// Definition describes one unit in terms of the SI.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Definition<'a> {
+    /// Canonical unit id
+    pub name: &'a str,
+    /// Canonical id of the coherent SI unit this unit is measured against
+    pub base: &'static str,
+    /// Number of `base` units in one of this unit
+    pub si: f64,
+    /// How far `si` can be trusted
+    pub basis: Basis,
+    /// Where the factor comes from
+    pub note: &'static str,
+}
+
+/// Basis says where the factor of a `Definition` comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Basis {
+    /// Follows from definitions alone
+    Exact,
+    /// Depends on a measured quantity such as the density of water or
+    /// mercury
+    Empirical,
+    /// Chained together from the raw conversion table and not checked
+    /// against anything. Only `Table::definition` gives these out; every
+    /// unit in `DEFINITIONS` is exact or empirical.
+    Tabulated,
+}
+
+// Building blocks. Everything below is derived from these. The inch, foot
+// and yard are each written out rather than multiplied up so that each one
+// is the closest f64 to its exact value.
+const INCH: f64 = 0.0254;
+const FOOT: f64 = 0.3048;
+const YARD: f64 = 0.9144;
+const MILE: f64 = 5280.0 * FOOT;
+const SURVEY_FOOT: f64 = 1200.0 / 3937.0;
+const POUND: f64 = 0.453_592_37;
+const GRAVITY: f64 = 9.806_65;
+const POUND_FORCE: f64 = POUND * GRAVITY;
+const KIP: f64 = 1000.0 * POUND_FORCE;
+const GALLON: f64 = 231.0 * INCH * INCH * INCH;
+const IMPERIAL_GALLON: f64 = 4.546_09e-3;
+const ACRE: f64 = 43_560.0 * FOOT * FOOT;
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+const YEAR: f64 = 365.25 * DAY;
+const ATMOSPHERE: f64 = 101_325.0;
+const CALORIE: f64 = 4.1868;
+const BTU: f64 = 1_055.055_852_62;
+// Conventional densities for manometric pressure units, kg/m^3
+const MERCURY: f64 = 13_595.1;
+const WATER_4C: f64 = 999.972;
+
+macro_rules! exact {
+    ($name:expr, $base:expr, $si:expr, $note:expr) => {
+        Definition {
+            name: $name,
+            base: $base,
+            si: $si,
+            basis: Basis::Exact,
+            note: $note,
+        }
+    };
+}
+
+macro_rules! empirical {
+    ($name:expr, $base:expr, $si:expr, $note:expr) => {
+        Definition {
+            name: $name,
+            base: $base,
+            si: $si,
+            basis: Basis::Empirical,
+            note: $note,
+        }
+    };
+}
+
+/// DEFINITIONS lists every unit with an SI definition.
+pub static DEFINITIONS: &[Definition<'static>] = &[
+    // length
+    exact!("meter", "meter", 1.0, "SI base unit"),
+    exact!("kilometer", "meter", 1.0e3, "1000 m"),
+    exact!("centimeter", "meter", 1.0e-2, "0.01 m"),
+    exact!("millimeter", "meter", 1.0e-3, "0.001 m"),
+    exact!("micron", "meter", 1.0e-6, "1 micrometer"),
+    exact!("angstrom", "meter", 1.0e-10, "1e-10 m"),
+    exact!("inch", "meter", INCH, "2.54 cm"),
+    exact!("foot", "meter", FOOT, "0.3048 m"),
+    exact!("yard", "meter", YARD, "0.9144 m"),
+    exact!("mile", "meter", MILE, "5280 ft"),
+    exact!("mil", "meter", INCH / 1000.0, "0.001 in"),
+    exact!("hand", "meter", 4.0 * INCH, "4 in"),
+    exact!("fathom", "meter", 6.0 * FOOT, "6 ft"),
+    exact!("mile (nautical)", "meter", 1852.0, "1852 m"),
+    exact!("nautical mile", "meter", 1852.0, "1852 m"),
+    exact!("foot (us survey)", "meter", SURVEY_FOOT, "1200/3937 m"),
+    exact!(
+        "mile (us survey)",
+        "meter",
+        5280.0 * SURVEY_FOOT,
+        "5280 US survey ft"
+    ),
+    exact!("chain (gunter's)", "meter", 66.0 * FOOT, "66 ft"),
+    exact!(
+        "chain (us survey)",
+        "meter",
+        66.0 * SURVEY_FOOT,
+        "66 US survey ft"
+    ),
+    exact!("chain (engineer's)", "meter", 100.0 * FOOT, "100 ft"),
+    exact!(
+        "link (gunter's)",
+        "meter",
+        0.66 * FOOT,
+        "1/100 Gunter's chain"
+    ),
+    exact!("link (engineer's)", "meter", FOOT, "1/100 engineer's chain"),
+    exact!("rod", "meter", 16.5 * FOOT, "16.5 ft"),
+    exact!("furlong", "meter", 660.0 * FOOT, "660 ft"),
+    exact!("league", "meter", 3.0 * MILE, "3 mi"),
+    exact!("point (printing)", "meter", INCH / 72.0, "1/72 in"),
+    exact!("pica (printing)", "meter", INCH / 6.0, "1/6 in"),
+    exact!("astronomical unit", "meter", 149_597_870_700.0, "IAU 2012"),
+    exact!(
+        "light year",
+        "meter",
+        299_792_458.0 * YEAR,
+        "c times a Julian year"
+    ),
+    // area
+    exact!("square meter", "square meter", 1.0, "SI derived unit"),
+    exact!("square kilometer", "square meter", 1.0e6, "(1000 m)^2"),
+    exact!("square centimeter", "square meter", 1.0e-4, "(0.01 m)^2"),
+    exact!("square millimeter", "square meter", 1.0e-6, "(0.001 m)^2"),
+    exact!("square inch", "square meter", INCH * INCH, "(2.54 cm)^2"),
+    exact!("square foot", "square meter", FOOT * FOOT, "(0.3048 m)^2"),
+    exact!("square yard", "square meter", YARD * YARD, "(0.9144 m)^2"),
+    exact!("square mile", "square meter", MILE * MILE, "(5280 ft)^2"),
+    exact!(
+        "square mil",
+        "square meter",
+        1.0e-6 * INCH * INCH,
+        "(0.001 in)^2"
+    ),
+    exact!(
+        "circular mil",
+        "square meter",
+        PI / 4.0e6 * INCH * INCH,
+        "pi/4 square mil"
+    ),
+    exact!("acre", "square meter", ACRE, "43560 square feet"),
+    exact!("hectare", "square meter", 1.0e4, "10000 square meters"),
+    exact!("are", "square meter", 100.0, "100 square meters"),
+    exact!(
+        "square rod",
+        "square meter",
+        16.5 * 16.5 * FOOT * FOOT,
+        "(16.5 ft)^2"
+    ),
+    // volume
+    exact!("cubic meter", "cubic meter", 1.0, "SI derived unit"),
+    exact!("liter", "cubic meter", 1.0e-3, "1 cubic decimeter"),
+    exact!("milliliter", "cubic meter", 1.0e-6, "1 cubic centimeter"),
+    exact!("cubic centimeter", "cubic meter", 1.0e-6, "(0.01 m)^3"),
+    exact!("cubic millimeter", "cubic meter", 1.0e-9, "(0.001 m)^3"),
+    exact!(
+        "cubic inch",
+        "cubic meter",
+        INCH * INCH * INCH,
+        "(2.54 cm)^3"
+    ),
+    exact!(
+        "cubic foot",
+        "cubic meter",
+        FOOT * FOOT * FOOT,
+        "(0.3048 m)^3"
+    ),
+    exact!(
+        "cubic yard",
+        "cubic meter",
+        YARD * YARD * YARD,
+        "(0.9144 m)^3"
+    ),
+    exact!("acre-foot", "cubic meter", ACRE * FOOT, "43560 cubic feet"),
+    exact!("gallon", "cubic meter", GALLON, "231 cubic inches"),
+    exact!(
+        "million gallon",
+        "cubic meter",
+        1.0e6 * GALLON,
+        "10^6 US gal"
+    ),
+    exact!("quart", "cubic meter", GALLON / 4.0, "1/4 US gal"),
+    exact!("pint", "cubic meter", GALLON / 8.0, "1/8 US gal"),
+    exact!("cup", "cubic meter", GALLON / 16.0, "1/16 US gal"),
+    exact!(
+        "ounce (fluid)",
+        "cubic meter",
+        GALLON / 128.0,
+        "1/128 US gal"
+    ),
+    exact!("tablespoon", "cubic meter", GALLON / 256.0, "1/2 US fl oz"),
+    exact!("teaspoon", "cubic meter", GALLON / 768.0, "1/6 US fl oz"),
+    exact!(
+        "gallon (imperial)",
+        "cubic meter",
+        IMPERIAL_GALLON,
+        "4.54609 L"
+    ),
+    exact!(
+        "quart (imperial)",
+        "cubic meter",
+        IMPERIAL_GALLON / 4.0,
+        "1/4 imp gal"
+    ),
+    exact!(
+        "pint (imperial)",
+        "cubic meter",
+        IMPERIAL_GALLON / 8.0,
+        "1/8 imp gal"
+    ),
+    exact!("barrel (oil)", "cubic meter", 42.0 * GALLON, "42 US gal"),
+    exact!(
+        "bushel",
+        "cubic meter",
+        2150.42 * INCH * INCH * INCH,
+        "2150.42 cubic inches"
+    ),
+    exact!("stere", "cubic meter", 1.0, "1 cubic meter"),
+    exact!(
+        "cord",
+        "cubic meter",
+        128.0 * FOOT * FOOT * FOOT,
+        "128 cubic feet"
+    ),
+    // mass
+    exact!("kilogram", "kilogram", 1.0, "SI base unit"),
+    exact!("gram", "kilogram", 1.0e-3, "0.001 kg"),
+    exact!("milligram", "kilogram", 1.0e-6, "0.001 g"),
+    exact!("microgram", "kilogram", 1.0e-9, "0.000001 g"),
+    exact!("tonne", "kilogram", 1.0e3, "1000 kg"),
+    exact!("ton (metric)", "kilogram", 1.0e3, "1000 kg"),
+    exact!("pound", "kilogram", POUND, "0.45359237 kg"),
+    exact!("ounce", "kilogram", POUND / 16.0, "1/16 lb"),
+    exact!("grain", "kilogram", POUND / 7000.0, "1/7000 lb"),
+    exact!(
+        "ounce (troy)",
+        "kilogram",
+        480.0 * POUND / 7000.0,
+        "480 grains"
+    ),
+    exact!(
+        "pound (troy)",
+        "kilogram",
+        5760.0 * POUND / 7000.0,
+        "5760 grains"
+    ),
+    exact!("stone", "kilogram", 14.0 * POUND, "14 lb"),
+    exact!("ton (short)", "kilogram", 2000.0 * POUND, "2000 lb"),
+    exact!("ton (long)", "kilogram", 2240.0 * POUND, "2240 lb"),
+    exact!("hundredweight (short)", "kilogram", 100.0 * POUND, "100 lb"),
+    exact!("hundredweight (long)", "kilogram", 112.0 * POUND, "112 lb"),
+    exact!("slug", "kilogram", POUND_FORCE / FOOT, "1 lbf s^2/ft"),
+    exact!("carat (metric)", "kilogram", 2.0e-4, "200 mg"),
+    // force
+    exact!("newton", "newton", 1.0, "SI derived unit"),
+    exact!("kilonewton", "newton", 1.0e3, "1000 N"),
+    exact!("meganewton", "newton", 1.0e6, "10^6 N"),
+    exact!("dyne", "newton", 1.0e-5, "1 g cm/s^2"),
+    exact!(
+        "pound force",
+        "newton",
+        POUND_FORCE,
+        "1 lb times standard gravity"
+    ),
+    exact!("kip", "newton", KIP, "1000 lbf"),
+    exact!(
+        "kilogram-force",
+        "newton",
+        GRAVITY,
+        "1 kg times standard gravity"
+    ),
+    exact!(
+        "ton-force (short)",
+        "newton",
+        2000.0 * POUND_FORCE,
+        "2000 lbf"
+    ),
+    exact!("poundal", "newton", POUND * FOOT, "1 lb ft/s^2"),
+    // pressure and stress
+    exact!("pascal", "pascal", 1.0, "SI derived unit"),
+    exact!("kilopascal", "pascal", 1.0e3, "1000 Pa"),
+    exact!("megapascal", "pascal", 1.0e6, "10^6 Pa"),
+    exact!("gigapascal", "pascal", 1.0e9, "10^9 Pa"),
+    exact!("bar", "pascal", 1.0e5, "100 kPa"),
+    exact!("millibar", "pascal", 100.0, "100 Pa"),
+    exact!("atmosphere", "pascal", ATMOSPHERE, "101325 Pa"),
+    exact!("torr", "pascal", ATMOSPHERE / 760.0, "1/760 atm"),
+    exact!(
+        "pound per square inch",
+        "pascal",
+        POUND_FORCE / (INCH * INCH),
+        "1 lbf/in^2"
+    ),
+    exact!(
+        "pound per square foot",
+        "pascal",
+        POUND_FORCE / (FOOT * FOOT),
+        "1 lbf/ft^2"
+    ),
+    exact!(
+        "kip per square inch",
+        "pascal",
+        KIP / (INCH * INCH),
+        "1000 psi"
+    ),
+    exact!(
+        "kip per square foot",
+        "pascal",
+        KIP / (FOOT * FOOT),
+        "1000 psf"
+    ),
+    exact!(
+        "ton per square foot (short)",
+        "pascal",
+        2000.0 * POUND_FORCE / (FOOT * FOOT),
+        "2000 psf"
+    ),
+    exact!(
+        "kilogram-force per square centimeter",
+        "pascal",
+        GRAVITY * 1.0e4,
+        "technical atmosphere"
+    ),
+    exact!(
+        "millimeter of mercury",
+        "pascal",
+        MERCURY * GRAVITY * 1.0e-3,
+        "conventional, 13595.1 kg/m^3"
+    ),
+    exact!(
+        "inch of mercury",
+        "pascal",
+        MERCURY * GRAVITY * INCH,
+        "conventional, 13595.1 kg/m^3"
+    ),
+    exact!(
+        "inch of water (conventional)",
+        "pascal",
+        1000.0 * GRAVITY * INCH,
+        "conventional, 1000 kg/m^3"
+    ),
+    empirical!(
+        "inch of water",
+        "pascal",
+        WATER_4C * GRAVITY * INCH,
+        "water at 4 C, 999.972 kg/m^3"
+    ),
+    empirical!(
+        "foot of water",
+        "pascal",
+        WATER_4C * GRAVITY * FOOT,
+        "water at 4 C, 999.972 kg/m^3"
+    ),
+    empirical!(
+        "meter of water",
+        "pascal",
+        WATER_4C * GRAVITY,
+        "water at 4 C, 999.972 kg/m^3"
+    ),
+    // energy
+    exact!("joule", "joule", 1.0, "SI derived unit"),
+    exact!("kilojoule", "joule", 1.0e3, "1000 J"),
+    exact!("megajoule", "joule", 1.0e6, "10^6 J"),
+    exact!("erg", "joule", 1.0e-7, "1 dyne cm"),
+    exact!("watt-hour", "joule", HOUR, "3600 J"),
+    exact!("kilowatt-hour", "joule", 1000.0 * HOUR, "3.6 MJ"),
+    exact!("calorie", "joule", CALORIE, "international table, 4.1868 J"),
+    exact!("calorie (thermochemical)", "joule", 4.184, "4.184 J"),
+    exact!("kilocalorie", "joule", 1000.0 * CALORIE, "1000 IT calories"),
+    exact!("btu", "joule", BTU, "international table"),
+    exact!("therm", "joule", 1.0e5 * BTU, "100000 Btu"),
+    exact!(
+        "horsepower-hour",
+        "joule",
+        550.0 * FOOT * POUND_FORCE * HOUR,
+        "1 hp for 1 hour"
+    ),
+    exact!(
+        "electronvolt",
+        "joule",
+        1.602_176_634e-19,
+        "SI 2019 definition"
+    ),
+    // moment and torque
+    exact!("newton-meter", "newton-meter", 1.0, "SI derived unit"),
+    exact!("kilonewton-meter", "newton-meter", 1.0e3, "1000 N m"),
+    exact!("pound-foot", "newton-meter", POUND_FORCE * FOOT, "1 lbf ft"),
+    exact!("foot-pound", "newton-meter", FOOT * POUND_FORCE, "1 ft lbf"),
+    exact!("pound-inch", "newton-meter", POUND_FORCE * INCH, "1 lbf in"),
+    exact!("kip-foot", "newton-meter", KIP * FOOT, "1000 lbf ft"),
+    exact!("kip-inch", "newton-meter", KIP * INCH, "1000 lbf in"),
+    exact!("kilogram-force meter", "newton-meter", GRAVITY, "1 kgf m"),
+    // power
+    exact!("watt", "watt", 1.0, "SI derived unit"),
+    exact!("pascal cubic meter per second", "watt", 1.0, "1 W"),
+    exact!("kilowatt", "watt", 1.0e3, "1000 W"),
+    exact!("megawatt", "watt", 1.0e6, "10^6 W"),
+    exact!(
+        "horsepower",
+        "watt",
+        550.0 * FOOT * POUND_FORCE,
+        "550 ft lbf/s"
+    ),
+    exact!("horsepower (metric)", "watt", 75.0 * GRAVITY, "75 kgf m/s"),
+    exact!("horsepower (electric)", "watt", 746.0, "746 W"),
+    empirical!(
+        "horsepower (boiler)",
+        "watt",
+        9809.5,
+        "evaporation of 34.5 lb/h of water at 212 F"
+    ),
+    exact!("btu per hour", "watt", BTU / HOUR, "1 Btu/h"),
+    exact!(
+        "ton refrigeration",
+        "watt",
+        12_000.0 * BTU / HOUR,
+        "12000 Btu/h"
+    ),
+    exact!(
+        "foot-pound per second",
+        "watt",
+        FOOT * POUND_FORCE,
+        "1 ft lbf/s"
+    ),
+    // time
+    exact!("second", "second", 1.0, "SI base unit"),
+    exact!("nanosecond", "second", 1.0e-9, "10^-9 s"),
+    exact!("minute", "second", MINUTE, "60 s"),
+    exact!("hour", "second", HOUR, "60 min"),
+    exact!("day", "second", DAY, "24 h"),
+    exact!("week", "second", 7.0 * DAY, "7 d"),
+    exact!("year", "second", YEAR, "Julian year, 365.25 d"),
+    exact!("month", "second", YEAR / 12.0, "1/12 Julian year"),
+    // velocity
+    exact!(
+        "meter per second",
+        "meter per second",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "kilometer per hour",
+        "meter per second",
+        1000.0 / HOUR,
+        "1 km/h"
+    ),
+    exact!("centimeter per second", "meter per second", 0.01, "1 cm/s"),
+    exact!("foot per second", "meter per second", FOOT, "1 ft/s"),
+    exact!(
+        "foot per minute",
+        "meter per second",
+        FOOT / MINUTE,
+        "1 ft/min"
+    ),
+    exact!("mile per hour", "meter per second", MILE / HOUR, "1 mi/h"),
+    exact!(
+        "knot",
+        "meter per second",
+        1852.0 / HOUR,
+        "1 nautical mile per hour"
+    ),
+    exact!("inch per hour", "meter per second", INCH / HOUR, "1 in/h"),
+    exact!(
+        "millimeter per hour",
+        "meter per second",
+        0.001 / HOUR,
+        "1 mm/h"
+    ),
+    // acceleration
+    exact!(
+        "meter per square second",
+        "meter per square second",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "foot per square second",
+        "meter per square second",
+        FOOT,
+        "1 ft/s^2"
+    ),
+    exact!(
+        "standard gravity",
+        "meter per square second",
+        GRAVITY,
+        "9.80665 m/s^2"
+    ),
+    exact!("galileo", "meter per square second", 0.01, "1 cm/s^2"),
+    // flow
+    exact!(
+        "cubic meter per second",
+        "cubic meter per second",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "cubic meter per hour",
+        "cubic meter per second",
+        1.0 / HOUR,
+        "1 m^3/h"
+    ),
+    exact!(
+        "cubic meter per day",
+        "cubic meter per second",
+        1.0 / DAY,
+        "1 m^3/d"
+    ),
+    exact!(
+        "liter per second",
+        "cubic meter per second",
+        1.0e-3,
+        "1 L/s"
+    ),
+    exact!(
+        "liter per minute",
+        "cubic meter per second",
+        1.0e-3 / MINUTE,
+        "1 L/min"
+    ),
+    exact!(
+        "cubic foot per second",
+        "cubic meter per second",
+        FOOT * FOOT * FOOT,
+        "1 ft^3/s"
+    ),
+    exact!(
+        "cubic foot per minute",
+        "cubic meter per second",
+        FOOT * FOOT * FOOT / MINUTE,
+        "1 ft^3/min"
+    ),
+    exact!(
+        "gallon per minute",
+        "cubic meter per second",
+        GALLON / MINUTE,
+        "1 US gal/min"
+    ),
+    exact!(
+        "gallon per day",
+        "cubic meter per second",
+        GALLON / DAY,
+        "1 US gal/d"
+    ),
+    exact!(
+        "million gallon per day",
+        "cubic meter per second",
+        1.0e6 * GALLON / DAY,
+        "10^6 US gal/d"
+    ),
+    exact!(
+        "acre-foot per day",
+        "cubic meter per second",
+        ACRE * FOOT / DAY,
+        "1 acre-ft/d"
+    ),
+    // density and concentration
+    exact!(
+        "kilogram per cubic meter",
+        "kilogram per cubic meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "gram per cubic centimeter",
+        "kilogram per cubic meter",
+        1000.0,
+        "1 g/cm^3"
+    ),
+    exact!(
+        "milligram per liter",
+        "kilogram per cubic meter",
+        1.0e-3,
+        "1 mg/L"
+    ),
+    exact!(
+        "pound per cubic foot",
+        "kilogram per cubic meter",
+        POUND / (FOOT * FOOT * FOOT),
+        "1 lb/ft^3"
+    ),
+    exact!(
+        "pound per gallon",
+        "kilogram per cubic meter",
+        POUND / GALLON,
+        "1 lb/US gal"
+    ),
+    exact!(
+        "slug per cubic foot",
+        "kilogram per cubic meter",
+        POUND_FORCE / (FOOT * FOOT * FOOT * FOOT),
+        "1 slug/ft^3"
+    ),
+    // unit weight
+    exact!(
+        "newton per cubic meter",
+        "newton per cubic meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "kilonewton per cubic meter",
+        "newton per cubic meter",
+        1.0e3,
+        "1 kN/m^3"
+    ),
+    exact!(
+        "pound force per cubic foot",
+        "newton per cubic meter",
+        POUND_FORCE / (FOOT * FOOT * FOOT),
+        "1 lbf/ft^3"
+    ),
+    // viscosity
+    exact!("pascal second", "pascal second", 1.0, "SI derived unit"),
+    exact!("poise", "pascal second", 0.1, "1 dyne s/cm^2"),
+    exact!("centipoise", "pascal second", 1.0e-3, "0.01 poise"),
+    exact!(
+        "square meter per second",
+        "square meter per second",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!("stoke", "square meter per second", 1.0e-4, "1 cm^2/s"),
+    exact!(
+        "centistoke",
+        "square meter per second",
+        1.0e-6,
+        "0.01 stokes"
+    ),
+    exact!(
+        "square foot per second",
+        "square meter per second",
+        FOOT * FOOT,
+        "1 ft^2/s"
+    ),
+    // temperature. These are the sizes of one degree, so a plain factor
+    // converts temperature differences. See `units::affine` for readings.
+    exact!("kelvin", "kelvin", 1.0, "SI base unit"),
+    exact!("celsius", "kelvin", 1.0, "1 K"),
+    exact!("fahrenheit", "kelvin", 5.0 / 9.0, "5/9 K"),
+    exact!("rankine", "kelvin", 5.0 / 9.0, "5/9 K"),
+    // angle
+    exact!("radian", "radian", 1.0, "SI derived unit"),
+    exact!("degree", "radian", PI / 180.0, "pi/180 rad"),
+    exact!("minute (angle)", "radian", PI / 10_800.0, "1/60 degree"),
+    exact!("second (angle)", "radian", PI / 648_000.0, "1/3600 degree"),
+    exact!("gradian", "radian", PI / 200.0, "1/400 revolution"),
+    exact!("revolution", "radian", 2.0 * PI, "2 pi rad"),
+    exact!(
+        "radian per second",
+        "radian per second",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "revolution per minute",
+        "radian per second",
+        2.0 * PI / MINUTE,
+        "2 pi rad/min"
+    ),
+    exact!(
+        "degree per second",
+        "radian per second",
+        PI / 180.0,
+        "1 degree/s"
+    ),
+    // electricity
+    exact!("ampere", "ampere", 1.0, "SI base unit"),
+    exact!("coulomb", "coulomb", 1.0, "SI derived unit"),
+    exact!("ampere-hour", "coulomb", HOUR, "3600 C"),
+    exact!("volt", "volt", 1.0, "SI derived unit"),
+    exact!("kilovolt", "volt", 1.0e3, "1000 V"),
+    exact!("ohm", "ohm", 1.0, "SI derived unit"),
+    exact!("hertz", "hertz", 1.0, "SI derived unit"),
+    exact!("ampere-turn", "ampere", 1.0, "1 A"),
+    exact!("farad", "farad", 1.0, "SI derived unit"),
+    exact!("microfarad", "farad", 1.0e-6, "10^-6 F"),
+    exact!("henry", "henry", 1.0, "SI derived unit"),
+    exact!("siemens", "siemens", 1.0, "SI derived unit"),
+    exact!("weber", "weber", 1.0, "SI derived unit"),
+    exact!("tesla", "tesla", 1.0, "SI derived unit"),
+    exact!(
+        "ampere per meter",
+        "ampere per meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!("amp-turn per meter", "ampere per meter", 1.0, "1 A/m"),
+    exact!("volt per meter", "volt per meter", 1.0, "SI derived unit"),
+    exact!("volt per centimeter", "volt per meter", 100.0, "100 V/m"),
+    exact!("coulomb meter", "coulomb meter", 1.0, "SI derived unit"),
+    exact!(
+        "coulomb per square meter",
+        "coulomb per square meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "coulomb per mole",
+        "coulomb per mole",
+        1.0,
+        "SI derived unit"
+    ),
+    // light
+    exact!(
+        "candela per square meter",
+        "candela per square meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!("lux", "lux", 1.0, "SI derived unit"),
+    // radiation
+    exact!("becquerel", "becquerel", 1.0, "SI derived unit"),
+    exact!("gray", "gray", 1.0, "SI derived unit"),
+    exact!("sievert", "sievert", 1.0, "SI derived unit"),
+    exact!(
+        "coulomb per kilogram",
+        "coulomb per kilogram",
+        1.0,
+        "SI derived unit"
+    ),
+    // heat
+    exact!(
+        "watt per square meter",
+        "watt per square meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "joule per square meter",
+        "joule per square meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "kelvin square meter per watt",
+        "kelvin square meter per watt",
+        1.0,
+        "SI derived unit"
+    ),
+    // water vapor
+    exact!(
+        "kilogram per pascal second meter",
+        "kilogram per pascal second meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "kilogram per pascal second square meter",
+        "kilogram per pascal second square meter",
+        1.0,
+        "SI derived unit"
+    ),
+    // mechanics
+    exact!(
+        "kilogram per meter",
+        "kilogram per meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!("gram per meter", "kilogram per meter", 1.0e-3, "0.001 kg/m"),
+    exact!("gram per centimeter", "kilogram per meter", 0.1, "0.1 kg/m"),
+    exact!(
+        "newton per meter",
+        "newton per meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "dyne per centimeter",
+        "newton per meter",
+        1.0e-3,
+        "0.001 N/m"
+    ),
+    exact!(
+        "meter to the fourth power",
+        "meter to the fourth power",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!("joule second", "joule second", 1.0, "SI derived unit"),
+    exact!("erg-second", "joule second", 1.0e-7, "10^-7 J s"),
+    exact!(
+        "per pascal second",
+        "per pascal second",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!(
+        "radian per square second",
+        "radian per square second",
+        1.0,
+        "SI derived unit"
+    ),
+    // ratios
+    exact!(
+        "reciprocal meter",
+        "reciprocal meter",
+        1.0,
+        "SI derived unit"
+    ),
+    exact!("per meter", "reciprocal meter", 1.0, "1 m^-1"),
+    exact!("meter per meter", "meter per meter", 1.0, "SI derived unit"),
+    exact!("percent grade", "meter per meter", 0.01, "1 m per 100 m"),
+    exact!("neper", "neper", 1.0, "SI coherent unit of level"),
+    exact!("steradian", "steradian", 1.0, "SI derived unit"),
+];
+
+impl Definition<'_> {
+    /// error is the relative error assumed for `si`: none to speak of for
+    /// exact definitions, about the fifth significant figure for empirical
+    /// ones, and the fourth for tabulated ones, which is how far most of
+    /// the raw table was rounded.
+    pub fn error(&self) -> f64 {
+        match self.basis {
+            Basis::Exact => EXACT,
+            Basis::Empirical => 1.0e-5,
+            Basis::Tabulated => 5.0e-4,
+        }
+    }
+}
+
+// Shorthand for the BASES table: length, mass, time, temperature, current
+const fn dim(l: i8, m: i8, t: i8, k: i8, i: i8) -> Dimension {
+    Dimension::new([l, m, t, k, i, 0, 0])
+}
+
+/// BASES gives the dimension of every SI unit used as a `base` above.
+pub static BASES: &[(&str, Dimension)] = &[
+    ("meter", dim(1, 0, 0, 0, 0)),
+    ("square meter", dim(2, 0, 0, 0, 0)),
+    ("cubic meter", dim(3, 0, 0, 0, 0)),
+    ("kilogram", dim(0, 1, 0, 0, 0)),
+    ("second", dim(0, 0, 1, 0, 0)),
+    ("kelvin", dim(0, 0, 0, 1, 0)),
+    ("ampere", dim(0, 0, 0, 0, 1)),
+    ("radian", dim(0, 0, 0, 0, 0)),
+    ("hertz", dim(0, 0, -1, 0, 0)),
+    ("radian per second", dim(0, 0, -1, 0, 0)),
+    ("meter per second", dim(1, 0, -1, 0, 0)),
+    ("meter per square second", dim(1, 0, -2, 0, 0)),
+    ("cubic meter per second", dim(3, 0, -1, 0, 0)),
+    ("square meter per second", dim(2, 0, -1, 0, 0)),
+    ("newton", dim(1, 1, -2, 0, 0)),
+    ("pascal", dim(-1, 1, -2, 0, 0)),
+    ("joule", dim(2, 1, -2, 0, 0)),
+    ("newton-meter", dim(2, 1, -2, 0, 0)),
+    ("watt", dim(2, 1, -3, 0, 0)),
+    ("kilogram per cubic meter", dim(-3, 1, 0, 0, 0)),
+    ("newton per cubic meter", dim(-2, 1, -2, 0, 0)),
+    ("pascal second", dim(-1, 1, -1, 0, 0)),
+    ("coulomb", dim(0, 0, 1, 0, 1)),
+    ("volt", dim(2, 1, -3, 0, -1)),
+    ("ohm", dim(2, 1, -3, 0, -2)),
+    ("farad", dim(-2, -1, 4, 0, 2)),
+    ("henry", dim(2, 1, -2, 0, -2)),
+    ("siemens", dim(-2, -1, 3, 0, 2)),
+    ("weber", dim(2, 1, -2, 0, -1)),
+    ("tesla", dim(0, 1, -2, 0, -1)),
+    ("ampere per meter", dim(-1, 0, 0, 0, 1)),
+    ("volt per meter", dim(1, 1, -3, 0, -1)),
+    ("coulomb meter", dim(1, 0, 1, 0, 1)),
+    ("coulomb per square meter", dim(-2, 0, 1, 0, 1)),
+    ("coulomb per mole", Dimension::new([0, 0, 1, 0, 1, -1, 0])),
+    (
+        "candela per square meter",
+        Dimension::new([-2, 0, 0, 0, 0, 0, 1]),
+    ),
+    ("lux", Dimension::new([-2, 0, 0, 0, 0, 0, 1])),
+    ("becquerel", dim(0, 0, -1, 0, 0)),
+    ("gray", dim(2, 0, -2, 0, 0)),
+    ("sievert", dim(2, 0, -2, 0, 0)),
+    ("coulomb per kilogram", dim(0, -1, 1, 0, 1)),
+    ("watt per square meter", dim(0, 1, -3, 0, 0)),
+    ("joule per square meter", dim(0, 1, -2, 0, 0)),
+    ("kelvin square meter per watt", dim(0, -1, 3, 1, 0)),
+    ("kilogram per pascal second meter", dim(0, 0, 1, 0, 0)),
+    (
+        "kilogram per pascal second square meter",
+        dim(-1, 0, 1, 0, 0),
+    ),
+    ("kilogram per meter", dim(-1, 1, 0, 0, 0)),
+    ("newton per meter", dim(0, 1, -2, 0, 0)),
+    ("meter to the fourth power", dim(4, 0, 0, 0, 0)),
+    ("joule second", dim(2, 1, -1, 0, 0)),
+    ("per pascal second", dim(1, -1, 1, 0, 0)),
+    ("radian per square second", dim(0, 0, -2, 0, 0)),
+    ("reciprocal meter", dim(-1, 0, 0, 0, 0)),
+    ("meter per meter", dim(0, 0, 0, 0, 0)),
+    ("neper", dim(0, 0, 0, 0, 0)),
+    ("steradian", dim(0, 0, 0, 0, 0)),
+];
+
+/// dimension returns the dimension of an SI base unit from `BASES`.
+pub fn dimension(base: &str) -> Option<Dimension> {
+    BASES.iter().find(|(b, _)| *b == base).map(|(_, d)| *d)
+}
+
+/// lookup finds the definition of a canonical unit id.
+pub fn lookup(name: &str) -> Option<&'static Definition<'static>> {
+    DEFINITIONS.iter().find(|d| d.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dimension, lookup, DEFINITIONS};
+    use crate::units::names::normalize;
+
+    #[test]
+    fn names_are_canonical() {
+        for d in DEFINITIONS.iter() {
+            assert_eq!(normalize(d.name), d.name);
+            assert_eq!(normalize(d.base), d.base);
+            assert_eq!(lookup(d.base).unwrap().si, 1.0, "{} has no base", d.name);
+            assert!(dimension(d.base).is_some(), "{} has no dimension", d.base);
+        }
+    }
+
+    #[test]
+    fn names_are_unique() {
+        for (i, d) in DEFINITIONS.iter().enumerate() {
+            assert!(
+                DEFINITIONS[i + 1..].iter().all(|e| e.name != d.name),
+                "{}",
+                d.name
+            );
+        }
+    }
+
+    #[test]
+    fn exact_definitions() {
+        let f = |a: &str, b: &str| lookup(a).unwrap().si / lookup(b).unwrap().si;
+        assert_eq!(f("foot", "meter"), 0.3048);
+        assert_eq!(f("mile", "foot"), 5280.0);
+        assert!((f("gallon", "cubic inch") - 231.0).abs() < 1e-12);
+        assert!((f("acre-foot", "gallon") - 325_851.428_571_428_6).abs() < 1e-6);
+        assert!((f("atmosphere", "pound per square inch") - 14.695_948_775_5).abs() < 1e-9);
+        assert!((f("acre", "hectare") - 0.404_685_642_24).abs() < 1e-12);
+        assert!((f("cubic foot per second", "gallon per minute") - 448.831_168_831).abs() < 1e-6);
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::assertions_on_constants)]
+
 #[test]
 fn we_can_test() {
     assert!(true)
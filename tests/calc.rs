@@ -1 +1,109 @@
-//! Place holder for integration tests related to calculations
\ No newline at end of file
+//! Place holder for integration tests related to calculations
+
+extern crate civil;
+
+use civil::calc::calculus::integrate;
+use civil::calc::dual::{jacobian, Dual, Float};
+use civil::calc::linalg::{Matrix, MatrixError};
+use civil::calc::nonlinear::newton_raphson_with_jacobian;
+use civil::calc::ode::{dormand_prince_until, rk4, Options};
+use civil::calc::roots::{bracket, brent, newton, Tolerance};
+
+#[test]
+fn springs_in_series() {
+    // Two springs, k1 = 100 and k2 = 50, fixed at the left with a load of
+    // 10 at the right end. The stiffness matrix for the two free nodes:
+    let k = Matrix::from_rows(&[[150.0, -50.0], [-50.0, 50.0]]).unwrap();
+    let u = k.solve(&[0.0, 10.0]).unwrap();
+    assert!((u[0] - 0.1).abs() < 1e-12);
+    assert!((u[1] - 0.3).abs() < 1e-12);
+    // Without the support the structure is a mechanism
+    let free = Matrix::from_rows(&[
+        [100.0, -100.0, 0.0],
+        [-100.0, 150.0, -50.0],
+        [0.0, -50.0, 50.0],
+    ])
+    .unwrap();
+    match free.solve(&[0.0, 0.0, 10.0]) {
+        Err(MatrixError::Singular { .. }) | Err(MatrixError::IllConditioned { .. }) => {}
+        other => panic!("expected a singular stiffness matrix, got {:?}", other),
+    }
+}
+
+#[test]
+fn implicit_design_equations() {
+    // Critical depth in a trapezoidal channel, 2 m base and 2:1 side
+    // slopes, carrying 10 m³/s: Q² T / (g A³) = 1
+    let (q, b, z, g) = (10.0f64, 2.0, 2.0, 9.806_65);
+    let froude = |y: f64| {
+        let (area, top) = ((b + z * y) * y, b + 2.0 * z * y);
+        q * q * top / (g * area.powi(3)) - 1.0
+    };
+    let (lo, hi) = bracket(froude, 0.1, 0.5).unwrap();
+    let yc = brent(froude, lo, hi, Tolerance::default());
+    assert!(yc.converged());
+    assert!(froude(yc.x).abs() < 1e-12);
+    assert!((yc.x - 0.9883).abs() < 1e-4, "{:?}", yc);
+    // Colebrook: Newton from the Swamee-Jain estimate takes a few steps
+    let (re, rr) = (2.0e5f64, 5.0e-4);
+    let colebrook = |f: f64| 1.0 / f.sqrt() + 2.0 * (rr / 3.7 + 2.51 / (re * f.sqrt())).log10();
+    let guess = 0.25 / (rr / 3.7 + 5.74 / re.powf(0.9)).log10().powi(2);
+    let f = newton(colebrook, guess, Tolerance::default());
+    assert!(f.converged() && f.iterations < 6, "{:?}", f);
+    assert!((f.x - guess).abs() / f.x < 0.02);
+}
+
+#[test]
+fn drawdown_profile() {
+    // An M2 profile in a wide channel with Chezy friction, walking
+    // downstream until the depth is nearly critical:
+    // dy/dx = S0 (y³ - yn³) / (y³ - yc³)
+    let (q, g, s0, yn) = (2.0f64, 9.806_65, 0.001, 1.2f64);
+    let yc = (q * q / g).cbrt();
+    let slope = |_x: f64, y: &[f64]| {
+        let y3 = y[0].powi(3);
+        vec![s0 * (y3 - yn.powi(3)) / (y3 - yc.powi(3))]
+    };
+    let near_critical = |_x: f64, y: &[f64]| y[0] - 0.8;
+    let run = dormand_prince_until(
+        slope,
+        near_critical,
+        0.0,
+        &[1.15],
+        5000.0,
+        Options::default(),
+    )
+    .unwrap();
+    assert!(run.stopped);
+    assert!(run.y.windows(2).all(|w| w[1][0] < w[0][0]));
+    // The same distance by integrating dx/dy instead
+    let dx = |y: f64| (y.powi(3) - yc.powi(3)) / (s0 * (y.powi(3) - yn.powi(3)));
+    let length = integrate(dx, 1.15, 0.8);
+    let (x, y) = run.last();
+    assert!((x - length).abs() < 1e-4, "{} {}", x, length);
+    assert!((y[0] - 0.8).abs() < 1e-9);
+    // Fixed steps over the same reach agree
+    let fixed = rk4(slope, 0.0, &[1.15], x, 1000);
+    assert!((fixed.last().1[0] - 0.8).abs() < 1e-6, "{:?}", fixed.last());
+}
+
+#[test]
+fn exact_jacobian() {
+    // Two pipes in parallel, with the exact Jacobian from duals
+    fn residual<T: Float>(q: &[T]) -> Vec<T> {
+        let (k1, k2) = (4.0, 9.0);
+        vec![
+            q[0] + q[1] - 1.0,
+            q[0] * q[0].abs() * k1 - q[1] * q[1].abs() * k2,
+        ]
+    }
+    let s = newton_raphson_with_jacobian(
+        |q: &[f64]| residual(q),
+        |q: &[f64]| jacobian(|q: &[Dual]| residual(q), q),
+        &[0.5, 0.5],
+        Tolerance::default(),
+    )
+    .unwrap();
+    assert!((s.x[0] - 0.6).abs() < 1e-10);
+    assert!((s.x[1] - 0.4).abs() < 1e-10);
+}
@@ -0,0 +1,130 @@
+extern crate civil;
+
+use civil::calc::dual::Dual;
+use civil::calc::geometry::{Point, Polygon};
+use civil::structural::beams::{
+    Beam, CircularBeam, CircularTube, IBeam, PolygonalBeam, TrapezoidalBeam,
+};
+use std::f64::consts::PI;
+
+#[test]
+fn section_sensitivity() {
+    // How the section modulus of a W-shape responds to the flange thickness,
+    // exactly and by a central difference
+    let (b, h, t, w) = (0.2, 0.4, 0.015, 0.01);
+    let beam = IBeam::new(
+        Dual::constant(b),
+        Dual::constant(h),
+        Dual::variable(t),
+        Dual::constant(w),
+    );
+    let plain = IBeam::new(b, h, t, w);
+    assert_eq!(beam.S.value, plain.S);
+    assert_eq!(beam.I.value, plain.I);
+    let step = 1.0e-6;
+    let ds = (IBeam::new(b, h, t + step, w).section_modulus()
+        - IBeam::new(b, h, t - step, w).section_modulus())
+        / (2.0 * step);
+    assert!((beam.S.derivative - ds).abs() < 1.0e-8 * ds);
+    assert!(beam.A.derivative > 0.0 && beam.k.derivative.is_finite());
+}
+
+// A ring of `sides` points on a circle about the origin, the first on the
+// x axis
+fn circle(radius: f64, sides: usize) -> Vec<Point> {
+    (0..sides)
+        .map(|i| {
+            let (s, c) = (2.0 * PI * i as f64 / sides as f64).sin_cos();
+            Point::new(radius * c, radius * s)
+        })
+        .collect()
+}
+
+// Check a section's area, moment of inertia about its horizontal centroidal
+// axis and, optionally, its section modulus against a polygon
+fn matches<B: Beam>(name: &str, beam: &B, shape: &Polygon, depth: Option<f64>, tolerance: f64) {
+    let close = |a: f64, b: f64| (a - b).abs() <= tolerance * b.abs();
+    let i = shape.centroidal_moments().ix;
+    assert!(
+        close(beam.area(), shape.area()),
+        "{}: area {} != {}",
+        name,
+        beam.area(),
+        shape.area()
+    );
+    assert!(
+        close(beam.moment_of_inertia(), i),
+        "{}: I {} != {}",
+        name,
+        beam.moment_of_inertia(),
+        i
+    );
+    let k = (i / shape.area()).sqrt();
+    assert!(
+        close(beam.radius_of_gyration(), k),
+        "{}: k {} != {}",
+        name,
+        beam.radius_of_gyration(),
+        k
+    );
+    if let Some(depth) = depth {
+        // distance from the centroid to the extreme fiber
+        let c = shape.centroid().y.max(depth - shape.centroid().y);
+        assert!(
+            close(beam.section_modulus(), i / c),
+            "{}: S {} != {}",
+            name,
+            beam.section_modulus(),
+            i / c
+        );
+    }
+}
+
+#[test]
+fn sections_match_geometry() {
+    let (b, h, t, w) = (0.2, 0.4, 0.015, 0.01);
+    let i_shape = Polygon::new(&[
+        Point::new(0.0, 0.0),
+        Point::new(b, 0.0),
+        Point::new(b, t),
+        Point::new((b + w) / 2.0, t),
+        Point::new((b + w) / 2.0, h - t),
+        Point::new(b, h - t),
+        Point::new(b, h),
+        Point::new(0.0, h),
+        Point::new(0.0, h - t),
+        Point::new((b - w) / 2.0, h - t),
+        Point::new((b - w) / 2.0, t),
+        Point::new(0.0, t),
+    ])
+    .unwrap();
+    matches("I-beam", &IBeam::new(b, h, t, w), &i_shape, Some(h), 1e-12);
+
+    let (minor, major, height) = (0.1, 0.3, 0.25);
+    let trapezoid = Polygon::new(&[
+        Point::new(0.0, 0.0),
+        Point::new(major, 0.0),
+        Point::new((major + minor) / 2.0, height),
+        Point::new((major - minor) / 2.0, height),
+    ])
+    .unwrap();
+    let beam = TrapezoidalBeam::new(minor, major, height);
+    matches("trapezoid", &beam, &trapezoid, Some(height), 1e-12);
+
+    // a hexagon with a flat top and bottom, its vertices on the x axis
+    let side = 0.1;
+    let hexagon = Polygon::new(&circle(side, 6)).unwrap();
+    let beam = PolygonalBeam::new(side, 6);
+    matches("hexagon", &beam, &hexagon, None, 1e-12);
+    assert!((beam.section_modulus() - hexagon.centroidal_moments().ix / beam.r).abs() < 1e-15);
+
+    // circles are close to polygons with enough sides
+    let n = 4096;
+    let round = Polygon::new(&circle(0.05, n)).unwrap();
+    matches("circle", &CircularBeam::new(0.05), &round, None, 1e-5);
+    let tube = Polygon::new(&circle(0.1, n))
+        .unwrap()
+        .with_hole(&circle(0.09, n))
+        .unwrap();
+    matches("tube", &CircularTube::new(0.09, 0.1), &tube, None, 1e-5);
+}
@@ -1,6 +1,13 @@
+#![allow(clippy::assertions_on_constants)]
+
 extern crate civil;
 
+use civil::units::affine::Atmosphere;
+use civil::units::category::Category;
 use civil::units::conversions;
+use civil::units::quantity::{Area, Flow, Force, Length, Pressure, Volume};
+use civil::units::si::Basis;
+use civil::units::UnitError;
 
 const PRECISION: f64 = 0.1;
 
@@ -27,3 +34,350 @@ fn valid_key_gets_the_right_value() {
     assert!((0.3048 - my_val).abs() < PRECISION)
 }
 
+#[test]
+fn aliases_resolve_to_canonical_units() {
+    let my_table = conversions::Table::new();
+    assert_eq!(my_table.canonical("square  foot").unwrap(), "square foot");
+    assert_eq!(my_table.canonical("Sq Ft").unwrap(), "square foot");
+    assert_eq!(my_table.canonical("psi").unwrap(), "pound per square inch");
+    assert_eq!(my_table.canonical("CFS").unwrap(), "cubic foot per second");
+    assert_eq!(my_table.canonical("feet").unwrap(), "foot");
+    assert_eq!(my_table.canonical("ton").unwrap(), "ton (short)");
+    assert_eq!(my_table.canonical("ksf").unwrap(), "kip per square foot");
+    assert_eq!(my_table.canonical("Hz").unwrap(), "hertz");
+    assert_eq!(
+        my_table.canonical("imperial gallon").unwrap(),
+        "gallon (imperial)"
+    );
+    assert_eq!(my_table.canonical("inch-pound").unwrap(), "pound-inch");
+    assert_eq!(my_table.canonical("foot-kip").unwrap(), "kip-foot");
+    for unit in ["lb", "lbm", "pound"].iter() {
+        let my_val = my_table.factor("ton", unit).unwrap();
+        assert!((2000.0 - my_val).abs() < 1.0e-9, "{}", unit);
+    }
+    let my_val = my_table.factor("ksf", "psf").unwrap();
+    assert!((1000.0 - my_val).abs() < 1.0e-9);
+    let my_val = my_table.factor("Hz", "1/s").unwrap();
+    assert_eq!(1.0, my_val);
+}
+
+#[test]
+fn si_prefixes_keep_their_case() {
+    let my_table = conversions::table();
+    let my_val = my_table.factor("mPa", "kPa").unwrap();
+    assert!((1.0e-6 - my_val).abs() < 1.0e-18);
+    let my_val = my_table.factor("MPa", "kPa").unwrap();
+    assert!((1000.0 - my_val).abs() < 1.0e-9);
+    let my_val = my_table.factor("mm", "m").unwrap();
+    assert!((0.001 - my_val).abs() < 1.0e-15);
+    let my_val = my_table.factor("Mm", "m").unwrap();
+    assert!((1.0e6 - my_val).abs() < 1.0e-6);
+    let my_val = my_table.factor("MW", "mW").unwrap();
+    assert!((1.0e9 - my_val).abs() < 1.0e-3);
+    let my_val = my_table.factor("Ms", "ms").unwrap();
+    assert!((1.0e9 - my_val).abs() < 1.0e-3);
+    assert_eq!(my_table.canonical("kN").unwrap(), "kilonewton");
+    assert!(my_table.factor("kilofoot", "ft").is_err());
+}
+
+#[test]
+fn abbreviated_keys_get_the_right_value() {
+    let my_table = conversions::Table::new();
+    let my_val = my_table.factor("ft", "m").unwrap();
+    assert!((0.3048 - my_val).abs() < PRECISION);
+    let my_val = my_table.factor("acres", "sq ft").unwrap();
+    assert!((43560.0 - my_val).abs() < PRECISION);
+}
+
+#[test]
+fn unknown_units_suggest_close_matches() {
+    let my_table = conversions::Table::new();
+    match my_table.canonical("furlongg") {
+        Err(UnitError::UnknownUnit { suggestions, .. }) => {
+            assert_eq!(suggestions.first().map(|s| s.as_str()), Some("furlong"))
+        }
+        other => panic!("expected an unknown unit error, got {:?}", other),
+    }
+}
+
+#[test]
+fn inverse_and_transitive_conversions() {
+    let my_table = conversions::Table::new();
+    let my_val = my_table.factor("square meter", "acre").unwrap();
+    assert!((1.0 / 4046.873 - my_val).abs() < 1.0e-6);
+    let my_val = my_table.factor("foot", "mile").unwrap();
+    assert!((1.0 / 5280.0 - my_val).abs() < 1.0e-6);
+    let c = my_table.conversion("acre", "square inch").unwrap();
+    assert_eq!(c.path, vec!["acre", "square meter", "square inch"]);
+    assert!((6272640.0 - c.factor).abs() < 1.0e-6);
+}
+
+#[test]
+fn factors_come_from_exact_definitions() {
+    let my_table = conversions::Table::new();
+    // both of these are rounded in the raw table
+    let my_val = my_table.convert[&("acre-foot", "gallon")];
+    assert!((43560.0 * 12.0 * 12.0 * 12.0 / 231.0 - my_val).abs() < 1.0e-8);
+    let my_val = my_table.factor("atm", "psi").unwrap();
+    assert!((14.695_948_775_5 - my_val).abs() < 1.0e-10);
+    let my_val = my_table.factor("ft", "m").unwrap();
+    assert_eq!(my_val, 0.3048);
+    let water = my_table.definition("foot of water").unwrap();
+    assert_eq!(water.basis, Basis::Empirical);
+    assert_eq!(water.base, "pascal");
+}
+
+#[test]
+fn unconnected_units_are_an_error() {
+    let my_table = conversions::Table::new();
+    match my_table.factor("acre", "second") {
+        Err(UnitError::CategoryMismatch {
+            from,
+            from_category,
+            to,
+            to_category,
+        }) => {
+            assert_eq!(from, "acre");
+            assert_eq!(from_category, Category::Area);
+            assert_eq!(to, "second");
+            assert_eq!(to_category, Category::Time);
+        }
+        other => panic!("expected a category mismatch, got {:?}", other),
+    }
+}
+
+#[test]
+fn audit_finds_inconsistent_and_duplicate_entries() {
+    let my_table = conversions::Table::new();
+    let audit = my_table.audit();
+    // loops made up of units with SI definitions always close
+    assert!(audit
+        .flagged(1.0e-9)
+        .iter()
+        .all(|c| c
+            .path
+            .iter()
+            .any(|id| my_table.definition(id).unwrap().basis == Basis::Tabulated)));
+    assert!(audit.flagged(1.0e-5).len() > audit.flagged(1.0e-2).len());
+    let calorie = audit
+        .duplicates
+        .iter()
+        .find(|d| d.from == "gram-calorie" && d.to == "Btu")
+        .unwrap();
+    assert!(calorie.conflicting());
+}
+
+#[test]
+fn static_lookups_match_the_table() {
+    let my_table = conversions::table();
+    assert!(std::ptr::eq(my_table, conversions::table()));
+    for ((from, to), f) in my_table.convert.iter() {
+        assert_eq!(conversions::get(from, to), Some(*f));
+    }
+    assert_eq!(
+        conversions::get("galileo", "meter per sec square"),
+        Some(0.01)
+    );
+    // quoted fields keep their commas
+    assert_eq!(
+        conversions::get("barrel (US, liquid)", "gallon"),
+        Some(31.5)
+    );
+    assert_eq!(conversions::get("apple", "orange"), None);
+}
+
+#[test]
+fn temperature_readings_and_differences() {
+    let my_table = conversions::table();
+    let my_val = my_table.temperature(-40.0, "°F", "°C").unwrap();
+    assert!((-40.0 - my_val).abs() < 1.0e-12);
+    let my_val = my_table.temperature(0.0, "K", "degree Rankine").unwrap();
+    assert_eq!(my_val, 0.0);
+    let my_val = my_table
+        .temperature(100.0, "celsius", "fahrenheit")
+        .unwrap();
+    assert!((212.0 - my_val).abs() < 1.0e-12);
+    let my_val = my_table
+        .temperature_difference(100.0, "celsius", "fahrenheit")
+        .unwrap();
+    assert!((180.0 - my_val).abs() < 1.0e-12);
+    // the plain factor is a difference too
+    let my_val = my_table.factor("kelvin", "rankine").unwrap();
+    assert!((1.8 - my_val).abs() < 1.0e-12);
+    match my_table.temperature(1.0, "foot", "celsius") {
+        Err(UnitError::WrongKind { name, .. }) => assert_eq!(name, "foot"),
+        other => panic!("expected the wrong kind of unit, got {:?}", other),
+    }
+}
+
+#[test]
+fn gauge_and_absolute_pressure() {
+    let my_table = conversions::table();
+    let denver = Atmosphere::at_elevation(1609.0);
+    let my_val = my_table.pressure(14.7, "psia", "psig", denver).unwrap();
+    assert!((14.7 - denver.pressure / 6894.757 - my_val).abs() < 1.0e-6);
+    let my_val = my_table
+        .pressure(1.0, "barg", "kPa", Atmosphere::standard())
+        .unwrap();
+    assert!((201.325 - my_val).abs() < 1.0e-9);
+    // two gauge readings against the same atmosphere
+    let my_val = my_table.pressure(10.0, "psig", "kPag", denver).unwrap();
+    assert!((68.947_572_9 - my_val).abs() < 1.0e-6);
+    assert!(my_table
+        .pressure(1.0, "psig", "foot", Atmosphere::standard())
+        .is_err());
+}
+
+#[test]
+fn quantities_check_dimensions() {
+    let q = Flow::new(10.0, "cfs").unwrap();
+    let my_val = q.value_in("gpm").unwrap();
+    assert!((4_488.311_688_3 - my_val).abs() < 1.0e-6);
+    let area = Area::new(2.0, "acre").unwrap();
+    let depth: Length = (Volume::new(1.0, "acre-foot").unwrap() / area)
+        .to_kind()
+        .unwrap();
+    assert!((0.5 - depth.value_in("ft").unwrap()).abs() < 1.0e-12);
+    match Pressure::new(1.0, "square foot") {
+        Err(UnitError::WrongKind { expected, .. }) => assert_eq!(expected, "pressure"),
+        other => panic!("expected the wrong kind of unit, got {:?}", other),
+    }
+    assert!(q.value_in("ft").is_err());
+}
+
+#[test]
+fn compound_unit_expressions() {
+    let my_table = conversions::table();
+    let my_val = my_table.factor("lbf/ft^3", "kN/m³").unwrap();
+    assert!((0.157_087_463_9 - my_val).abs() < 1.0e-9);
+    let my_val = my_table.factor("gpm/ft^2", "mm/s").unwrap();
+    assert!((0.679_097_222_2 - my_val).abs() < 1.0e-9);
+    let my_val = my_table.factor("kip-ft", "lbf*in").unwrap();
+    assert!((12_000.0 - my_val).abs() < 1.0e-8);
+    let my_val = my_table.factor("slug", "lbf*s^2/ft").unwrap();
+    assert!((1.0 - my_val).abs() < 1.0e-12);
+    match my_table.factor("kN*m", "kN/m") {
+        Err(UnitError::DimensionMismatch { .. }) => {}
+        other => panic!("expected a dimension mismatch, got {:?}", other),
+    }
+    match my_table.unit("kN*(m") {
+        Err(UnitError::InvalidExpression { .. }) => {}
+        other => panic!("expected an invalid expression, got {:?}", other),
+    }
+    for huge in ["m^100^2", "m^127*m"].iter() {
+        match my_table.factor(huge, "m") {
+            Err(UnitError::InvalidExpression { reason, .. }) => {
+                assert_eq!(reason, "exponent out of range")
+            }
+            other => panic!("expected an invalid expression, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn plain_pounds_are_weighed_when_a_force_is_wanted() {
+    let my_table = conversions::table();
+    for unit in ["lb/ft^3", "pcf", "lbf/ft^3"].iter() {
+        let my_val = my_table.factor(unit, "kN/m^3").unwrap();
+        assert!((0.157_087_463_9 - my_val).abs() < 1.0e-9, "{}", unit);
+    }
+    let my_val = my_table.factor("lbm/ft^3", "kg/m^3").unwrap();
+    assert!((16.018_463_37 - my_val).abs() < 1.0e-8);
+    // products mean the same in either order
+    for unit in ["in-lb", "lb-in", "in-lbf", "inch-pound"].iter() {
+        let my_val = my_table.factor(unit, "N-m").unwrap();
+        assert!((0.112_984_829 - my_val).abs() < 1.0e-9, "{}", unit);
+    }
+    let my_val = my_table.factor("yard-pound", "ft-lb").unwrap();
+    assert!((3.0 - my_val).abs() < 1.0e-12);
+    let my_val = my_table.factor("kip/ft", "lb/ft").unwrap();
+    assert!((1000.0 - my_val).abs() < 1.0e-9);
+    let p = Pressure::new(1.0, "lb/ft^2").unwrap();
+    assert!((1.0 - p.value_in("psf").unwrap()).abs() < 1.0e-12);
+    for unit in ["lb", "lbm", "pound"].iter() {
+        let my_val = my_table.factor(unit, "kg").unwrap();
+        assert_eq!(0.453_592_37, my_val, "{}", unit);
+    }
+    for unit in ["lb", "lbf", "pound"].iter() {
+        let my_val = my_table.factor(unit, "N").unwrap();
+        assert!((4.448_221_615 - my_val).abs() < 1.0e-9, "{}", unit);
+    }
+    let my_val = my_table.factor("N", "lb").unwrap();
+    assert!((0.224_808_943 - my_val).abs() < 1.0e-9);
+    let f = Force::new(1.0, "lb").unwrap();
+    assert!((4.448_221_615 - f.value()).abs() < 1.0e-9);
+    assert!((1.0 - f.value_in("pound").unwrap()).abs() < 1.0e-12);
+    // the explicit spellings are never weighed
+    assert!(my_table.factor("lbm", "lbf").is_err());
+    assert!(my_table.factor("kg", "N").is_err());
+}
+
+#[test]
+fn field_notation_round_trips() {
+    use civil::units::notation::*;
+    let my_val = parse_feet_inches("10'-4 3/8\"").unwrap();
+    assert_eq!(format_feet_inches(my_val, 16), "10'-4 3/8\"");
+    let my_val = parse_station("3+07.25", Stationing::Feet).unwrap();
+    assert_eq!(format_station(my_val, Stationing::Feet, 2), "3+07.25");
+    let my_val = parse_bearing("N 89°59'59\" W").unwrap();
+    assert_eq!(format_bearing(my_val, 0), "N 89°59'59\" W");
+    let my_val = parse_slope("4:1").unwrap();
+    assert_eq!(format_slope_percent(my_val, 1), "25.0%");
+}
+
+#[test]
+fn units_are_grouped_by_category() {
+    let t = conversions::table();
+    let all = t.units();
+    assert!(all.windows(2).all(|w| w[0] < w[1]));
+    let lengths = t.units_in(Category::Length);
+    for id in ["foot", "meter", "mile", "foot (us survey)"].iter() {
+        assert!(lengths.contains(id), "{} is not a length", id);
+    }
+    // every unit in a category converts to its SI unit
+    for c in Category::all() {
+        for id in t.units_in(c) {
+            assert!(t.factor(id, c.base()).is_ok(), "{} to {}", id, c.base());
+            assert!(all.contains(&id));
+        }
+    }
+    assert_eq!(t.category("btu").unwrap(), Some(Category::Energy));
+    assert_eq!(t.category("kip-ft").unwrap(), Some(Category::Moment));
+    assert!(t.neighbors("acre").unwrap().contains(&"square meter"));
+    let e = t.factor("btu", "kip").unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "cannot convert \"btu\" (energy) to \"kip\" (force)"
+    );
+}
+
+#[test]
+fn foot_pounds_are_moments() {
+    let t = conversions::table();
+    assert_eq!(t.category("ft-lb").unwrap(), Some(Category::Moment));
+    let my_val = t.factor("ft-lb", "lb-ft").unwrap();
+    assert!((1.0 - my_val).abs() < 1.0e-12);
+    let my_val = t.factor("kip-ft", "ft-lb").unwrap();
+    assert!((1000.0 - my_val).abs() < 1.0e-9);
+    let my_val = t.factor("N-m", "ft-lb").unwrap();
+    assert!((0.737_562_149_3 - my_val).abs() < 1.0e-9);
+    let c = t.conversion("ft-lb", "kip-in").unwrap();
+    assert_eq!(c.path, vec!["foot-pound", "newton-meter", "kip-inch"]);
+    assert!((0.012 - c.factor).abs() < 1.0e-12);
+    let e = t.factor("btu", "kip-ft").unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "cannot convert \"btu\" (energy) to \"kip-foot\" (moment)"
+    );
+}
+
+#[test]
+fn every_listed_unit_resolves() {
+    let t = conversions::table();
+    for id in t.units() {
+        assert_eq!(t.canonical(id).unwrap(), id);
+        assert!(t.category(id).is_ok(), "{}", id);
+        assert!(t.neighbors(id).is_ok(), "{}", id);
+        assert!(t.definition(id).is_ok(), "{}", id);
+    }
+}